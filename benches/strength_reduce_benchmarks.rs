@@ -1,3 +1,5 @@
+// To measure the WebAssembly code paths, build for a WASI target and run the benchmarks under wasmtime:
+// `rustup +nightly target add wasm32-wasip1`, then `cargo +nightly bench --target wasm32-wasip1`. The runner is configured in .cargo/config.toml
 #![feature(test)]
 extern crate test;
 extern crate strength_reduce;
@@ -70,7 +72,7 @@ macro_rules! bench_unsigned {
 		}
 
 		fn gen_numerators() -> Vec<$primitive_type> {
-			test::black_box((0..std::$primitive_type::MAX).rev().cycle().take(REPETITIONS).collect::<Vec<$primitive_type>>())
+			test::black_box((0..$primitive_type::MAX).rev().cycle().take(REPETITIONS).collect::<Vec<$primitive_type>>())
 		}
 
 		#[bench]
@@ -128,6 +130,13 @@ macro_rules! bench_unsigned {
 			let numerators = gen_numerators();
 			b.iter(|| { test::black_box(compute_repeated_divrem(&numerators, reduced_divisor)); });
 		}
+
+		#[bench]
+		fn repeated_divrem_reduced_odd(b: &mut test::Bencher) {
+			let reduced_divisor = strength_reduce::$struct_name::new(7);
+			let numerators = gen_numerators();
+			b.iter(|| { test::black_box(compute_repeated_divrem(&numerators, reduced_divisor)); });
+		}
 		
 		#[bench]
 		fn single_division_reduced_power2(b: &mut test::Bencher) {
@@ -135,9 +144,17 @@ macro_rules! bench_unsigned {
 			b.iter(|| { test::black_box(compute_single_division(&divisors)); });
 		}
 
+		#[bench]
+		fn single_division_reduced_random(b: &mut test::Bencher) {
+			let mut gen = StdRng::seed_from_u64(5673573);
+			let dist = Uniform::new_inclusive($primitive_type::MAX / 2, $primitive_type::MAX);
+			let divisors: Vec<$primitive_type> = test::black_box((0..REPETITIONS).map(|_| dist.sample(&mut gen)).collect());
+			b.iter(|| { test::black_box(compute_single_division(&divisors)); });
+		}
+
 		#[bench]
 		fn single_division_reduced(b: &mut test::Bencher) {
-			let divisors = test::black_box(vec![core::$primitive_type::MAX; REPETITIONS]);
+			let divisors = test::black_box(vec![$primitive_type::MAX; REPETITIONS]);
 			b.iter(|| { test::black_box(compute_single_division(&divisors)); });
 		}
 	)
@@ -165,7 +182,7 @@ mod bench_u64 {
 		assert!(bit_max <= 64);
 
 		let min_value = 1u64 << bit_min;
-		let max_value = 1u64.checked_shl(bit_max).map_or(core::u64::MAX, |v| v - 1);
+		let max_value = 1u64.checked_shl(bit_max).map_or(u64::MAX, |v| v - 1);
 
 		let mut gen = StdRng::seed_from_u64(5673573);
 		let dist = Uniform::new_inclusive(min_value, max_value);
@@ -195,7 +212,7 @@ mod bench_u128 {
 		assert!(bit_max <= 128);
 
 		let min_value = 1u128 << bit_min;
-		let max_value = 1u128.checked_shl(bit_max).map_or(core::u128::MAX, |v| v - 1);
+		let max_value = 1u128.checked_shl(bit_max).map_or(u128::MAX, |v| v - 1);
 
 		let mut gen = StdRng::seed_from_u64(5673573);
 		let dist = Uniform::new_inclusive(min_value, max_value);
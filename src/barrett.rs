@@ -0,0 +1,114 @@
+use ::StrengthReducedU32;
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU64;
+#[cfg(not(strength_reduce_no_u128))]
+use long_division;
+use long_multiplication;
+
+// Barrett reduction: with reciprocal = floor((2^(2N) - 1) / divisor), the upper half of value * reciprocal is floor(value / divisor) or one less than it, for any double-width value,
+// because the reciprocal is at most 1/2^(2N) too small relative to the true reciprocal, and the value is less than 2^(2N). So after one multiply and a multiply-subtract, the remainder is either correct or one divisor too big.
+// The reciprocal is the strength-reduced multiplier minus one, which is the same threshold that the divisibility types store.
+macro_rules! barrett_struct {
+    ($struct_name:ident, $reduced_struct:ident, $primitive_type:ident, $wide_type:ident, $reciprocal_fn:path, $upperbits_fn:path) => (
+        /// Implements Barrett reduction, ie computing remainders of double-width values with a precomputed reciprocal.
+        ///
+        /// The strength-reduced types only divide values as wide as the divisor. This reduces values twice as wide, like the full product of two
+        /// reduced operands, with one double-width high multiply, one multiply-subtract, and a single correction, so it's the building block for chains of modular multiplications.
+        /// It can't compute quotients.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $struct_name {
+            reciprocal: $wide_type,
+            divisor: $primitive_type,
+        }
+        impl $struct_name {
+            /// Creates a new Barrett reduction instance.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `divisor` is 0
+            #[inline]
+            pub fn new(divisor: $primitive_type) -> Self {
+                assert!(divisor > 0);
+                Self { reciprocal: $reciprocal_fn(divisor), divisor }
+            }
+
+            /// Computes `value % divisor`, for any double-width `value`.
+            #[inline]
+            pub fn reduce(&self, value: $wide_type) -> $primitive_type {
+                let divisor = self.divisor as $wide_type;
+                let quotient = $upperbits_fn(value, self.reciprocal);
+                let remainder = value.wrapping_sub(quotient.wrapping_mul(divisor));
+                if remainder >= divisor {
+                    (remainder - divisor) as $primitive_type
+                } else {
+                    remainder as $primitive_type
+                }
+            }
+
+            /// Retrieve the value used to create this struct
+            #[inline]
+            pub fn get(&self) -> $primitive_type {
+                self.divisor
+            }
+        }
+        impl From<$reduced_struct> for $struct_name {
+            /// Converts without redoing the division, since the strength-reduced multiplier is the reciprocal plus one.
+            #[inline]
+            fn from(reduced: $reduced_struct) -> Self {
+                let reciprocal = if reduced.multiplier == 0 {
+                    $wide_type::MAX >> reduced.shift
+                } else {
+                    reduced.multiplier - 1
+                };
+                Self { reciprocal, divisor: reduced.get() }
+            }
+        }
+    )
+}
+
+#[inline]
+fn reciprocal_u32(divisor: u32) -> u64 {
+    u64::MAX / divisor as u64
+}
+
+barrett_struct!(BarrettU32, StrengthReducedU32, u32, u64, reciprocal_u32, long_multiplication::multiply_64_by_64_upperbits);
+#[cfg(not(strength_reduce_no_u128))]
+barrett_struct!(BarrettU64, StrengthReducedU64, u64, u128, long_division::divide_128_max_by_64, long_multiplication::multiply_128_by_128_upperbits);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    macro_rules! barrett_test {
+        ($test_name:ident, $struct_name:ident, $reduced_struct:ident, $primitive_type:ident, $wide_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let mut rng = StdRng::seed_from_u64(1104);
+                let max = $primitive_type::MAX;
+                let wide_max = $wide_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let barrett = $struct_name::new(divisor);
+                    assert_eq!(divisor, barrett.get());
+
+                    let converted = $struct_name::from($reduced_struct::new(divisor));
+                    assert_eq!(barrett.reciprocal, converted.reciprocal);
+
+                    let wide_divisor = divisor as $wide_type;
+                    let products = [max as $wide_type * max as $wide_type, (divisor - 1) as $wide_type * (divisor - 1) as $wide_type];
+                    let edges = [0, 1, wide_divisor - 1, wide_divisor, wide_divisor + 1, wide_max / wide_divisor * wide_divisor, wide_max - 1, wide_max];
+                    let random: [$wide_type; 50] = core::array::from_fn(|_| rng.gen::<$wide_type>() >> rng.gen_range(0..$wide_type::BITS));
+                    for &value in products.iter().chain(&edges).chain(&random) {
+                        assert_eq!((value % wide_divisor) as $primitive_type, barrett.reduce(value), "reduce failed with value: {}, divisor: {}", value, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    barrett_test!(test_barrett_u32, BarrettU32, StrengthReducedU32, u32, u64);
+    #[cfg(not(strength_reduce_no_u128))]
+    barrett_test!(test_barrett_u64, BarrettU64, StrengthReducedU64, u64, u128);
+}
@@ -0,0 +1,159 @@
+use alloc::vec::Vec;
+#[cfg(not(strength_reduce_no_u128))]
+use core::convert::TryInto;
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+#[cfg(not(strength_reduce_no_u128))]
+use long_division;
+
+macro_rules! batch_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl $struct_name {
+            /// Creates a divisor instance for each element of `divisors`.
+            ///
+            /// All of the divisors are checked for zero up front, so the construction loop itself has no panic branches, and the compiler is free to unroll or vectorize it.
+            ///
+            /// # Panics:
+            /// 
+            /// Panics if any element of `divisors` is 0
+            pub fn new_many(divisors: &[$primitive_type]) -> Vec<Self> {
+                assert!(divisors.iter().all(|&divisor| divisor > 0));
+                // we just checked that every divisor is nonzero
+                divisors.iter().map(|&divisor| unsafe { Self::new_unchecked(divisor) }).collect()
+            }
+        }
+    )
+}
+
+// new() computes the 64-bit types' multiplier with hardware division, because Möller and Granlund's multiply-only reciprocal is a long chain of dependent multiplies,
+// which takes longer for a single divisor. The chains for different divisors are independent though, so new_many computes the reciprocals for several divisors in lockstep,
+// and the CPU overlaps their multiplies. There's no 64x64->128 vector multiply on x86 or aarch64, so the lanes are interleaved scalar code rather than SIMD.
+#[cfg(not(strength_reduce_no_u128))]
+const LANES: usize = 4;
+
+// computes floor(2^128 / divisor) + 1 for every divisor, which is the multiplier new() computes for divisors that aren't powers of two
+#[cfg(not(strength_reduce_no_u128))]
+#[inline]
+fn multipliers_u64(divisors: [u64; LANES]) -> [u128; LANES] {
+    let shifts = divisors.map(u64::leading_zeros);
+    let shifted_divisors: [u64; LANES] = core::array::from_fn(|lane| divisors[lane] << shifts[lane]);
+    let reciprocals = shifted_divisors.map(long_division::reciprocal_128_by_64);
+
+    // With the divisor normalized to d' = divisor << shift, the reciprocal is floor((2^128 - 1) / d') - 2^64, so (2^128 - 1) / divisor is (2^64 + reciprocal) << shift,
+    // plus the low bits that come from dividing the reciprocal's remainder, shifted up by the same amount. That remainder is smaller than d', so one 2/1 division finishes it.
+    // For a divisor of 1, the quotient is u128::MAX, so the multiplier wraps around to 0, but powers of two don't use it
+    core::array::from_fn(|lane| {
+        let shift = shifts[lane];
+        let divisor = shifted_divisors[lane];
+        let remainder = u64::MAX.wrapping_sub(reciprocals[lane].wrapping_mul(divisor));
+        let (quotient_lo, _) = long_division::divide_128_by_64_preinv(remainder >> 1 >> (63 - shift), remainder << shift, divisor, reciprocals[lane]);
+        ((((1u128 << 64) | reciprocals[lane] as u128) << shift) + quotient_lo as u128).wrapping_add(1)
+    })
+}
+
+#[cfg(not(strength_reduce_no_u128))]
+macro_rules! batch_impl_u64 {
+    ($struct_name:ident, $primitive_type:ident, $nonzero_type:ident) => (
+        impl $struct_name {
+            /// Creates a divisor instance for each element of `divisors`.
+            ///
+            /// This computes the same multipliers as `new()`, but instead of dividing, it computes them from Möller and Granlund's reciprocal of each divisor,
+            /// four divisors at a time, so that the multiplies for different divisors overlap. The gain depends on how fast the CPU divides: Divisors above 2^32 take several divisions in `new()`,
+            /// so they benefit the most, while small divisors on CPUs with fast dividers build at about the same speed either way.
+            ///
+            /// # Panics:
+            /// 
+            /// Panics if any element of `divisors` is 0
+            pub fn new_many(divisors: &[$primitive_type]) -> Vec<Self> {
+                assert!(divisors.iter().all(|&divisor| divisor > 0));
+
+                let mut reduced_divisors = Vec::with_capacity(divisors.len());
+                let mut chunks = divisors.chunks_exact(LANES);
+                for chunk in &mut chunks {
+                    let chunk: [$primitive_type; LANES] = chunk.try_into().unwrap();
+                    let multipliers = multipliers_u64(chunk.map(|divisor| divisor as u64));
+                    reduced_divisors.extend(chunk.iter().zip(&multipliers).map(|(&divisor, &multiplier)| {
+                        let multiplier = if divisor.is_power_of_two() { 0 } else { multiplier };
+                        // we just checked that every divisor is nonzero
+                        $struct_name::from_parts(multiplier, unsafe { ::core::num::$nonzero_type::new_unchecked(divisor) }, divisor.trailing_zeros() as u8)
+                    }));
+                }
+                reduced_divisors.extend(chunks.remainder().iter().map(|&divisor| unsafe { Self::new_unchecked(divisor) }));
+                reduced_divisors
+            }
+        }
+    )
+}
+
+batch_impl!(StrengthReducedU8, u8);
+batch_impl!(StrengthReducedU16, u16);
+batch_impl!(StrengthReducedU32, u32);
+#[cfg(not(strength_reduce_no_u128))]
+batch_impl_u64!(StrengthReducedU64, u64, NonZeroU64);
+#[cfg(strength_reduce_no_u128)]
+batch_impl!(StrengthReducedU64, u64);
+#[cfg(all(target_pointer_width = "64", not(strength_reduce_no_u128)))]
+batch_impl_u64!(StrengthReducedUsize, usize, NonZeroUsize);
+#[cfg(any(not(target_pointer_width = "64"), strength_reduce_no_u128))]
+batch_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+batch_impl!(StrengthReducedU128, u128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! batch_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+                let reduced_divisors = $struct_name::new_many(&divisors);
+                assert_eq!(divisors.len(), reduced_divisors.len());
+
+                for (&divisor, &reduced_divisor) in divisors.iter().zip(&reduced_divisors) {
+                    assert_eq!(divisor, reduced_divisor.get());
+                    for &numerator in &[0, 1, 2, 99, 100, max / 3, max - 1, max] {
+                        assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+                assert!($struct_name::new_many(&[]).is_empty());
+            }
+        )
+    }
+
+    batch_test!(test_new_many_u8, StrengthReducedU8, u8);
+    batch_test!(test_new_many_u16, StrengthReducedU16, u16);
+    batch_test!(test_new_many_u32, StrengthReducedU32, u32);
+    batch_test!(test_new_many_u64, StrengthReducedU64, u64);
+    batch_test!(test_new_many_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    batch_test!(test_new_many_u128, StrengthReducedU128, u128);
+
+    #[test]
+    #[cfg(not(strength_reduce_no_u128))]
+    fn test_new_many_u64_matches_new() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(998);
+        let mut divisors: Vec<u64> = (1..300).chain((0..64).map(|shift| 1 << shift)).chain((0..64).map(|shift| u64::MAX >> shift)).collect();
+        divisors.extend((0..2000).map(|_| (rng.gen::<u64>() >> rng.gen_range(0..64)).max(1)));
+
+        // skip the first few, so that the chunks don't always line up with the same divisors
+        for offset in 0..4 {
+            let reduced_divisors = StrengthReducedU64::new_many(&divisors[offset..]);
+            for (&divisor, reduced_divisor) in divisors[offset..].iter().zip(reduced_divisors) {
+                assert_eq!(StrengthReducedU64::new(divisor).into_raw_parts(), reduced_divisor.into_raw_parts(), "new_many disagrees with new for divisor: {}", divisor);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_many_zero() {
+        StrengthReducedU64::new_many(&[3, 0, 5]);
+    }
+}
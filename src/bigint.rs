@@ -0,0 +1,73 @@
+use num_bigint::BigUint;
+
+use ::StrengthReducedU64;
+use long_division;
+
+/// Divides `numerator` by `divisor`, and returns `(quotient, remainder)`.
+///
+/// This runs this crate's limb-by-limb long division: Each 64-bit limb takes one multiply-based 128-by-64 division, with a reciprocal
+/// that comes straight from the divisor's stored multiplier, so repeatedly peeling digits off a big number, like when formatting it in decimal, never touches a hardware divide,
+/// and doesn't redo any per-divisor work.
+/// Powers of two are just a shift and a mask.
+///
+/// ```
+/// extern crate num_bigint;
+/// # extern crate strength_reduce;
+/// use num_bigint::BigUint;
+/// use strength_reduce::{divide_biguint, StrengthReducedU64};
+///
+/// # fn main() {
+/// let ten_to_the_19 = StrengthReducedU64::new(10_000_000_000_000_000_000);
+/// let mut value = BigUint::parse_bytes(b"123456789012345678901234567890123456789", 10).unwrap();
+/// let mut chunks = Vec::new();
+/// while value.bits() > 0 {
+///     let (quotient, remainder) = divide_biguint(&value, &ten_to_the_19);
+///     chunks.push(remainder);
+///     value = quotient;
+/// }
+/// assert_eq!(chunks, [1234567890123456789, 2345678901234567890, 1]);
+/// # }
+/// ```
+pub fn divide_biguint(numerator: &BigUint, divisor: &StrengthReducedU64) -> (BigUint, u64) {
+    if divisor.is_shift_only() {
+        let remainder = numerator.iter_u64_digits().next().unwrap_or(0) & (divisor.get() - 1);
+        return (numerator >> divisor.shift(), remainder);
+    }
+
+    // the multiplier minus one is floor((2^128 - 1) / divisor), so shifting it right by the divisor's leading zeros gives floor((2^128 - 1) / shifted_divisor),
+    // which is the shifted divisor's reciprocal plus its implicit 2^64 bit
+    let shift = divisor.get().leading_zeros();
+    let shifted_divisor = divisor.get() << shift;
+    let reciprocal = ((divisor.multiplier - 1) >> shift) as u64;
+
+    let numerator_limbs = numerator.to_u64_digits();
+    let mut quotient_limbs = alloc::vec![0; numerator_limbs.len()];
+    let remainder = long_division::long_division_preinv(&numerator_limbs, shifted_divisor, reciprocal, shift, &mut quotient_limbs);
+
+    let quotient = BigUint::new(quotient_limbs.iter().flat_map(|&limb| [limb as u32, (limb >> 32) as u32]).collect());
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_divide_biguint() {
+        let mut rng = StdRng::seed_from_u64(1101);
+        let edge_divisors = [1, 2, 3, 7, 10, 1 << 32, (1 << 32) + 1, 10_000_000_000_000_000_000, u64::MAX / 2 + 1, u64::MAX - 1, u64::MAX];
+        let random_divisors: [u64; 50] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..64)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU64::new(divisor);
+            for limbs in 0..6 {
+                let digits: alloc::vec::Vec<u32> = (0..limbs * 2).map(|_| rng.gen()).collect();
+                for numerator in &[BigUint::new(digits.clone()), BigUint::new(alloc::vec![u32::MAX; limbs * 2])] {
+                    let expected = (numerator / divisor, (numerator % divisor).iter_u64_digits().next().unwrap_or(0));
+                    assert_eq!(expected, divide_biguint(numerator, &reduced_divisor), "divide_biguint failed with numerator: {}, divisor: {}", numerator, divisor);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,721 @@
+//! Divides whole slices by one divisor, with the fastest kernel that the CPU supports.
+//!
+//! [`div_slice`] and [`rem_slice`] work with every unsigned divisor type. For u8, u16, u32, and u64, they use the SIMD kernels from the [`simd`] module,
+//! which detect the CPU's instruction sets at runtime with the `std` feature, or at compile time without it. The other types, and CPUs without a matching instruction set, use a scalar loop that handles four elements per iteration.
+//! Both functions work in place, so they don't need an output buffer. When a pipeline needs both the quotients and the remainders, [`div_rem_slice`] writes them to two output slices in a single pass.
+//!
+//! ```
+//! use strength_reduce::{bulk, StrengthReducedU32};
+//!
+//! let mut values = [10u32, 25, 30, 99];
+//! bulk::div_slice(&mut values, StrengthReducedU32::new(10));
+//! assert_eq!(values, [1, 2, 3, 9]);
+//! ```
+//!
+//! [`sum_of_quotients`] and [`sum_of_remainders`] add up the quotients or remainders of a slice without an output buffer.
+//!
+//! [`residue_histogram`] counts how many elements of a slice have each remainder. With the `alloc` feature, `sort_by_rem` goes one step further, and stably groups the elements by their remainder.
+//!
+//! [`SliceDivExt`] adds the same operations to slices as methods, so `values.div_all(&divisor)` works without naming this module.
+//!
+//! With the `alloc` feature, `partition_by_rem` scatters the indices of a slice of keys into one buffer per remainder, which is the core of hash-join and shuffle partitioning.
+//!
+//! With the `rayon` feature, `par_div_slice`, `par_rem_slice`, and `par_div_rem_slice` split large slices into chunks, and run the same kernels on each chunk in rayon's thread pool.
+use core::convert::TryInto;
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+use core::slice;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use ::simd;
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+
+/// A divisor type that can divide a whole slice of its primitive type at once.
+///
+/// This is implemented for all of the unsigned divisor types, so that [`div_slice`] and [`rem_slice`] can pick the right kernel for each of them.
+pub trait SliceDivisor: Copy {
+    /// The primitive type that this divisor divides.
+    type Primitive: Copy;
+
+    /// Returns the divisor's value, so that generic code can size its per-remainder buffers.
+    fn divisor(self) -> Self::Primitive;
+
+    /// Divides every element of `values` by `self`, in place.
+    fn div_slice(self, values: &mut [Self::Primitive]);
+
+    /// Replaces every element of `values` with its remainder after dividing by `self`.
+    fn rem_slice(self, values: &mut [Self::Primitive]);
+
+    /// Divides every element of `input` by `self`, writing the quotients to `quotients` and the remainders to `remainders`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `quotients` or `remainders` isn't the same length as `input`
+    fn div_rem_slice(self, input: &[Self::Primitive], quotients: &mut [Self::Primitive], remainders: &mut [Self::Primitive]);
+
+    /// Returns the sum of every element of `values` divided by `self`, wrapping around on overflow.
+    fn sum_of_quotients(self, values: &[Self::Primitive]) -> Self::Primitive;
+
+    /// Returns the sum of the remainders of every element of `values` after dividing by `self`, wrapping around on overflow.
+    fn sum_of_remainders(self, values: &[Self::Primitive]) -> Self::Primitive;
+}
+
+/// Divides every element of `values` by `divisor`, in place.
+#[inline]
+pub fn div_slice<D: SliceDivisor>(values: &mut [D::Primitive], divisor: D) {
+    divisor.div_slice(values)
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`.
+#[inline]
+pub fn rem_slice<D: SliceDivisor>(values: &mut [D::Primitive], divisor: D) {
+    divisor.rem_slice(values)
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`.
+///
+/// Each remainder is derived from its quotient, so this is cheaper than calling [`div_slice`] and [`rem_slice`] on two copies of the input.
+///
+/// ```
+/// use strength_reduce::{bulk, StrengthReducedU32};
+///
+/// let digits = [1234u32, 56, 7890];
+/// let mut quotients = [0; 3];
+/// let mut remainders = [0; 3];
+/// bulk::div_rem_slice(&digits, &mut quotients, &mut remainders, StrengthReducedU32::new(100));
+/// assert_eq!(quotients, [12, 0, 78]);
+/// assert_eq!(remainders, [34, 56, 90]);
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[inline]
+pub fn div_rem_slice<D: SliceDivisor>(input: &[D::Primitive], quotients: &mut [D::Primitive], remainders: &mut [D::Primitive], divisor: D) {
+    divisor.div_rem_slice(input, quotients, remainders)
+}
+
+/// Adds bulk division methods to slices, using the same kernels as [`div_slice`] and [`rem_slice`].
+///
+/// ```
+/// use strength_reduce::bulk::SliceDivExt;
+/// use strength_reduce::StrengthReducedU16;
+///
+/// let divisor = StrengthReducedU16::new(60);
+/// let mut seconds = [59u16, 60, 3599];
+/// seconds.div_all(&divisor);
+/// assert_eq!(seconds, [0, 1, 59]);
+/// ```
+pub trait SliceDivExt {
+    /// The type of the slice's elements.
+    type Item;
+
+    /// Divides every element of this slice by `divisor`, in place.
+    fn div_all<D: SliceDivisor<Primitive = Self::Item>>(&mut self, divisor: &D);
+
+    /// Replaces every element of this slice with its remainder after dividing by `divisor`.
+    fn rem_all<D: SliceDivisor<Primitive = Self::Item>>(&mut self, divisor: &D);
+
+    /// Divides every element of this slice by `divisor`, and returns the quotients and the remainders as two new vectors.
+    #[cfg(feature = "alloc")]
+    fn div_rem_unzip<D: SliceDivisor<Primitive = Self::Item>>(&self, divisor: &D) -> (Vec<Self::Item>, Vec<Self::Item>);
+}
+
+impl<T: Copy> SliceDivExt for [T] {
+    type Item = T;
+
+    #[inline]
+    fn div_all<D: SliceDivisor<Primitive = T>>(&mut self, divisor: &D) {
+        divisor.div_slice(self)
+    }
+
+    #[inline]
+    fn rem_all<D: SliceDivisor<Primitive = T>>(&mut self, divisor: &D) {
+        divisor.rem_slice(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn div_rem_unzip<D: SliceDivisor<Primitive = T>>(&self, divisor: &D) -> (Vec<T>, Vec<T>) {
+        // copying the input is the cheapest way to get initialized buffers of the right length, and the kernel overwrites every element anyway
+        let mut quotients = self.to_vec();
+        let mut remainders = self.to_vec();
+        divisor.div_rem_slice(self, &mut quotients, &mut remainders);
+        (quotients, remainders)
+    }
+}
+
+/// Returns the sum of every element of `values` divided by `divisor`, without storing the quotients anywhere.
+///
+/// The quotients are computed a block at a time with the same kernel as [`div_slice`], and summed while the block is still in L1. Like `wrapping_add`, the sum wraps around on overflow.
+///
+/// ```
+/// use strength_reduce::{bulk, StrengthReducedU32};
+///
+/// let values = [5u32, 10, 15, 22];
+/// assert_eq!(bulk::sum_of_quotients(&values, &StrengthReducedU32::new(5)), 1 + 2 + 3 + 4);
+/// ```
+#[inline]
+pub fn sum_of_quotients<D: SliceDivisor>(values: &[D::Primitive], divisor: &D) -> D::Primitive {
+    divisor.sum_of_quotients(values)
+}
+
+/// Returns the sum of the remainders of every element of `values` after dividing by `divisor`, without storing the remainders anywhere.
+///
+/// The remainders are computed a block at a time with the same kernel as [`rem_slice`], and summed while the block is still in L1. Like `wrapping_add`, the sum wraps around on overflow.
+#[inline]
+pub fn sum_of_remainders<D: SliceDivisor>(values: &[D::Primitive], divisor: &D) -> D::Primitive {
+    divisor.sum_of_remainders(values)
+}
+
+/// Stably reorders `values` so that they're grouped by their remainder after dividing by `divisor`, in ascending order of remainder, like one pass of a counting sort.
+///
+/// Returns the bucket boundaries: a vector with one more element than the divisor, where the elements with remainder `r` end up in `values[boundaries[r]..boundaries[r + 1]]`.
+/// Elements with the same remainder keep their relative order, so this can be used as the partitioning pass of a radix sort.
+///
+/// ```
+/// use strength_reduce::{bulk, StrengthReducedU32};
+///
+/// let mut values = [10u32, 21, 32, 40, 55, 3];
+/// let boundaries = bulk::sort_by_rem(&mut values, &StrengthReducedU32::new(3));
+/// assert_eq!(values, [21, 3, 10, 40, 55, 32]);
+/// assert_eq!(boundaries, [0, 2, 5, 6]);
+/// ```
+///
+/// Reordering stably scatters from a temporary copy of `values`, so this allocates as much memory as `values` takes up, on top of the boundaries.
+/// The boundaries have one element per possible remainder, so the divisor is limited to [`MAX_SORT_DIVISOR`].
+///
+/// # Panics:
+///
+/// Panics if the divisor is greater than [`MAX_SORT_DIVISOR`]
+#[cfg(feature = "alloc")]
+pub fn sort_by_rem<D: SliceDivisor>(values: &mut [D::Primitive], divisor: &D) -> Vec<usize> where D::Primitive: TryInto<usize> {
+    let bucket_count = match divisor.divisor().try_into() {
+        Ok(bucket_count) if bucket_count <= MAX_SORT_DIVISOR => bucket_count,
+        _ => panic!("the divisor must be at most MAX_SORT_DIVISOR"),
+    };
+    let mut boundaries = alloc::vec![0; bucket_count + 1];
+    let first_value = match values.first() {
+        Some(&value) => value,
+        None => return boundaries,
+    };
+
+    // the remainders are computed a block at a time, twice: once to count the buckets, and once to scatter into them, which is cheaper than storing them all.
+    // Every remainder is smaller than the divisor, so it fits in usize
+    let mut remainders = [first_value; BLOCK_SIZE];
+    for block in values.chunks(BLOCK_SIZE) {
+        let remainders = &mut remainders[..block.len()];
+        remainders.copy_from_slice(block);
+        divisor.rem_slice(remainders);
+        for &remainder in remainders.iter() {
+            boundaries[remainder.try_into().unwrap_or(usize::MAX)] += 1;
+        }
+    }
+
+    // turn the counts into the start of each bucket
+    let mut bucket_start = 0;
+    for boundary in boundaries.iter_mut() {
+        let count = *boundary;
+        *boundary = bucket_start;
+        bucket_start += count;
+    }
+
+    // scatter from a copy, using the start of each bucket as its cursor. Afterwards, each cursor has moved to the start of the next bucket, so shifting them up by one restores the boundaries
+    let original = values.to_vec();
+    for block in original.chunks(BLOCK_SIZE) {
+        let remainders = &mut remainders[..block.len()];
+        remainders.copy_from_slice(block);
+        divisor.rem_slice(remainders);
+        for (&value, &remainder) in block.iter().zip(remainders.iter()) {
+            let cursor = &mut boundaries[remainder.try_into().unwrap_or(usize::MAX)];
+            values[*cursor] = value;
+            *cursor += 1;
+        }
+    }
+    boundaries.rotate_right(1);
+    boundaries[0] = 0;
+    boundaries
+}
+
+/// The largest divisor that [`sort_by_rem`] accepts. Its boundaries take `8 * (MAX_SORT_DIVISOR + 1)` bytes on 64-bit targets, ie 128MiB.
+///
+/// On 16-bit targets, it's `1 << 12`, so that the boundaries take 8KiB.
+#[cfg(all(feature = "alloc", not(target_pointer_width = "16")))]
+pub const MAX_SORT_DIVISOR: usize = 1 << 24;
+/// The largest divisor that [`sort_by_rem`] accepts. Its boundaries take `8 * (MAX_SORT_DIVISOR + 1)` bytes on 64-bit targets, ie 128MiB.
+///
+/// On 16-bit targets, it's `1 << 12`, so that the boundaries take 8KiB.
+#[cfg(all(feature = "alloc", target_pointer_width = "16"))]
+pub const MAX_SORT_DIVISOR: usize = 1 << 12;
+
+/// Adds the number of elements of `values` with each remainder after dividing by `divisor` to `counts[remainder]`.
+///
+/// The remainders are computed a block at a time with the same kernel as [`rem_slice`], so counting them is the only per-element work left.
+/// The counts are added to rather than overwritten, so one histogram can be accumulated across several slices.
+///
+/// ```
+/// use strength_reduce::{bulk, StrengthReducedU64};
+///
+/// let values = [3u64, 4, 5, 7, 8, 11];
+/// let mut counts = [0; 4];
+/// bulk::residue_histogram(&values, &StrengthReducedU64::new(4), &mut counts);
+/// assert_eq!(counts, [2, 1, 0, 3]);
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `counts` has fewer elements than the divisor
+pub fn residue_histogram<D: SliceDivisor>(values: &[D::Primitive], divisor: &D, counts: &mut [u64]) where D::Primitive: TryInto<usize> {
+    // a divisor that doesn't fit in usize needs more counts than any slice can hold
+    assert!(counts.len() >= divisor.divisor().try_into().unwrap_or(usize::MAX), "counts must have at least as many elements as the divisor");
+
+    let first_value = match values.first() {
+        Some(&value) => value,
+        None => return,
+    };
+    let mut remainders = [first_value; BLOCK_SIZE];
+    for block in values.chunks(BLOCK_SIZE) {
+        let remainders = &mut remainders[..block.len()];
+        remainders.copy_from_slice(block);
+        divisor.rem_slice(remainders);
+
+        for &remainder in remainders.iter() {
+            // every remainder is smaller than the divisor, so it fits in usize, and the assert above keeps it in bounds
+            counts[remainder.try_into().unwrap_or(usize::MAX)] += 1;
+        }
+    }
+}
+
+// Runs `kernel` on a copy of each block of `values`, and adds up the results. Summing a buffer of primitives is simple enough for the compiler to vectorize
+#[inline]
+fn sum_in_blocks<T: Copy + Default>(values: &[T], wrapping_add: fn(T, T) -> T, mut kernel: impl FnMut(&mut [T])) -> T {
+    let mut buffer = [T::default(); BLOCK_SIZE];
+    let mut sum = T::default();
+    for block in values.chunks(BLOCK_SIZE) {
+        let buffer = &mut buffer[..block.len()];
+        buffer.copy_from_slice(block);
+        kernel(buffer);
+        sum = buffer.iter().fold(sum, |sum, &value| wrapping_add(sum, value));
+    }
+    sum
+}
+
+// Helpers that consume the quotients or remainders right away compute them this many at a time, into a buffer that stays in L1 while we read it back
+const BLOCK_SIZE: usize = 256;
+
+/// Appends the index of every element of `keys` to `partitions[key % divisor]`, so that each partition ends up with the indices of its keys, in ascending order.
+///
+/// The remainders are computed a block at a time with the same kernel as [`rem_slice`], so scattering the indices is the only per-key work left.
+/// The partitions are appended to rather than cleared, so the same buffers can be reused across several calls, as long as the caller clears them in between.
+///
+/// ```
+/// use strength_reduce::{bulk, StrengthReducedU64};
+///
+/// let keys = [10u64, 21, 32, 40, 55];
+/// let mut partitions = vec![Vec::new(); 3];
+/// bulk::partition_by_rem(&keys, &StrengthReducedU64::new(3), &mut partitions);
+/// assert_eq!(partitions, [vec![1], vec![0, 3, 4], vec![2]]);
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `partitions` has fewer elements than the divisor, or if `keys` is too long for its indices to fit in a `u32`
+#[cfg(feature = "alloc")]
+pub fn partition_by_rem<D: SliceDivisor>(keys: &[D::Primitive], divisor: &D, partitions: &mut [Vec<u32>]) where D::Primitive: TryInto<usize> {
+    assert!(keys.len() as u64 <= u32::MAX as u64 + 1, "keys must have at most 2^32 elements");
+    // a divisor that doesn't fit in usize needs more partitions than any slice can hold
+    assert!(partitions.len() >= divisor.divisor().try_into().unwrap_or(usize::MAX), "partitions must have at least as many elements as the divisor");
+
+    let first_key = match keys.first() {
+        Some(&key) => key,
+        None => return,
+    };
+    let mut remainders = [first_key; BLOCK_SIZE];
+    for (block_index, block) in keys.chunks(BLOCK_SIZE).enumerate() {
+        let remainders = &mut remainders[..block.len()];
+        remainders.copy_from_slice(block);
+        divisor.rem_slice(remainders);
+
+        let block_start = block_index * BLOCK_SIZE;
+        for (offset, &remainder) in remainders.iter().enumerate() {
+            // every remainder is smaller than the divisor, so it fits in usize, and the assert above keeps it in bounds
+            let partition = remainder.try_into().unwrap_or(usize::MAX);
+            partitions[partition].push((block_start + offset) as u32);
+        }
+    }
+}
+
+// Each task handles at least this many elements, so that splitting the work up costs much less than the division itself
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 1 << 14;
+
+/// Divides every element of `values` by `divisor`, in place, spreading the work across rayon's thread pool.
+///
+/// Each thread runs the same kernel as [`div_slice`] on its own chunk. This only pays off for large slices: Below a few tens of thousands of elements, use [`div_slice`] instead.
+#[cfg(feature = "rayon")]
+pub fn par_div_slice<D: SliceDivisor + Sync>(values: &mut [D::Primitive], divisor: D) where D::Primitive: Send {
+    values.par_chunks_mut(PARALLEL_CHUNK_SIZE).for_each(|chunk| divisor.div_slice(chunk));
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`, spreading the work across rayon's thread pool.
+///
+/// Each thread runs the same kernel as [`rem_slice`] on its own chunk. This only pays off for large slices: Below a few tens of thousands of elements, use [`rem_slice`] instead.
+#[cfg(feature = "rayon")]
+pub fn par_rem_slice<D: SliceDivisor + Sync>(values: &mut [D::Primitive], divisor: D) where D::Primitive: Send {
+    values.par_chunks_mut(PARALLEL_CHUNK_SIZE).for_each(|chunk| divisor.rem_slice(chunk));
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`, spreading the work across rayon's thread pool.
+///
+/// Each thread runs the same kernel as [`div_rem_slice`] on its own chunk.
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[cfg(feature = "rayon")]
+pub fn par_div_rem_slice<D: SliceDivisor + Sync>(input: &[D::Primitive], quotients: &mut [D::Primitive], remainders: &mut [D::Primitive], divisor: D) where D::Primitive: Send + Sync {
+    assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+    assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+
+    input.par_chunks(PARALLEL_CHUNK_SIZE).zip(quotients.par_chunks_mut(PARALLEL_CHUNK_SIZE)).zip(remainders.par_chunks_mut(PARALLEL_CHUNK_SIZE)).for_each(|((input, quotients), remainders)| divisor.div_rem_slice(input, quotients, remainders));
+}
+
+// u8, u16, u32, and u64 go through the SIMD kernels' dispatch, and the other types go straight to the scalar loops that those kernels fall back to
+macro_rules! slice_divisor {
+    ($struct_name:ident, $primitive_type:ident, $div_fn:ident, $rem_fn:ident, $div_rem_fn:ident) => (
+        impl SliceDivisor for $struct_name {
+            type Primitive = $primitive_type;
+
+            #[inline]
+            fn divisor(self) -> $primitive_type {
+                self.get()
+            }
+
+            #[inline]
+            fn div_slice(self, values: &mut [$primitive_type]) {
+                simd::$div_fn(values, self)
+            }
+
+            #[inline]
+            fn rem_slice(self, values: &mut [$primitive_type]) {
+                simd::$rem_fn(values, self)
+            }
+
+            #[inline]
+            fn div_rem_slice(self, input: &[$primitive_type], quotients: &mut [$primitive_type], remainders: &mut [$primitive_type]) {
+                assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+                assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+                simd::$div_rem_fn(input, quotients, remainders, self)
+            }
+
+            #[inline]
+            fn sum_of_quotients(self, values: &[$primitive_type]) -> $primitive_type {
+                sum_in_blocks(values, $primitive_type::wrapping_add, |block| simd::$div_fn(block, self))
+            }
+
+            #[inline]
+            fn sum_of_remainders(self, values: &[$primitive_type]) -> $primitive_type {
+                sum_in_blocks(values, $primitive_type::wrapping_add, |block| simd::$rem_fn(block, self))
+            }
+        }
+    )
+}
+
+// usize has the same layout as one of the fixed-width types, so we reinterpret the slice, and rebuild the divisor from its parts, which doesn't divide like new() would
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+macro_rules! slice_divisor_usize {
+    ($fixed_struct:ident, $fixed_type:ident, $fixed_nonzero:ident, $div_fn:ident, $rem_fn:ident, $div_rem_fn:ident) => (
+        impl StrengthReducedUsize {
+            #[inline]
+            fn to_fixed_width(self) -> $fixed_struct {
+                // Safety: the divisor is already nonzero, and usize is the same width as the fixed-width type
+                $fixed_struct::from_parts(self.multiplier, unsafe { ::core::num::$fixed_nonzero::new_unchecked(self.divisor.get() as $fixed_type) }, self.shift)
+            }
+        }
+
+        impl SliceDivisor for StrengthReducedUsize {
+            type Primitive = usize;
+
+            #[inline]
+            fn divisor(self) -> usize {
+                self.get()
+            }
+
+            #[inline]
+            fn div_slice(self, values: &mut [usize]) {
+                simd::$div_fn(to_fixed_width_slice(values), self.to_fixed_width())
+            }
+
+            #[inline]
+            fn rem_slice(self, values: &mut [usize]) {
+                simd::$rem_fn(to_fixed_width_slice(values), self.to_fixed_width())
+            }
+
+            #[inline]
+            fn div_rem_slice(self, input: &[usize], quotients: &mut [usize], remainders: &mut [usize]) {
+                simd::$div_rem_fn(to_fixed_width_input(input), to_fixed_width_slice(quotients), to_fixed_width_slice(remainders), self.to_fixed_width())
+            }
+
+            #[inline]
+            fn sum_of_quotients(self, values: &[usize]) -> usize {
+                let divisor = self.to_fixed_width();
+                sum_in_blocks(to_fixed_width_input(values), $fixed_type::wrapping_add, |block| simd::$div_fn(block, divisor)) as usize
+            }
+
+            #[inline]
+            fn sum_of_remainders(self, values: &[usize]) -> usize {
+                let divisor = self.to_fixed_width();
+                sum_in_blocks(to_fixed_width_input(values), $fixed_type::wrapping_add, |block| simd::$rem_fn(block, divisor)) as usize
+            }
+        }
+
+        #[inline]
+        fn to_fixed_width_input(values: &[usize]) -> &[$fixed_type] {
+            // Safety: same as to_fixed_width_slice, and the shared borrow keeps the values from changing while we read them
+            unsafe { slice::from_raw_parts(values.as_ptr() as *const $fixed_type, values.len()) }
+        }
+
+        #[inline]
+        fn to_fixed_width_slice(values: &mut [usize]) -> &mut [$fixed_type] {
+            // Safety: usize has the same size and alignment as the fixed-width type, and the new slice borrows the old one mutably, so nothing else can observe it as usize in the meantime
+            unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut $fixed_type, values.len()) }
+        }
+    )
+}
+
+slice_divisor!(StrengthReducedU8, u8, div_slice_u8, rem_slice_u8, div_rem_slice_u8);
+slice_divisor!(StrengthReducedU16, u16, div_slice_u16, rem_slice_u16, div_rem_slice_u16);
+slice_divisor!(StrengthReducedU32, u32, div_slice_u32, rem_slice_u32, div_rem_slice_u32);
+slice_divisor!(StrengthReducedU64, u64, div_slice_u64, rem_slice_u64, div_rem_slice_u64);
+#[cfg(not(strength_reduce_no_u128))]
+slice_divisor!(StrengthReducedU128, u128, div_slice_u128_scalar, rem_slice_u128_scalar, div_rem_slice_u128_scalar);
+
+#[cfg(target_pointer_width = "32")]
+slice_divisor_usize!(StrengthReducedU32, u32, NonZeroU32, div_slice_u32, rem_slice_u32, div_rem_slice_u32);
+#[cfg(target_pointer_width = "64")]
+slice_divisor_usize!(StrengthReducedU64, u64, NonZeroU64, div_slice_u64, rem_slice_u64, div_rem_slice_u64);
+// on 16-bit targets, StrengthReducedUsize doesn't store the same parts as the fixed-width type
+#[cfg(target_pointer_width = "16")]
+slice_divisor!(StrengthReducedUsize, usize, div_slice_usize_scalar, rem_slice_usize_scalar, div_rem_slice_usize_scalar);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! bulk_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+
+                    // enough numerators for a couple of SIMD chunks and a scalar tail
+                    let values: [$primitive_type; 21] = [0, 1, 2, 3, 7, 8, 9, 99, 100, 101, divisor - 1, divisor, divisor.wrapping_add(1), max / 3, max / 2, max / 2 + 1, max - max % divisor, max - 3, max - 2, max - 1, max];
+
+                    let mut quotients = values;
+                    div_slice(&mut quotients, reduced_divisor);
+                    let mut remainders = values;
+                    rem_slice(&mut remainders, reduced_divisor);
+
+                    for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+                        assert_eq!(value / divisor, quotient, "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                        assert_eq!(value % divisor, remainder, "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+                    }
+
+                    assert_eq!(values.iter().fold(0, |sum: $primitive_type, &value| sum.wrapping_add(value / divisor)), sum_of_quotients(&values, &reduced_divisor));
+                    assert_eq!(values.iter().fold(0, |sum: $primitive_type, &value| sum.wrapping_add(value % divisor)), sum_of_remainders(&values, &reduced_divisor));
+
+                    let mut fused_quotients = [0; 21];
+                    let mut fused_remainders = [0; 21];
+                    div_rem_slice(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+                    assert_eq!(quotients, fused_quotients);
+                    assert_eq!(remainders, fused_remainders);
+                }
+            }
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_partition_by_rem() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1073);
+        // a few full blocks, and a partial block at the end
+        let keys: Vec<u32> = (0..(BLOCK_SIZE * 3 + 17)).map(|_| rng.gen()).collect();
+        for &divisor in &[1, 2, 7, 16, 1000] {
+            let mut partitions = alloc::vec![Vec::new(); divisor as usize];
+            partition_by_rem(&keys, &StrengthReducedU32::new(divisor), &mut partitions);
+
+            assert_eq!(keys.len(), partitions.iter().map(Vec::len).sum::<usize>());
+            for (partition, indices) in partitions.iter().enumerate() {
+                assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+                for &index in indices {
+                    assert_eq!(partition as u32, keys[index as usize] % divisor, "Partition failed with key: {}, divisor: {}", keys[index as usize], divisor);
+                }
+            }
+        }
+
+        let mut partitions = alloc::vec![Vec::new(); 3];
+        partition_by_rem(&[] as &[u64], &StrengthReducedU64::new(3), &mut partitions);
+        assert!(partitions.iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_partition_by_rem_too_few_partitions() {
+        // none of these keys has remainder 2, so only the up-front check catches the missing partition
+        partition_by_rem(&[0u8, 1, 3], &StrengthReducedU8::new(3), &mut alloc::vec![Vec::new(); 2]);
+    }
+
+    #[test]
+    fn test_sums_across_blocks() {
+        // a few full blocks, and a partial block at the end
+        let values: [u32; BLOCK_SIZE * 3 + 17] = core::array::from_fn(|index| (index as u32).wrapping_mul(0x9E37_79B9));
+        for &divisor in &[1, 3, 16, 1000, u32::MAX] {
+            let reduced_divisor = StrengthReducedU32::new(divisor);
+            assert_eq!(values.iter().fold(0u32, |sum, &value| sum.wrapping_add(value / divisor)), sum_of_quotients(&values, &reduced_divisor));
+            assert_eq!(values.iter().fold(0u32, |sum, &value| sum.wrapping_add(value % divisor)), sum_of_remainders(&values, &reduced_divisor));
+        }
+        assert_eq!(0, sum_of_quotients(&[], &StrengthReducedU64::new(7)));
+    }
+
+    #[test]
+    fn test_residue_histogram() {
+        // a few full blocks, and a partial block at the end
+        let values: [u64; BLOCK_SIZE * 3 + 17] = core::array::from_fn(|index| (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        for &divisor in &[1, 2, 7, 16, 100] {
+            let mut counts = [0; 100];
+            residue_histogram(&values, &StrengthReducedU64::new(divisor), &mut counts);
+
+            let mut expected = [0; 100];
+            for &value in &values {
+                expected[(value % divisor) as usize] += 1;
+            }
+            assert_eq!(&expected[..], &counts[..]);
+        }
+
+        // the counts accumulate across calls
+        let mut counts = [1, 2, 3];
+        residue_histogram(&[0u8, 1, 2, 3], &StrengthReducedU8::new(3), &mut counts);
+        assert_eq!([3, 3, 4], counts);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_residue_histogram_too_few_counts() {
+        // none of these values has remainder 2, so only the up-front check catches the missing count
+        residue_histogram(&[0u16, 1, 3], &StrengthReducedU16::new(3), &mut [0; 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sort_by_rem() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1078);
+        let original: Vec<u64> = (0..1000).map(|_| rng.gen()).collect();
+        for &divisor in &[1, 2, 7, 16, 1000] {
+            let mut values = original.clone();
+            let boundaries = sort_by_rem(&mut values, &StrengthReducedU64::new(divisor));
+            assert_eq!(divisor as usize + 1, boundaries.len());
+            assert_eq!(0, boundaries[0]);
+            assert_eq!(values.len(), boundaries[divisor as usize]);
+
+            // every bucket holds exactly the values with its remainder, in their original order
+            for (remainder, bucket) in boundaries.windows(2).enumerate() {
+                let expected: Vec<u64> = original.iter().copied().filter(|&value| value % divisor == remainder as u64).collect();
+                assert_eq!(expected, values[bucket[0]..bucket[1]]);
+            }
+        }
+
+        let mut empty: [u8; 0] = [];
+        assert_eq!(sort_by_rem(&mut empty, &StrengthReducedU8::new(3)), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_sort_by_rem_divisor_too_large() {
+        sort_by_rem(&mut [1u64, 2, 3], &StrengthReducedU64::new(MAX_SORT_DIVISOR as u64 + 1));
+    }
+
+    #[test]
+    fn test_slice_div_ext() {
+        let values = [0u32, 1, 9, 10, 11, 99, 100, 12345, u32::MAX];
+        let divisor = StrengthReducedU32::new(10);
+
+        let mut quotients = values;
+        quotients.div_all(&divisor);
+        let mut remainders = values;
+        remainders.rem_all(&divisor);
+        for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+            assert_eq!(value / 10, quotient);
+            assert_eq!(value % 10, remainder);
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let (unzipped_quotients, unzipped_remainders) = values.div_rem_unzip(&divisor);
+            assert_eq!(&quotients[..], &unzipped_quotients[..]);
+            assert_eq!(&remainders[..], &unzipped_remainders[..]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_rem_length_mismatch() {
+        div_rem_slice(&[1u8, 2, 3], &mut [0; 2], &mut [0; 3], StrengthReducedU8::new(3));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parallel() {
+        use std::vec::Vec;
+
+        // enough elements for several chunks, and a partial chunk at the end
+        let values: Vec<u64> = (0..(PARALLEL_CHUNK_SIZE as u64 * 5 + 123)).map(|value| value.wrapping_mul(0x9E37_79B9_7F4A_7C15)).collect();
+        for &divisor in &[1, 7, 1 << 20, 1000003, u64::MAX / 2 + 1] {
+            let reduced_divisor = StrengthReducedU64::new(divisor);
+
+            let mut quotients = values.clone();
+            par_div_slice(&mut quotients, reduced_divisor);
+            let mut remainders = values.clone();
+            par_rem_slice(&mut remainders, reduced_divisor);
+
+            let mut fused_quotients = std::vec![0; values.len()];
+            let mut fused_remainders = std::vec![0; values.len()];
+            par_div_rem_slice(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+
+            for (index, &value) in values.iter().enumerate() {
+                assert_eq!(value / divisor, quotients[index], "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, remainders[index], "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value / divisor, fused_quotients[index], "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, fused_remainders[index], "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    #[should_panic]
+    fn test_parallel_length_mismatch() {
+        par_div_rem_slice(&[1u32, 2, 3], &mut [0; 3], &mut [0; 2], StrengthReducedU32::new(3));
+    }
+
+    bulk_test!(test_bulk_u8, StrengthReducedU8, u8);
+    bulk_test!(test_bulk_u16, StrengthReducedU16, u16);
+    bulk_test!(test_bulk_u32, StrengthReducedU32, u32);
+    bulk_test!(test_bulk_u64, StrengthReducedU64, u64);
+    bulk_test!(test_bulk_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    bulk_test!(test_bulk_u128, StrengthReducedU128, u128);
+}
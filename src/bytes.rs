@@ -0,0 +1,169 @@
+// A field of a divisor's precomputed state, which can be written to a byte array in either byte order
+pub(crate) trait StateField: Copy {
+    const SIZE: usize;
+
+    fn write_bytes(self, out: &mut [u8], big_endian: bool);
+}
+
+macro_rules! state_field_impl {
+    ($primitive_type:ident) => (
+        impl StateField for $primitive_type {
+            const SIZE: usize = core::mem::size_of::<$primitive_type>();
+
+            #[inline]
+            fn write_bytes(self, out: &mut [u8], big_endian: bool) {
+                out.copy_from_slice(&if big_endian { self.to_be_bytes() } else { self.to_le_bytes() });
+            }
+        }
+    )
+}
+
+state_field_impl!(u8);
+state_field_impl!(u16);
+state_field_impl!(u32);
+state_field_impl!(u64);
+state_field_impl!(u128);
+state_field_impl!(usize);
+state_field_impl!(i8);
+state_field_impl!(i16);
+state_field_impl!(i32);
+state_field_impl!(i64);
+state_field_impl!(i128);
+state_field_impl!(isize);
+
+impl StateField for bool {
+    const SIZE: usize = 1;
+
+    #[inline]
+    fn write_bytes(self, out: &mut [u8], _big_endian: bool) {
+        out[0] = self as u8;
+    }
+}
+
+// The divisor always comes first, so that reading the bytes back only has to find the divisor, then recompute the rest and check that it matches.
+// Every field is derived from the divisor, so that check rejects any bytes that `new()` wouldn't have produced, and a divisor that passes it is safe to use
+macro_rules! state_bytes_impl {
+    ($struct_name:ident, $primitive_type:ident, [$($field:ident: $field_type:ty),*]) => (
+        impl ::bytes::StateField for $struct_name {
+            const SIZE: usize = $struct_name::STATE_BYTES;
+
+            #[inline]
+            fn write_bytes(self, out: &mut [u8], big_endian: bool) {
+                out.copy_from_slice(&self.to_bytes(big_endian));
+            }
+        }
+
+        impl $struct_name {
+            /// The length of the byte arrays returned by `to_le_bytes` and `to_be_bytes`.
+            pub const STATE_BYTES: usize = core::mem::size_of::<$primitive_type>() $(+ <$field_type as ::bytes::StateField>::SIZE)*;
+
+            /// Returns the complete precomputed state of this divisor as a byte array in little-endian byte order.
+            ///
+            /// The divisor comes first, followed by the precomputed constants. The layout is only meaningful to `from_le_bytes` in a build of the same version of this crate,
+            /// with the same features, on a target with the same pointer width, so it's suited to sharing divisors between processes, but not to long-term storage.
+            #[inline]
+            pub fn to_le_bytes(self) -> [u8; Self::STATE_BYTES] {
+                self.to_bytes(false)
+            }
+
+            /// Returns the complete precomputed state of this divisor as a byte array in big-endian byte order.
+            ///
+            /// See `to_le_bytes` for the caveats about the layout.
+            #[inline]
+            pub fn to_be_bytes(self) -> [u8; Self::STATE_BYTES] {
+                self.to_bytes(true)
+            }
+
+            /// Restores a divisor from the output of `to_le_bytes`.
+            ///
+            /// Returns `None` if the divisor is 0, or if the precomputed constants don't match the divisor. The check recomputes them, so it costs as much as `new()`.
+            #[inline]
+            pub fn from_le_bytes(bytes: [u8; Self::STATE_BYTES]) -> Option<Self> {
+                Self::from_bytes(bytes, false)
+            }
+
+            /// Restores a divisor from the output of `to_be_bytes`.
+            ///
+            /// Returns `None` if the divisor is 0, or if the precomputed constants don't match the divisor. The check recomputes them, so it costs as much as `new()`.
+            #[inline]
+            pub fn from_be_bytes(bytes: [u8; Self::STATE_BYTES]) -> Option<Self> {
+                Self::from_bytes(bytes, true)
+            }
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn to_bytes(self, big_endian: bool) -> [u8; Self::STATE_BYTES] {
+                let mut bytes = [0; Self::STATE_BYTES];
+                let mut offset = core::mem::size_of::<$primitive_type>();
+                ::bytes::StateField::write_bytes(self.get(), &mut bytes[..offset], big_endian);
+                $(
+                    let end = offset + <$field_type as ::bytes::StateField>::SIZE;
+                    ::bytes::StateField::write_bytes(self.$field, &mut bytes[offset..end], big_endian);
+                    offset = end;
+                )*
+                bytes
+            }
+
+            #[inline]
+            fn from_bytes(bytes: [u8; Self::STATE_BYTES], big_endian: bool) -> Option<Self> {
+                const DIVISOR_BYTES: usize = core::mem::size_of::<$primitive_type>();
+                let mut divisor_bytes = [0; DIVISOR_BYTES];
+                divisor_bytes.copy_from_slice(&bytes[..DIVISOR_BYTES]);
+                let divisor = if big_endian { $primitive_type::from_be_bytes(divisor_bytes) } else { $primitive_type::from_le_bytes(divisor_bytes) };
+
+                let reduced = Self::try_new(divisor)?;
+                if reduced.to_bytes(big_endian) == bytes {
+                    Some(reduced)
+                } else {
+                    None
+                }
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+    use ::{StrengthReducedI8, StrengthReducedI32, StrengthReducedI64};
+    #[cfg(not(strength_reduce_no_u128))]
+    use ::StrengthReducedU128;
+
+    macro_rules! state_bytes_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                for &divisor in &[1, 2, 3, 7, 10, 64, max / 2, max / 2 + 1, max - 1, max] {
+                    let reduced_divisor = $struct_name::new(divisor);
+
+                    let le_bytes = reduced_divisor.to_le_bytes();
+                    let be_bytes = reduced_divisor.to_be_bytes();
+                    assert_eq!(le_bytes[..core::mem::size_of::<$primitive_type>()], divisor.to_le_bytes());
+                    assert_eq!(be_bytes[..core::mem::size_of::<$primitive_type>()], divisor.to_be_bytes());
+                    assert_eq!(Some(divisor), $struct_name::from_le_bytes(le_bytes).map(|d| d.get()), "Round trip failed with divisor: {}", divisor);
+                    assert_eq!(Some(divisor), $struct_name::from_be_bytes(be_bytes).map(|d| d.get()), "Round trip failed with divisor: {}", divisor);
+
+                    // flipping any bit of the precomputed state has to be caught, even when the divisor itself still parses
+                    for index in core::mem::size_of::<$primitive_type>()..$struct_name::STATE_BYTES {
+                        let mut corrupted = le_bytes;
+                        corrupted[index] ^= 1;
+                        assert!($struct_name::from_le_bytes(corrupted).is_none(), "Corrupted byte {} accepted with divisor: {}", index, divisor);
+                    }
+                }
+                assert!($struct_name::from_le_bytes([0; $struct_name::STATE_BYTES]).is_none());
+            }
+        )
+    }
+
+    state_bytes_test!(test_state_bytes_u8, StrengthReducedU8, u8);
+    state_bytes_test!(test_state_bytes_u16, StrengthReducedU16, u16);
+    state_bytes_test!(test_state_bytes_u32, StrengthReducedU32, u32);
+    state_bytes_test!(test_state_bytes_u64, StrengthReducedU64, u64);
+    state_bytes_test!(test_state_bytes_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    state_bytes_test!(test_state_bytes_u128, StrengthReducedU128, u128);
+    state_bytes_test!(test_state_bytes_i8, StrengthReducedI8, i8);
+    state_bytes_test!(test_state_bytes_i32, StrengthReducedI32, i32);
+    state_bytes_test!(test_state_bytes_i64, StrengthReducedI64, i64);
+}
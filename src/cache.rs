@@ -0,0 +1,78 @@
+use core::cell::Cell;
+
+use ::StrengthReducedU64;
+
+// A small direct-mapped cache: each divisor can only live in one slot, picked by hashing it, so a lookup is a single compare.
+// Every slot starts out holding a divisor of 1, which is a valid entry for divisor 1, so we don't need a separate "empty" flag.
+const CACHE_SLOTS: usize = 16;
+
+std::thread_local! {
+    static CACHE: [Cell<StrengthReducedU64>; CACHE_SLOTS] = const { [const { Cell::new(StrengthReducedU64::new(1)) }; CACHE_SLOTS] };
+}
+
+#[inline]
+fn cache_slot(divisor: u64) -> usize {
+    // fibonacci hashing: the top bits of the product depend on every bit of the divisor
+    (divisor.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (64 - CACHE_SLOTS.trailing_zeros())) as usize
+}
+
+impl StrengthReducedU64 {
+    /// Returns a divisor instance for `divisor`, reusing a previous instance from a small thread-local cache if possible.
+    ///
+    /// This is for code that can't keep a divisor instance around between calls, like trait callbacks. If you can store the instance yourself, that's always faster.
+    ///
+    /// # Panics:
+    /// 
+    /// Panics if `divisor` is 0
+    #[inline]
+    pub fn cached(divisor: u64) -> Self {
+        let slot = cache_slot(divisor);
+        CACHE.with(|cache| {
+            let entry = cache[slot].get();
+            if entry.get() == divisor {
+                entry
+            } else {
+                // none of the slots can hold a zero divisor, so this is where a zero divisor panics
+                let reduced = Self::new(divisor);
+                cache[slot].set(reduced);
+                reduced
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_cached() {
+        let max = u64::MAX;
+        let divisors = [1, 2, 3, 7, 10, 100, 1000, 12345, max / 2, max - 1, max];
+
+        // run twice, so that the second pass hits the cache
+        for _ in 0..2 {
+            for &divisor in &divisors {
+                let reduced_divisor = StrengthReducedU64::cached(divisor);
+                assert_eq!(divisor, reduced_divisor.get());
+                for &numerator in &[0, 1, 99, 100, 101, max / 3, max - 1, max] {
+                    assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                    assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_slot() {
+        for divisor in 1..1000 {
+            assert!(cache_slot(divisor) < CACHE_SLOTS);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cached_zero() {
+        StrengthReducedU64::cached(0);
+    }
+}
@@ -0,0 +1,169 @@
+//! A C API for the `u32` and `u64` divisors, for C and C++ projects.
+//!
+//! Each divisor is a `#[repr(C)]` struct that holds the same precomputed constants as the Rust type, so it can live on the C side's stack or in its own data structures,
+//! and every function takes it by value. The structs are plain data, and C code should treat their fields as opaque: Changing them makes every division return the wrong result.
+//!
+//! Running `cbindgen` on this crate with the `capi` feature generates a header for these items. To link against them, build the crate as a static or dynamic library,
+//! for example with `cargo rustc --release --features capi,std --crate-type staticlib`. The `std` feature provides the panic handler that a standalone library needs.
+//!
+//! Like the Rust API, creating a divisor of 0 panics, which aborts the process across the `extern "C"` boundary. So does passing a struct whose divisor is 0.
+use ::{StrengthReducedU32, StrengthReducedU64};
+
+/// A `StrengthReducedU32`, in a layout that C can hold.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct CStrengthReducedU32 {
+    /// The multiplier from `StrengthReducedU32::into_raw_parts`.
+    pub multiplier: u64,
+    /// The divisor that this struct divides by.
+    pub divisor: u32,
+    /// The shift from `StrengthReducedU32::into_raw_parts`.
+    pub shift: u8,
+}
+
+/// A `StrengthReducedU64`, in a layout that C can hold.
+///
+/// The multiplier is split into two 64-bit halves, because C has no portable 128-bit integer. With `--cfg strength_reduce_no_u128`, the upper half is always 0.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct CStrengthReducedU64 {
+    /// The lower 64 bits of the multiplier from `StrengthReducedU64::into_raw_parts`.
+    pub multiplier_lo: u64,
+    /// The upper 64 bits of the multiplier from `StrengthReducedU64::into_raw_parts`.
+    pub multiplier_hi: u64,
+    /// The divisor that this struct divides by.
+    pub divisor: u64,
+    /// The shift from `StrengthReducedU64::into_raw_parts`.
+    pub shift: u8,
+}
+
+/// The quotient and remainder returned by `strength_reduce_u32_div_rem`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CDivRemU32 {
+    /// The quotient.
+    pub quotient: u32,
+    /// The remainder.
+    pub remainder: u32,
+}
+
+/// The quotient and remainder returned by `strength_reduce_u64_div_rem`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CDivRemU64 {
+    /// The quotient.
+    pub quotient: u64,
+    /// The remainder.
+    pub remainder: u64,
+}
+
+impl From<StrengthReducedU32> for CStrengthReducedU32 {
+    #[inline]
+    fn from(divisor: StrengthReducedU32) -> Self {
+        let (multiplier, divisor, shift) = divisor.into_raw_parts();
+        Self{ multiplier, divisor, shift }
+    }
+}
+
+impl From<CStrengthReducedU32> for StrengthReducedU32 {
+    #[inline]
+    fn from(divisor: CStrengthReducedU32) -> Self {
+        assert!(divisor.divisor != 0, "attempt to divide by zero");
+        // Safety: the divisor is nonzero, and the other fields only affect the results, which is documented on the struct
+        unsafe { StrengthReducedU32::from_raw_parts(divisor.multiplier, divisor.divisor, divisor.shift) }
+    }
+}
+
+impl From<StrengthReducedU64> for CStrengthReducedU64 {
+    #[inline]
+    fn from(divisor: StrengthReducedU64) -> Self {
+        let (multiplier, divisor, shift) = divisor.into_raw_parts();
+        #[cfg(not(strength_reduce_no_u128))]
+        let (multiplier_lo, multiplier_hi) = (multiplier as u64, (multiplier >> 64) as u64);
+        #[cfg(strength_reduce_no_u128)]
+        let (multiplier_lo, multiplier_hi) = (multiplier, 0);
+        Self{ multiplier_lo, multiplier_hi, divisor, shift }
+    }
+}
+
+impl From<CStrengthReducedU64> for StrengthReducedU64 {
+    #[inline]
+    fn from(divisor: CStrengthReducedU64) -> Self {
+        assert!(divisor.divisor != 0, "attempt to divide by zero");
+        #[cfg(not(strength_reduce_no_u128))]
+        let multiplier = ((divisor.multiplier_hi as u128) << 64) | divisor.multiplier_lo as u128;
+        #[cfg(strength_reduce_no_u128)]
+        let multiplier = divisor.multiplier_lo;
+        // Safety: the divisor is nonzero, and the other fields only affect the results, which is documented on the struct
+        unsafe { StrengthReducedU64::from_raw_parts(multiplier, divisor.divisor, divisor.shift) }
+    }
+}
+
+macro_rules! capi_impl {
+    ($c_struct:ident, $div_rem_struct:ident, $struct_name:ident, $primitive_type:ident, $new_fn:ident, $div_fn:ident, $rem_fn:ident, $div_rem_fn:ident) => (
+        /// Creates a new divisor. Aborts if `divisor` is 0.
+        #[no_mangle]
+        pub extern "C" fn $new_fn(divisor: $primitive_type) -> $c_struct {
+            $struct_name::new(divisor).into()
+        }
+
+        /// Divides `numerator` by `divisor`.
+        #[no_mangle]
+        pub extern "C" fn $div_fn(numerator: $primitive_type, divisor: $c_struct) -> $primitive_type {
+            numerator / $struct_name::from(divisor)
+        }
+
+        /// Returns the remainder of dividing `numerator` by `divisor`.
+        #[no_mangle]
+        pub extern "C" fn $rem_fn(numerator: $primitive_type, divisor: $c_struct) -> $primitive_type {
+            numerator % $struct_name::from(divisor)
+        }
+
+        /// Divides `numerator` by `divisor`, and returns both the quotient and the remainder.
+        #[no_mangle]
+        pub extern "C" fn $div_rem_fn(numerator: $primitive_type, divisor: $c_struct) -> $div_rem_struct {
+            let (quotient, remainder) = $struct_name::div_rem(numerator, divisor.into());
+            $div_rem_struct{ quotient, remainder }
+        }
+    )
+}
+
+capi_impl!(CStrengthReducedU32, CDivRemU32, StrengthReducedU32, u32, strength_reduce_u32_new, strength_reduce_u32_div, strength_reduce_u32_rem, strength_reduce_u32_div_rem);
+capi_impl!(CStrengthReducedU64, CDivRemU64, StrengthReducedU64, u64, strength_reduce_u64_new, strength_reduce_u64_div, strength_reduce_u64_rem, strength_reduce_u64_div_rem);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_capi() {
+        for &divisor in &[1u64, 2, 3, 7, 10, 1 << 32, (1 << 32) + 1, u64::MAX / 2 + 1, u64::MAX] {
+            let c_divisor = strength_reduce_u64_new(divisor);
+            assert_eq!(divisor, c_divisor.divisor);
+            for &numerator in &[0u64, 1, 9, 10, 11, u32::MAX as u64, u64::MAX - 1, u64::MAX] {
+                let expected = CDivRemU64{ quotient: numerator / divisor, remainder: numerator % divisor };
+                assert_eq!(expected.quotient, strength_reduce_u64_div(numerator, c_divisor), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected.remainder, strength_reduce_u64_rem(numerator, c_divisor), "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected, strength_reduce_u64_div_rem(numerator, c_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+
+        for &divisor in &[1u32, 3, 10, 1 << 31, u32::MAX] {
+            let c_divisor = strength_reduce_u32_new(divisor);
+            for &numerator in &[0u32, 1, 10, 11, u32::MAX] {
+                let expected = CDivRemU32{ quotient: numerator / divisor, remainder: numerator % divisor };
+                assert_eq!(expected, strength_reduce_u32_div_rem(numerator, c_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected.quotient, strength_reduce_u32_div(numerator, c_divisor));
+                assert_eq!(expected.remainder, strength_reduce_u32_rem(numerator, c_divisor));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_capi_zero_divisor() {
+        let mut c_divisor = strength_reduce_u64_new(7);
+        c_divisor.divisor = 0;
+        let _ = StrengthReducedU64::from(c_divisor);
+    }
+}
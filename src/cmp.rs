@@ -0,0 +1,123 @@
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+use ::{LazyStrengthReducedU64, StrengthReducedU64Narrow};
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU128, StrengthReducedI128};
+
+// Everything else in a divisor struct is computed from the divisor, so comparing and hashing the divisor alone is consistent with comparing every field,
+// and it orders divisors the same way as the values they were created from
+macro_rules! divisor_cmp_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl PartialEq for $struct_name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        impl Eq for $struct_name {}
+
+        impl PartialOrd for $struct_name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $struct_name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl Hash for $struct_name {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.get().hash(state)
+            }
+        }
+
+        impl PartialEq<$primitive_type> for $struct_name {
+            #[inline]
+            fn eq(&self, other: &$primitive_type) -> bool {
+                self.get() == *other
+            }
+        }
+
+        impl PartialEq<$struct_name> for $primitive_type {
+            #[inline]
+            fn eq(&self, other: &$struct_name) -> bool {
+                *self == other.get()
+            }
+        }
+    )
+}
+
+divisor_cmp_impl!(StrengthReducedU8, u8);
+divisor_cmp_impl!(StrengthReducedU16, u16);
+divisor_cmp_impl!(StrengthReducedU32, u32);
+divisor_cmp_impl!(StrengthReducedU64, u64);
+divisor_cmp_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+divisor_cmp_impl!(StrengthReducedU128, u128);
+divisor_cmp_impl!(StrengthReducedI8, i8);
+divisor_cmp_impl!(StrengthReducedI16, i16);
+divisor_cmp_impl!(StrengthReducedI32, i32);
+divisor_cmp_impl!(StrengthReducedI64, i64);
+divisor_cmp_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+divisor_cmp_impl!(StrengthReducedI128, i128);
+divisor_cmp_impl!(StrengthReducedU64Narrow, u64);
+divisor_cmp_impl!(LazyStrengthReducedU64, u64);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! cmp_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let divisors = test_divisors!($primitive_type);
+                for &a in &divisors {
+                    let reduced_a = $struct_name::new(a);
+                    assert!(reduced_a == a && a == reduced_a, "Equality with primitive failed with divisor: {}", a);
+                    assert!(reduced_a != a.wrapping_add(1), "Inequality with primitive failed with divisor: {}", a);
+                    for &b in &divisors {
+                        let reduced_b = $struct_name::new(b);
+                        assert_eq!(a == b, reduced_a == reduced_b, "Equality failed with divisors: {}, {}", a, b);
+                        assert_eq!(a.cmp(&b), reduced_a.cmp(&reduced_b), "Ordering failed with divisors: {}, {}", a, b);
+                    }
+                }
+            }
+        )
+    }
+
+    cmp_test!(test_cmp_u8, StrengthReducedU8, u8);
+    cmp_test!(test_cmp_u32, StrengthReducedU32, u32);
+    cmp_test!(test_cmp_u64, StrengthReducedU64, u64);
+    cmp_test!(test_cmp_i32, StrengthReducedI32, i32);
+    cmp_test!(test_cmp_narrow, StrengthReducedU64Narrow, u64);
+    cmp_test!(test_cmp_lazy, LazyStrengthReducedU64, u64);
+    #[cfg(not(strength_reduce_no_u128))]
+    cmp_test!(test_cmp_u128, StrengthReducedU128, u128);
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hash_map_key() {
+        use std::collections::HashMap;
+
+        let mut counts = HashMap::new();
+        for &divisor in &[3u32, 5, 3, 7, 3, 5] {
+            *counts.entry(StrengthReducedU32::new(divisor)).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&StrengthReducedU32::new(3)], 3);
+        assert_eq!(counts[&StrengthReducedU32::new(5)], 2);
+        assert_eq!(counts[&StrengthReducedU32::new(7)], 1);
+    }
+}
@@ -0,0 +1,133 @@
+use core::ops::{Div, Rem};
+
+use ::StrengthReducedU64;
+
+/// Implements unsigned division and modulo by a divisor that's known at compile time.
+///
+/// On 64-bit targets, rustc already strength-reduces division by a constant, so the `Div` and `Rem` impls divide by `D` directly, and the codegen is identical to dividing by a literal.
+/// On narrower targets, LLVM often leaves a 64-bit division by a constant as a libcall, so there they use `REDUCED` instead.
+/// What this type adds is the same API as the runtime `StrengthReduced##` types, so generic code can accept either one.
+///
+/// A divisor of 0 is a compile-time error:
+/// ```compile_fail
+/// let divisor = strength_reduce::ConstDivisor::<0>::new();
+/// ```
+///
+/// # Example:
+/// ```
+/// use strength_reduce::ConstDivisor;
+///
+/// let divisor = ConstDivisor::<1000>::new();
+/// assert_eq!(123456 / divisor, 123);
+/// assert_eq!(123456 % divisor, 456);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConstDivisor<const D: u64> {
+    // keeps new() the only way to create one, so every instance has gone through the NONZERO check
+    _nonzero: (),
+}
+
+impl<const D: u64> ConstDivisor<D> {
+    // evaluated when the type is monomorphized, so a zero divisor fails to compile instead of panicking
+    const NONZERO: () = assert!(D != 0, "attempt to create a divisor of zero");
+
+    /// The runtime strength-reduced equivalent of this divisor, computed at compile time.
+    pub const REDUCED: StrengthReducedU64 = StrengthReducedU64::new(D);
+
+    /// Creates a new divisor instance.
+    #[inline]
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NONZERO;
+        ConstDivisor { _nonzero: () }
+    }
+
+    /// Simultaneous truncated integer division and modulus.
+    /// Returns `(quotient, remainder)`.
+    #[inline]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub fn div_rem(numerator: u64, denom: Self) -> (u64, u64) {
+        (numerator / denom, numerator % denom)
+    }
+
+    /// Retrieve the value used to create this struct
+    #[inline]
+    pub const fn get(&self) -> u64 {
+        D
+    }
+}
+
+impl<const D: u64> Default for ConstDivisor<D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const D: u64> From<ConstDivisor<D>> for StrengthReducedU64 {
+    #[inline]
+    fn from(_: ConstDivisor<D>) -> Self {
+        ConstDivisor::<D>::REDUCED
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl<const D: u64> Div<ConstDivisor<D>> for u64 {
+    type Output = u64;
+
+    #[inline]
+    fn div(self, _rhs: ConstDivisor<D>) -> Self::Output {
+        #[cfg(target_pointer_width = "64")]
+        #[allow(clippy::integer_division_remainder_used)]
+        let quotient = self / D;
+        #[cfg(not(target_pointer_width = "64"))]
+        let quotient = self / ConstDivisor::<D>::REDUCED;
+        quotient
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl<const D: u64> Rem<ConstDivisor<D>> for u64 {
+    type Output = u64;
+
+    #[inline]
+    fn rem(self, _rhs: ConstDivisor<D>) -> Self::Output {
+        #[cfg(target_pointer_width = "64")]
+        #[allow(clippy::integer_division_remainder_used)]
+        let remainder = self % D;
+        #[cfg(not(target_pointer_width = "64"))]
+        let remainder = self % ConstDivisor::<D>::REDUCED;
+        remainder
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! const_divisor_test {
+        ($test_name:ident, $divisor:expr) => (
+            #[test]
+            fn $test_name() {
+                let divisor = ConstDivisor::<{ $divisor }>::new();
+                let reduced_divisor = StrengthReducedU64::from(divisor);
+                let plain_divisor: u64 = $divisor;
+                assert_eq!(plain_divisor, divisor.get());
+                assert_eq!(plain_divisor, reduced_divisor.get());
+
+                let max = u64::MAX;
+                for &numerator in &[0, 1, 2, 3, 999, 1000, 1001, 123456789, max / 2, max - 1, max] {
+                    assert_eq!((numerator / plain_divisor, numerator % plain_divisor), ConstDivisor::div_rem(numerator, divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, plain_divisor);
+                    assert_eq!(numerator / plain_divisor, numerator / reduced_divisor, "REDUCED divide failed with numerator: {}, divisor: {}", numerator, plain_divisor);
+                    assert_eq!(numerator % plain_divisor, numerator % reduced_divisor, "REDUCED modulo failed with numerator: {}, divisor: {}", numerator, plain_divisor);
+                }
+            }
+        )
+    }
+
+    const_divisor_test!(test_const_divisor_1, 1);
+    const_divisor_test!(test_const_divisor_7, 7);
+    const_divisor_test!(test_const_divisor_1000, 1000);
+    const_divisor_test!(test_const_divisor_4096, 4096);
+    const_divisor_test!(test_const_divisor_max, u64::MAX);
+}
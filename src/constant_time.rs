@@ -0,0 +1,62 @@
+use core::hint::black_box;
+
+// Branch-free building blocks for the `constant-time` feature.
+// Every helper goes through a mask that's either all ones or all zeros, and black_box hides that the mask can only have those two values, so the optimizer can't turn the masking back into a branch.
+pub(crate) trait ConstantTimeInt: Copy {
+    // all ones if `condition` is true, 0 otherwise
+    fn mask(condition: bool) -> Self;
+
+    // `if_true` if `condition` is true, `if_false` otherwise
+    fn select(condition: bool, if_true: Self, if_false: Self) -> Self;
+
+    // the two's complement negation of self if `condition` is true, self otherwise
+    fn negate_if(self, condition: bool) -> Self;
+}
+
+macro_rules! constant_time_int {
+    ($($primitive_type:ident),*) => ($(
+        impl ConstantTimeInt for $primitive_type {
+            #[inline]
+            fn mask(condition: bool) -> Self {
+                black_box((condition as $primitive_type).wrapping_neg())
+            }
+
+            #[inline]
+            fn select(condition: bool, if_true: Self, if_false: Self) -> Self {
+                let mask = Self::mask(condition);
+                (if_true & mask) | (if_false & !mask)
+            }
+
+            #[inline]
+            fn negate_if(self, condition: bool) -> Self {
+                let mask = Self::mask(condition);
+                (self ^ mask).wrapping_sub(mask)
+            }
+        }
+    )*)
+}
+
+constant_time_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+#[cfg(not(strength_reduce_no_u128))]
+constant_time_int!(u128, i128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_int() {
+        assert_eq!(u32::MAX, u32::mask(true));
+        assert_eq!(0, u32::mask(false));
+        assert_eq!(-1, i64::mask(true));
+
+        assert_eq!(3, u16::select(true, 3, 5));
+        assert_eq!(5, u16::select(false, 3, 5));
+        assert_eq!(-3, i8::select(true, -3, 5));
+
+        assert_eq!(-7, 7i32.negate_if(true));
+        assert_eq!(7, 7i32.negate_if(false));
+        assert_eq!(i64::MIN, i64::MIN.negate_if(true));
+        assert_eq!(u8::MAX, 1u8.negate_if(true));
+    }
+}
@@ -0,0 +1,48 @@
+//! Ready-made divisor instances for common divisors, computed at compile time.
+//!
+//! These are all `StrengthReducedU64` instances. The constructors for `u8` through `u64` are `const fn`, so for other types or divisors, you can build your own the same way:
+//! ```
+//! use strength_reduce::StrengthReducedU32;
+//!
+//! const MILLIS_PER_MINUTE: StrengthReducedU32 = StrengthReducedU32::new(60_000);
+//! assert_eq!(150_000 / MILLIS_PER_MINUTE, 2);
+//! ```
+use ::StrengthReducedU64;
+
+/// Divides by 7, the number of days in a week.
+pub const SEVEN: StrengthReducedU64 = StrengthReducedU64::new(7);
+/// Divides by 10, for extracting decimal digits.
+pub const TEN: StrengthReducedU64 = StrengthReducedU64::new(10);
+/// Divides by 24, the number of hours in a day.
+pub const TWENTY_FOUR: StrengthReducedU64 = StrengthReducedU64::new(24);
+/// Divides by 60, the number of seconds in a minute, or minutes in an hour.
+pub const SIXTY: StrengthReducedU64 = StrengthReducedU64::new(60);
+/// Divides by 100, for extracting pairs of decimal digits.
+pub const HUNDRED: StrengthReducedU64 = StrengthReducedU64::new(100);
+/// Divides by 1000, for converting between seconds, milliseconds, microseconds, and nanoseconds.
+pub const THOUSAND: StrengthReducedU64 = StrengthReducedU64::new(1000);
+/// Divides by 3600, the number of seconds in an hour.
+pub const SECONDS_PER_HOUR: StrengthReducedU64 = StrengthReducedU64::new(3600);
+/// Divides by 86400, the number of seconds in a day.
+pub const SECONDS_PER_DAY: StrengthReducedU64 = StrengthReducedU64::new(86400);
+/// Divides by 10^9, the number of nanoseconds in a second.
+pub const NANOS_PER_SECOND: StrengthReducedU64 = StrengthReducedU64::new(1_000_000_000);
+/// Divides by 10^19, the largest power of 10 that fits in a `u64`.
+pub const TEN_POW_19: StrengthReducedU64 = StrengthReducedU64::new(10_000_000_000_000_000_000);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_consts() {
+        let expected = [(SEVEN, 7), (TEN, 10), (TWENTY_FOUR, 24), (SIXTY, 60), (HUNDRED, 100), (THOUSAND, 1000), (SECONDS_PER_HOUR, 3600), (SECONDS_PER_DAY, 86400), (NANOS_PER_SECOND, 1_000_000_000), (TEN_POW_19, 10u64.pow(19))];
+        for &(reduced_divisor, divisor) in &expected {
+            assert_eq!(divisor, reduced_divisor.get());
+            for &numerator in &[0, 1, 59, 60, 61, 86399, 86400, 1_234_567_890_123, u64::MAX - 1, u64::MAX] {
+                assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+}
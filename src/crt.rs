@@ -0,0 +1,143 @@
+use ::{mod_inverse, ReducedModulus};
+
+/// Converts between a number and its residues modulo several pairwise coprime moduli, using the Chinese remainder theorem.
+///
+/// Multi-modular pipelines split a large value into residues with `decompose`, do their arithmetic on each residue with the matching [`ReducedModulus`],
+/// and recombine the results with `reconstruct`. The product of the moduli must fit in a `u128`, and reconstructed values are reduced modulo that product.
+///
+/// `reconstruct` uses Garner's algorithm: The inverse of each modulus's prefix product is computed once, up front, so recombining `N` residues takes `N` multiplications modulo each modulus and no inversions.
+///
+/// ```
+/// use strength_reduce::{CrtBasis, ReducedModulus};
+///
+/// let basis = CrtBasis::new([3, 5, 7].map(ReducedModulus::<u64>::new));
+/// assert_eq!(basis.modulus(), 105);
+/// assert_eq!(basis.decompose(52), [1, 2, 3]);
+/// assert_eq!(basis.reconstruct(&[1, 2, 3]), 52);
+///
+/// // arithmetic on the residues carries over to the reconstructed value
+/// let (a, b) = (basis.decompose(9), basis.decompose(11));
+/// let product = core::array::from_fn(|index| basis.moduli()[index].mul_mod(a[index], b[index]));
+/// assert_eq!(basis.reconstruct(&product), 99);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CrtBasis<const N: usize> {
+    moduli: [ReducedModulus<u64>; N],
+    // the product of the moduli before each one
+    prefix_products: [u128; N],
+    // the inverse of each prefix product, modulo the modulus it's paired with
+    coefficients: [u64; N],
+    modulus: u128,
+}
+
+impl<const N: usize> CrtBasis<N> {
+    /// Creates a new CRT basis from the given moduli.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the moduli aren't pairwise coprime, or if their product doesn't fit in a `u128`
+    #[inline]
+    pub fn new(moduli: [ReducedModulus<u64>; N]) -> Self {
+        Self::try_new(moduli).expect("the moduli of a CRT basis must be pairwise coprime, and their product must fit in a u128")
+    }
+
+    /// Creates a new CRT basis from the given moduli, or returns `None` if the moduli aren't pairwise coprime, or if their product doesn't fit in a `u128`.
+    pub fn try_new(moduli: [ReducedModulus<u64>; N]) -> Option<Self> {
+        let mut prefix_products = [0; N];
+        let mut coefficients = [0; N];
+        let mut product: u128 = 1;
+        for (index, modulus) in moduli.iter().enumerate() {
+            // the prefix product is coprime to this modulus exactly when this modulus is coprime to every one before it
+            prefix_products[index] = product;
+            coefficients[index] = mod_inverse(modulus.reduce_wide(product), modulus)?;
+            product = product.checked_mul(modulus.get() as u128)?;
+        }
+        Some(Self { moduli, prefix_products, coefficients, modulus: product })
+    }
+
+    /// Returns the moduli this basis was created with.
+    #[inline]
+    pub fn moduli(&self) -> &[ReducedModulus<u64>; N] {
+        &self.moduli
+    }
+
+    /// Returns the product of the moduli. Reconstructed values are always smaller than this.
+    #[inline]
+    pub fn modulus(&self) -> u128 {
+        self.modulus
+    }
+
+    /// Computes the residues of `value` modulo each of the moduli.
+    #[inline]
+    pub fn decompose(&self, value: u128) -> [u64; N] {
+        self.moduli.map(|modulus| modulus.reduce_wide(value))
+    }
+
+    /// Computes the unique value smaller than the product of the moduli that has the given residues.
+    ///
+    /// Each residue must be smaller than its modulus, like the results of `decompose` and [`ReducedModulus`]'s operations are. In debug builds, passing an unreduced residue panics.
+    pub fn reconstruct(&self, residues: &[u64; N]) -> u128 {
+        // Garner's algorithm builds the result one modulus at a time. After each step, the result has the right residues for the moduli so far, and it's smaller than their product,
+        // so it's still correct after adding any multiple of that product, which is what the next step adds
+        let mut result = 0;
+        for (((modulus, &residue), &coefficient), &prefix_product) in self.moduli.iter().zip(residues).zip(&self.coefficients).zip(&self.prefix_products) {
+            let difference = modulus.sub_mod(residue, modulus.reduce_wide(result));
+            let digit = modulus.mul_mod(difference, coefficient);
+            result += digit as u128 * prefix_product;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn check_round_trip<const N: usize>(basis: &CrtBasis<N>, value: u128) {
+        let residues = basis.decompose(value);
+        for (modulus, &residue) in basis.moduli().iter().zip(&residues) {
+            assert_eq!((value % modulus.get() as u128) as u64, residue, "decompose failed with value: {}, modulus: {}", value, modulus.get());
+        }
+        assert_eq!(value % basis.modulus(), basis.reconstruct(&residues), "reconstruct failed with value: {}, modulus: {}", value, basis.modulus());
+    }
+
+    #[test]
+    fn test_crt_basis() {
+        let mut rng = StdRng::seed_from_u64(1108);
+        let max = u64::MAX;
+
+        let small = CrtBasis::new([3, 5, 7, 11, 13].map(ReducedModulus::<u64>::new));
+        assert_eq!(15015, small.modulus());
+        for value in 0..small.modulus() + 100 {
+            check_round_trip(&small, value);
+        }
+
+        // two moduli close to 2^64, whose product nearly fills a u128
+        let large = CrtBasis::new([max, max - 1].map(ReducedModulus::<u64>::new));
+        assert_eq!(max as u128 * (max - 1) as u128, large.modulus());
+
+        let primes = CrtBasis::new([1_000_000_007, 998_244_353, (1 << 61) - 1].map(ReducedModulus::<u64>::new));
+        let single = CrtBasis::new([ReducedModulus::<u64>::new(1 << 40)]);
+        let with_one = CrtBasis::new([1, 2, 1, 9].map(ReducedModulus::<u64>::new));
+        let empty = CrtBasis::<0>::new([]);
+        assert_eq!(1, empty.modulus());
+
+        let random_values: [u128; 100] = core::array::from_fn(|_| rng.gen::<u128>() >> rng.gen_range(0..128));
+        for &value in [0, 1, u64::MAX as u128, u128::MAX - 1, u128::MAX].iter().chain(&random_values) {
+            check_round_trip(&small, value);
+            check_round_trip(&large, value);
+            check_round_trip(&primes, value);
+            check_round_trip(&single, value);
+            check_round_trip(&with_one, value);
+            check_round_trip(&empty, value);
+        }
+    }
+
+    #[test]
+    fn test_invalid_basis() {
+        assert!(CrtBasis::try_new([6, 35, 10].map(ReducedModulus::<u64>::new)).is_none());
+        assert!(CrtBasis::try_new([4, 4].map(ReducedModulus::<u64>::new)).is_none());
+        assert!(CrtBasis::try_new([u64::MAX, u64::MAX - 1, 11].map(ReducedModulus::<u64>::new)).is_none());
+    }
+}
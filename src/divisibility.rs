@@ -0,0 +1,199 @@
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+
+// If the divisor is odd, it has a multiplicative inverse mod 2^N, and multiplying by it maps the multiples of the divisor, 0, d, 2d, ..., onto 0, 1, 2, ..., MAX / d, in order.
+// Every other numerator lands above MAX / d, so a numerator is divisible by an odd divisor exactly when numerator * inverse <= MAX / d.
+// Even divisors split into an odd part and a power of two. Rotating the product right by the divisor's trailing zeros moves any nonzero low bits to the top, where they push it over the threshold,
+// so the same comparison checks both parts at once. That's a single N-bit multiply, rotate, and compare, with no double-width arithmetic.
+// See Granlund and Montgomery, "Division by Invariant Integers using Multiplication", section 9
+macro_rules! divisibility_struct {
+    ($struct_name:ident, $reduced_struct:ident, $primitive_type:ident, $threshold_fn:expr) => (
+        /// Implements a divisibility test via a single multiplication, rotation, and comparison.
+        ///
+        /// Unlike the strength-reduced types, this only stores the state needed to check `numerator % divisor == 0`, and the check never needs a double-width multiply.
+        /// It can't compute quotients or remainders, and it doesn't remember the divisor it was created with.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $struct_name {
+            inverse: $primitive_type,
+            threshold: $primitive_type,
+            shift: u32,
+        }
+        impl $struct_name {
+            /// Creates a new divisibility tester.
+            ///
+            /// # Panics:
+            /// 
+            /// Panics if `divisor` is 0
+            #[inline]
+            pub fn new(divisor: $primitive_type) -> Self {
+                assert!(divisor > 0);
+                Self::from_threshold(divisor, $primitive_type::MAX / divisor)
+            }
+
+            #[inline]
+            fn from_threshold(divisor: $primitive_type, threshold: $primitive_type) -> Self {
+                let shift = divisor.trailing_zeros();
+                let odd_part = divisor >> shift;
+
+                // same Newton iteration as ExactDivU32, starting from the 3 bits that every odd number gets right as its own inverse
+                let mut inverse = odd_part;
+                let mut correct_bits = 3;
+                while correct_bits < $primitive_type::BITS {
+                    inverse = inverse.wrapping_mul((2 as $primitive_type).wrapping_sub(odd_part.wrapping_mul(inverse)));
+                    correct_bits *= 2;
+                }
+
+                Self { inverse, threshold, shift }
+            }
+
+            /// Returns true if `numerator` is a multiple of this divisor.
+            #[inline]
+            pub fn is_multiple_of(&self, numerator: $primitive_type) -> bool {
+                numerator.wrapping_mul(self.inverse).rotate_right(self.shift) <= self.threshold
+            }
+        }
+        impl From<$reduced_struct> for $struct_name {
+            /// Converts without redoing the division, since the threshold `MAX / divisor` can be read off the strength-reduced multiplier.
+            #[inline]
+            fn from(reduced: $reduced_struct) -> Self {
+                let divisor = reduced.divisor.get();
+                if reduced.multiplier == 0 {
+                    Self::from_threshold(divisor, $primitive_type::MAX >> reduced.shift)
+                } else {
+                    Self::from_threshold(divisor, ($threshold_fn)(reduced.multiplier, divisor))
+                }
+            }
+        }
+
+        impl $reduced_struct {
+            /// Returns true if `numerator` is a multiple of this divisor.
+            ///
+            #[doc = concat!("This uses the same multiply, rotate, and compare as [`", stringify!($struct_name), "`], which is cheaper than checking `numerator % divisor == 0`. ")]
+            /// The modular inverse it multiplies by takes a few multiplies to derive from the divisor, but since it only depends on the divisor, it gets hoisted out of loops.
+            #[doc = concat!("To check many numerators outside of a loop, convert the divisor to a `", stringify!($struct_name), "` once instead.")]
+            #[inline]
+            pub fn is_multiple_of(&self, numerator: $primitive_type) -> bool {
+                $struct_name::from(*self).is_multiple_of(numerator)
+            }
+        }
+    )
+}
+
+// The 8, 16, and 32-bit multipliers are floor(2^(2N) / divisor) + 1, so for a divisor that isn't a power of two, their upper half is floor(2^N / divisor) == floor(MAX / divisor)
+divisibility_struct!(DivisibilityU8, StrengthReducedU8, u8, |multiplier: u16, _| ((multiplier - 1) >> 8) as u8);
+divisibility_struct!(DivisibilityU16, StrengthReducedU16, u16, |multiplier: u32, _| ((multiplier - 1) >> 16) as u16);
+divisibility_struct!(DivisibilityU32, StrengthReducedU32, u32, |multiplier: u64, _| ((multiplier - 1) >> 32) as u32);
+#[cfg(not(strength_reduce_no_u128))]
+divisibility_struct!(DivisibilityU64, StrengthReducedU64, u64, |multiplier: u128, _| ((multiplier - 1) >> 64) as u64);
+// the round-up multiplier is floor(2^(64 + l) / divisor) + 1 - 2^64, where l is the divisor's bit length, so shifting it down by l recovers floor(2^64 / divisor) without its top bit.
+// l can be 64, so the shift is split in two
+#[cfg(strength_reduce_no_u128)]
+divisibility_struct!(DivisibilityU64, StrengthReducedU64, u64, |multiplier: u64, divisor: u64| {
+    let bits = 64 - divisor.leading_zeros();
+    ((multiplier - 1) >> (bits - 1) >> 1) + (1 << (64 - bits))
+});
+#[cfg(target_pointer_width = "16")]
+divisibility_struct!(DivisibilityUsize, StrengthReducedUsize, usize, |multiplier: u32, _| ((multiplier - 1) >> 16) as usize);
+#[cfg(target_pointer_width = "32")]
+divisibility_struct!(DivisibilityUsize, StrengthReducedUsize, usize, |multiplier: u64, _| ((multiplier - 1) >> 32) as usize);
+#[cfg(all(target_pointer_width = "64", not(strength_reduce_no_u128)))]
+divisibility_struct!(DivisibilityUsize, StrengthReducedUsize, usize, |multiplier: u128, _| ((multiplier - 1) >> 64) as usize);
+#[cfg(all(target_pointer_width = "64", strength_reduce_no_u128))]
+divisibility_struct!(DivisibilityUsize, StrengthReducedUsize, usize, |multiplier: u64, divisor: usize| {
+    let bits = 64 - divisor.leading_zeros();
+    (((multiplier - 1) >> (bits - 1) >> 1) + (1 << (64 - bits))) as usize
+});
+
+// A 128-bit inverse would need six rounds of 128-bit multiplies, so u128 checks the remainder instead
+#[cfg(not(strength_reduce_no_u128))]
+impl StrengthReducedU128 {
+    /// Returns true if `numerator` is a multiple of this divisor.
+    #[inline]
+    pub fn is_multiple_of(&self, numerator: u128) -> bool {
+        numerator % *self == 0
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! is_multiple_of_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+
+                    // test multiples of the divisor, their neighbors, and the neighbors of MAX
+                    let multiples = (0..20).map(|k| divisor.wrapping_mul(k)).chain((1..20).map(|k| (max - max % divisor).wrapping_sub(divisor.wrapping_mul(k))));
+                    for multiple in multiples {
+                        for &numerator in &[multiple, multiple.wrapping_add(1), multiple.wrapping_sub(1), max - multiple] {
+                            assert_eq!(numerator % divisor == 0, reduced_divisor.is_multiple_of(numerator), "is_multiple_of failed with numerator: {}, divisor: {}", numerator, divisor);
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    macro_rules! divisibility_struct_test {
+        ($test_name:ident, $struct_name:ident, $reduced_struct:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let tester = $struct_name::new(divisor);
+                    let converted = $struct_name::from($reduced_struct::new(divisor));
+                    assert_eq!((tester.inverse, tester.threshold, tester.shift), (converted.inverse, converted.threshold, converted.shift), "conversion from {} failed with divisor: {}", stringify!($reduced_struct), divisor);
+
+                    let multiples = (0..20).map(|k| divisor.wrapping_mul(k)).chain((1..20).map(|k| (max - max % divisor).wrapping_sub(divisor.wrapping_mul(k))));
+                    for multiple in multiples {
+                        for &numerator in &[multiple, multiple.wrapping_add(1), multiple.wrapping_sub(1), max - multiple] {
+                            assert_eq!(numerator % divisor == 0, tester.is_multiple_of(numerator), "is_multiple_of failed with numerator: {}, divisor: {}", numerator, divisor);
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn test_is_multiple_of_u8_exhaustive() {
+        for divisor in 1..=u8::MAX {
+            let reduced_divisor = StrengthReducedU8::new(divisor);
+            let tester = DivisibilityU8::new(divisor);
+            for numerator in 0..=u8::MAX {
+                assert_eq!(numerator % divisor == 0, reduced_divisor.is_multiple_of(numerator), "is_multiple_of failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor == 0, tester.is_multiple_of(numerator), "DivisibilityU8 failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(strength_reduce_no_u128))]
+    fn test_divisibility_struct_size() {
+        assert!(core::mem::size_of::<DivisibilityU32>() < core::mem::size_of::<StrengthReducedU32>());
+        assert!(core::mem::size_of::<DivisibilityU64>() < core::mem::size_of::<StrengthReducedU64>());
+    }
+
+    divisibility_struct_test!(test_divisibility_u8, DivisibilityU8, StrengthReducedU8, u8);
+    divisibility_struct_test!(test_divisibility_u16, DivisibilityU16, StrengthReducedU16, u16);
+    divisibility_struct_test!(test_divisibility_u32, DivisibilityU32, StrengthReducedU32, u32);
+    divisibility_struct_test!(test_divisibility_u64, DivisibilityU64, StrengthReducedU64, u64);
+    divisibility_struct_test!(test_divisibility_usize, DivisibilityUsize, StrengthReducedUsize, usize);
+
+    is_multiple_of_test!(test_is_multiple_of_u8, StrengthReducedU8, u8);
+    is_multiple_of_test!(test_is_multiple_of_u16, StrengthReducedU16, u16);
+    is_multiple_of_test!(test_is_multiple_of_u32, StrengthReducedU32, u32);
+    is_multiple_of_test!(test_is_multiple_of_u64, StrengthReducedU64, u64);
+    is_multiple_of_test!(test_is_multiple_of_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    is_multiple_of_test!(test_is_multiple_of_u128, StrengthReducedU128, u128);
+}
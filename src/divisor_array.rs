@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+use core::num::NonZeroU32;
+#[cfg(not(strength_reduce_no_u128))]
+use core::num::NonZeroU64;
+
+use ::StrengthReducedU32;
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU64;
+
+macro_rules! divisor_array {
+    ($array_name:ident, $struct_name:ident, $primitive_type:ident, $nonzero_type:ident, $multiplier_type:ident) => (
+        /// A structure-of-arrays container for many divisor instances.
+        ///
+        /// A `Vec` of divisor instances interleaves the multipliers, divisors, and shifts, and pads each instance out to the multiplier's alignment.
+        /// This stores each of them in its own contiguous array instead, so engines that hold tens of thousands of divisors touch less memory per division,
+        /// and bulk operations stream through each array in order.
+        #[derive(Clone, Debug, Default)]
+        pub struct $array_name {
+            multipliers: Vec<$multiplier_type>,
+            divisors: Vec<$nonzero_type>,
+            shifts: Vec<u8>,
+        }
+        impl $array_name {
+            /// Creates a new, empty array.
+            #[inline]
+            pub fn new() -> Self {
+                Self { multipliers: Vec::new(), divisors: Vec::new(), shifts: Vec::new() }
+            }
+
+            /// Creates an array containing a divisor instance for each element of `divisors`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if any element of `divisors` is 0
+            pub fn from_divisors(divisors: &[$primitive_type]) -> Self {
+                let mut result = Self { multipliers: Vec::with_capacity(divisors.len()), divisors: Vec::with_capacity(divisors.len()), shifts: Vec::with_capacity(divisors.len()) };
+                for &divisor in divisors {
+                    result.push($struct_name::new(divisor));
+                }
+                result
+            }
+
+            /// Appends a divisor instance to the end of this array.
+            #[inline]
+            pub fn push(&mut self, divisor: $struct_name) {
+                self.multipliers.push(divisor.multiplier);
+                self.divisors.push(divisor.divisor);
+                self.shifts.push(divisor.shift);
+            }
+
+            /// Returns the number of divisors in this array.
+            #[inline]
+            pub fn len(&self) -> usize {
+                self.divisors.len()
+            }
+
+            /// Returns true if this array doesn't contain any divisors.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.divisors.is_empty()
+            }
+
+            /// Returns the divisor instance at `index`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `index` is out of bounds
+            #[inline]
+            pub fn get(&self, index: usize) -> $struct_name {
+                $struct_name::from_parts(self.multipliers[index], self.divisors[index], self.shifts[index])
+            }
+
+            /// Divides `numerator` by the divisor at `index`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `index` is out of bounds
+            #[inline]
+            pub fn div(&self, index: usize, numerator: $primitive_type) -> $primitive_type {
+                numerator / self.get(index)
+            }
+
+            /// Computes `numerator` modulo the divisor at `index`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `index` is out of bounds
+            #[inline]
+            pub fn rem(&self, index: usize, numerator: $primitive_type) -> $primitive_type {
+                numerator % self.get(index)
+            }
+
+            /// Simultaneous truncated integer division and modulus of `numerator` by the divisor at `index`.
+            /// Returns `(quotient, remainder)`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `index` is out of bounds
+            #[inline]
+            pub fn div_rem(&self, index: usize, numerator: $primitive_type) -> ($primitive_type, $primitive_type) {
+                $struct_name::div_rem(numerator, self.get(index))
+            }
+
+            /// Divides each element of `numerators` by the divisor at the same index, and writes the quotients to `quotients`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerators` or `quotients` doesn't have the same length as this array
+            pub fn div_slice(&self, numerators: &[$primitive_type], quotients: &mut [$primitive_type]) {
+                assert_eq!(numerators.len(), self.len());
+                assert_eq!(quotients.len(), self.len());
+                for (index, (&numerator, quotient)) in numerators.iter().zip(quotients.iter_mut()).enumerate() {
+                    *quotient = self.div(index, numerator);
+                }
+            }
+
+            /// Computes each element of `numerators` modulo the divisor at the same index, and writes the remainders to `remainders`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerators` or `remainders` doesn't have the same length as this array
+            pub fn rem_slice(&self, numerators: &[$primitive_type], remainders: &mut [$primitive_type]) {
+                assert_eq!(numerators.len(), self.len());
+                assert_eq!(remainders.len(), self.len());
+                for (index, (&numerator, remainder)) in numerators.iter().zip(remainders.iter_mut()).enumerate() {
+                    *remainder = self.rem(index, numerator);
+                }
+            }
+
+            /// Divides `numerator` by every divisor in this array, and writes the quotients to `quotients`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `quotients` doesn't have the same length as this array
+            pub fn div_all(&self, numerator: $primitive_type, quotients: &mut [$primitive_type]) {
+                assert_eq!(quotients.len(), self.len());
+                for (index, quotient) in quotients.iter_mut().enumerate() {
+                    *quotient = self.div(index, numerator);
+                }
+            }
+
+            /// Returns an iterator over the divisor instances in this array.
+            #[inline]
+            pub fn iter(&self) -> impl Iterator<Item = $struct_name> + '_ {
+                (0..self.len()).map(move |index| self.get(index))
+            }
+        }
+
+        impl Extend<$struct_name> for $array_name {
+            fn extend<I: IntoIterator<Item = $struct_name>>(&mut self, iter: I) {
+                for divisor in iter {
+                    self.push(divisor);
+                }
+            }
+        }
+
+        impl core::iter::FromIterator<$struct_name> for $array_name {
+            fn from_iter<I: IntoIterator<Item = $struct_name>>(iter: I) -> Self {
+                let mut result = Self::new();
+                result.extend(iter);
+                result
+            }
+        }
+    )
+}
+
+divisor_array!(DivisorArrayU32, StrengthReducedU32, u32, NonZeroU32, u64);
+#[cfg(not(strength_reduce_no_u128))]
+divisor_array!(DivisorArrayU64, StrengthReducedU64, u64, NonZeroU64, u128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! divisor_array_test {
+        ($test_name:ident, $array_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = [1, 2, 3, 5, 6, 7, 8, 10, 100, 641, max / 2, max - 1, max];
+                let array = $array_name::from_divisors(&divisors);
+                assert_eq!(divisors.len(), array.len());
+
+                let numerators = [0, 1, 2, 99, 100, 101, 12345, max / 3, max / 2 + 1, max - 2, max - 1, max, 7];
+                let mut quotients = [0; 13];
+                let mut remainders = [0; 13];
+                array.div_slice(&numerators, &mut quotients);
+                array.rem_slice(&numerators, &mut remainders);
+
+                for (index, &divisor) in divisors.iter().enumerate() {
+                    assert_eq!(divisor, array.get(index).get());
+                    let numerator = numerators[index];
+                    assert_eq!(numerator / divisor, quotients[index], "div_slice failed with numerator: {}, divisor: {}", numerator, divisor);
+                    assert_eq!(numerator % divisor, remainders[index], "rem_slice failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                    for &numerator in &numerators {
+                        assert_eq!(numerator / divisor, array.div(index, numerator), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator % divisor, array.rem(index, numerator), "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!((numerator / divisor, numerator % divisor), array.div_rem(index, numerator), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+
+                array.div_all(max, &mut quotients);
+                for (&divisor, &quotient) in divisors.iter().zip(&quotients) {
+                    assert_eq!(max / divisor, quotient);
+                }
+
+                let collected: $array_name = array.iter().collect();
+                assert!(collected.iter().map(|divisor| divisor.get()).eq(divisors.iter().cloned()));
+                assert!($array_name::new().is_empty());
+            }
+        )
+    }
+
+    divisor_array_test!(test_divisor_array_u32, DivisorArrayU32, StrengthReducedU32, u32);
+    #[cfg(not(strength_reduce_no_u128))]
+    divisor_array_test!(test_divisor_array_u64, DivisorArrayU64, StrengthReducedU64, u64);
+
+    #[test]
+    #[should_panic]
+    fn test_divisor_array_zero() {
+        DivisorArrayU32::from_divisors(&[3, 0, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(not(strength_reduce_no_u128))]
+    fn test_divisor_array_length_mismatch() {
+        let array = DivisorArrayU64::from_divisors(&[3, 5]);
+        array.div_slice(&[1, 2, 3], &mut [0; 3]);
+    }
+}
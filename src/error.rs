@@ -0,0 +1,112 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedI128;
+use ::StrengthReducedU64Narrow;
+
+/// The error returned when a divisor instance can't be created from the given value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DivisorError {
+    /// The divisor was zero.
+    Zero,
+}
+
+impl fmt::Display for DivisorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivisorError::Zero => f.write_str("attempt to create a divisor of zero"),
+        }
+    }
+}
+
+impl core::error::Error for DivisorError {}
+
+macro_rules! try_from_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl TryFrom<$primitive_type> for $struct_name {
+            type Error = DivisorError;
+
+            #[inline]
+            fn try_from(divisor: $primitive_type) -> Result<Self, Self::Error> {
+                $struct_name::try_new(divisor).ok_or(DivisorError::Zero)
+            }
+        }
+    )
+}
+
+try_from_impl!(StrengthReducedU8, u8);
+try_from_impl!(StrengthReducedU16, u16);
+try_from_impl!(StrengthReducedU32, u32);
+try_from_impl!(StrengthReducedU64, u64);
+try_from_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+try_from_impl!(StrengthReducedU128, u128);
+
+try_from_impl!(StrengthReducedI8, i8);
+try_from_impl!(StrengthReducedI16, i16);
+try_from_impl!(StrengthReducedI32, i32);
+try_from_impl!(StrengthReducedI64, i64);
+try_from_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+try_from_impl!(StrengthReducedI128, i128);
+try_from_impl!(StrengthReducedU64Narrow, u64);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! try_from_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                assert_eq!(Err(DivisorError::Zero), $struct_name::try_from(0 as $primitive_type).map(|d| d.get()));
+                for &divisor in &[1, 2, 3, 7, $primitive_type::MAX] {
+                    assert_eq!(Ok(divisor), $struct_name::try_from(divisor).map(|d| d.get()));
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn test_error_propagation() {
+        fn make_divisor(divisor: u64) -> Result<StrengthReducedU64, DivisorError> {
+            let reduced = StrengthReducedU64::try_from(divisor)?;
+            Ok(reduced)
+        }
+        assert!(make_divisor(5).is_ok());
+
+        let error: &dyn core::error::Error = &make_divisor(0).unwrap_err();
+        struct Checker(bool);
+        impl fmt::Write for Checker {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 |= s.contains("zero");
+                Ok(())
+            }
+        }
+        let mut checker = Checker(false);
+        fmt::write(&mut checker, format_args!("{}", error)).unwrap();
+        assert!(checker.0);
+    }
+
+    try_from_test!(test_try_from_u8, StrengthReducedU8, u8);
+    try_from_test!(test_try_from_u16, StrengthReducedU16, u16);
+    try_from_test!(test_try_from_u32, StrengthReducedU32, u32);
+    try_from_test!(test_try_from_u64, StrengthReducedU64, u64);
+    try_from_test!(test_try_from_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    try_from_test!(test_try_from_u128, StrengthReducedU128, u128);
+    try_from_test!(test_try_from_i8, StrengthReducedI8, i8);
+    try_from_test!(test_try_from_i16, StrengthReducedI16, i16);
+    try_from_test!(test_try_from_i32, StrengthReducedI32, i32);
+    try_from_test!(test_try_from_i64, StrengthReducedI64, i64);
+    try_from_test!(test_try_from_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    try_from_test!(test_try_from_i128, StrengthReducedI128, i128);
+    try_from_test!(test_try_from_narrow, StrengthReducedU64Narrow, u64);
+}
@@ -0,0 +1,114 @@
+// If the divisor is odd, it has a multiplicative inverse mod 2^N, and for any exact multiple of the divisor, numerator * inverse wraps around to exactly numerator / divisor.
+// Even divisors are split into an odd part and a power of two, and the power of two is shifted out first. Both of those steps are exact, so no rounding correction is needed.
+// See Granlund and Montgomery, "Division by Invariant Integers using Multiplication", section 9
+macro_rules! exact_div {
+    ($struct_name:ident, $primitive_type:ident) => (
+        /// Implements exact division via multiplication by a modular inverse.
+        ///
+        /// Only use this when the numerator is known to be a multiple of the divisor, like when dividing a byte length by an element size or stride.
+        /// In exchange for that restriction, each division is a single shift and multiply, which is cheaper than a full strength-reduced division.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $struct_name {
+            inverse: $primitive_type,
+            shift: u32,
+            divisor: $primitive_type,
+        }
+        impl $struct_name {
+            /// Creates a new divisor instance.
+            ///
+            /// # Panics:
+            /// 
+            /// Panics if `divisor` is 0
+            #[inline]
+            pub fn new(divisor: $primitive_type) -> Self {
+                assert!(divisor > 0);
+
+                let shift = divisor.trailing_zeros();
+                let odd_part = divisor >> shift;
+
+                // Newton's method doubles the number of correct bits each iteration. Every odd number is its own inverse mod 8, so we start with 3 correct bits
+                let mut inverse = odd_part;
+                let mut correct_bits = 3;
+                while correct_bits < $primitive_type::BITS {
+                    inverse = inverse.wrapping_mul((2 as $primitive_type).wrapping_sub(odd_part.wrapping_mul(inverse)));
+                    correct_bits *= 2;
+                }
+
+                Self { inverse, shift, divisor }
+            }
+
+            /// Computes `numerator / divisor`, assuming that `numerator` is a multiple of the divisor.
+            ///
+            /// If `numerator` isn't a multiple of the divisor, the result is unspecified. In debug builds, this panics instead.
+            #[inline]
+            pub fn exact_div(&self, numerator: $primitive_type) -> $primitive_type {
+                let quotient = (numerator >> self.shift).wrapping_mul(self.inverse);
+                debug_assert!(quotient.wrapping_mul(self.divisor) == numerator && quotient <= $primitive_type::MAX / self.divisor, "exact_div called with numerator {}, which is not a multiple of {}", numerator, self.divisor);
+                quotient
+            }
+
+            /// Retrieve the value used to create this struct
+            #[inline]
+            pub fn get(&self) -> $primitive_type {
+                self.divisor
+            }
+        }
+    )
+}
+
+exact_div!(ExactDivU32, u32);
+exact_div!(ExactDivU64, u64);
+
+#[cfg(not(strength_reduce_no_u128))]
+impl ExactDivU64 {
+    // the inverse of the divisor's odd part mod 2^64, so Montgomery multiplication can share the Newton iteration above
+    #[inline]
+    pub(crate) fn inverse(&self) -> u64 {
+        self.inverse
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! exact_div_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let exact_divisor = $struct_name::new(divisor);
+                    assert_eq!(divisor, exact_divisor.get());
+
+                    let max_quotient = max / divisor;
+                    let quotients = (0..20).chain((0..20).map(|k| max_quotient - k.min(max_quotient))).chain(core::iter::once(max_quotient / 2));
+                    for quotient in quotients.filter(|&quotient| quotient <= max_quotient) {
+                        let numerator = quotient * divisor;
+                        assert_eq!(quotient, exact_divisor.exact_div(numerator), "exact_div failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    exact_div_test!(test_exact_div_u32, ExactDivU32, u32);
+    exact_div_test!(test_exact_div_u64, ExactDivU64, u64);
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_exact_div_inexact() {
+        ExactDivU32::new(6).exact_div(9);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_exact_div_inexact_even() {
+        // shifting out the power of two drops the low bit, leaving an exact multiple of 3, so the debug check needs to catch the lost bits too
+        ExactDivU64::new(192).exact_div(64 * 3 * 5 + 1);
+    }
+}
@@ -0,0 +1,111 @@
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32};
+#[cfg(any(not(target_pointer_width = "64"), not(strength_reduce_no_u128)))]
+use ::StrengthReducedUsize;
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU64, StrengthReducedU128};
+#[cfg(not(strength_reduce_no_u128))]
+use ::long_multiplication;
+
+// The multiplier is ceil(2^(2N) / divisor), so the low 2N bits of numerator * multiplier are the fractional part of numerator / divisor, as a Q0.2N fixed point number.
+// Rounding the multiplier up adds an error of less than numerator / 2^(2N), which is always less than 2^-N, so the upper half of those bits is accurate to within one unit in the last place.
+macro_rules! fraction_impl {
+    ($struct_name:ident, $primitive_type:ident, $multiplier_type:ident) => (
+        impl $struct_name {
+            /// Computes the fractional part of `numerator / divisor` as a fixed-point number with all of its bits after the radix point.
+            /// In other words, computes `(numerator % divisor) * 2^BITS / divisor`, without an extra division.
+            ///
+            /// The result is either that value rounded down, or one more than that. It's always exact if the divisor is a power of two, or if `numerator` is a multiple of the divisor.
+            #[inline]
+            pub fn fraction_bits(&self, numerator: $primitive_type) -> $primitive_type {
+                if self.multiplier == 0 {
+                    // if the divisor is 1, the remainder is 0, so it doesn't matter that the shift amount wraps around
+                    (numerator & (self.divisor.get() - 1)).wrapping_shl($primitive_type::BITS - self.shift as u32)
+                } else {
+                    let fraction = (numerator as $multiplier_type).wrapping_mul(self.multiplier);
+                    (fraction >> $primitive_type::BITS) as $primitive_type
+                }
+            }
+        }
+    )
+}
+
+fraction_impl!(StrengthReducedU8, u8, u16);
+fraction_impl!(StrengthReducedU16, u16, u32);
+fraction_impl!(StrengthReducedU32, u32, u64);
+#[cfg(not(strength_reduce_no_u128))]
+fraction_impl!(StrengthReducedU64, u64, u128);
+#[cfg(target_pointer_width = "16")]
+fraction_impl!(StrengthReducedUsize, usize, u32);
+#[cfg(target_pointer_width = "32")]
+fraction_impl!(StrengthReducedUsize, usize, u64);
+#[cfg(all(target_pointer_width = "64", not(strength_reduce_no_u128)))]
+fraction_impl!(StrengthReducedUsize, usize, u128);
+
+#[cfg(not(strength_reduce_no_u128))]
+impl StrengthReducedU128 {
+    /// Computes the fractional part of `numerator / divisor` as a fixed-point number with all of its bits after the radix point.
+    /// In other words, computes `(numerator % divisor) * 2^128 / divisor`, without an extra division.
+    ///
+    /// The result is either that value rounded down, or one more than that. It's always exact if the divisor is a power of two, or if `numerator` is a multiple of the divisor.
+    #[inline]
+    pub fn fraction_bits(&self, numerator: u128) -> u128 {
+        let ceil_log2 = 128 - self.divisor.get().leading_zeros();
+        if self.multiplier == 0 {
+            let shift = ceil_log2 - 1;
+            (numerator & (self.divisor.get() - 1)).wrapping_shl(128 - shift)
+        } else {
+            // The full 129-bit multiplier is slightly more than 2^(128 + l) / divisor, where l = ceil(log2(divisor)).
+            // So remainder * multiplier / 2^l is slightly more than remainder * 2^128 / divisor, and since the remainder is less than 2^l, the error is less than 1
+            let remainder = numerator % *self;
+            let product_lo = remainder.wrapping_mul(self.multiplier);
+            let product_hi = long_multiplication::multiply_128_by_128_upperbits(remainder, self.multiplier) + remainder;
+            if ceil_log2 == 128 {
+                product_hi
+            } else {
+                (product_hi << (128 - ceil_log2)) | (product_lo >> ceil_log2)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    macro_rules! fraction_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let values = [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,100,max/3,max/2,max/2+1,max-2,max-1,max];
+
+                for &divisor in &values {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in values.iter().chain(&[0]) {
+                        let remainder = numerator % divisor;
+                        let expected: BigUint = (BigUint::from(remainder) << $primitive_type::BITS as usize) / BigUint::from(divisor);
+                        let fraction = reduced_divisor.fraction_bits(numerator);
+
+                        if remainder == 0 || divisor.is_power_of_two() {
+                            assert_eq!(expected, BigUint::from(fraction), "fraction_bits wasn't exact with numerator: {}, divisor: {}", numerator, divisor);
+                        } else {
+                            let difference = BigUint::from(fraction) - &expected;
+                            assert!(difference <= BigUint::from(1u8), "fraction_bits failed with numerator: {}, divisor: {}", numerator, divisor);
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    fraction_test!(test_fraction_bits_u8, StrengthReducedU8, u8);
+    fraction_test!(test_fraction_bits_u16, StrengthReducedU16, u16);
+    fraction_test!(test_fraction_bits_u32, StrengthReducedU32, u32);
+    #[cfg(not(strength_reduce_no_u128))]
+    fraction_test!(test_fraction_bits_u64, StrengthReducedU64, u64);
+    #[cfg(any(not(target_pointer_width = "64"), not(strength_reduce_no_u128)))]
+    fraction_test!(test_fraction_bits_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    fraction_test!(test_fraction_bits_u128, StrengthReducedU128, u128);
+}
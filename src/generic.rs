@@ -0,0 +1,303 @@
+use core::fmt::Debug;
+use core::ops::{Div, Rem};
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU128, StrengthReducedI128};
+
+/// A strength-reduced divisor for the primitive type `T`, for algorithms that are generic over the integer width.
+///
+/// This is implemented by every `StrengthReduced*` type, signed and unsigned, and just forwards to their inherent functions and operators.
+///
+/// ```
+/// use strength_reduce::{ReducedDivisor, StrengthReducedU32, StrengthReducedI64};
+///
+/// fn split_index<D: ReducedDivisor<T>, T>(index: T, row_length: T) -> (T, T) {
+///     D::new(row_length).div_rem(index)
+/// }
+///
+/// assert_eq!(split_index::<StrengthReducedU32, u32>(17, 5), (3, 2));
+/// assert_eq!(split_index::<StrengthReducedI64, i64>(-17, 5), (-3, -2));
+/// ```
+pub trait ReducedDivisor<T>: Copy {
+    /// Creates a new divisor instance.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `divisor` is 0
+    fn new(divisor: T) -> Self;
+
+    /// Divides `numerator` by this divisor.
+    fn div(&self, numerator: T) -> T;
+
+    /// Returns the remainder of dividing `numerator` by this divisor.
+    fn rem(&self, numerator: T) -> T;
+
+    /// Divides `numerator` by this divisor, and returns `(quotient, remainder)`.
+    fn div_rem(&self, numerator: T) -> (T, T);
+
+    /// Retrieve the value used to create this divisor.
+    fn get(&self) -> T;
+}
+
+/// An unsigned primitive type that has a strength-reduced divisor type.
+///
+/// This is what lets [`StrengthReduced`] name the divisor type for a generic integer. Every primitive can be divided by its own divisor type, so
+/// `T: Reducible` is all that generic code needs to divide a `T` by a `StrengthReduced<T>`.
+pub trait Reducible: Copy + Div<<Self as Reducible>::Reduced, Output = Self> + Rem<<Self as Reducible>::Reduced, Output = Self> {
+    /// The strength-reduced divisor type for this primitive, eg `StrengthReducedU32` for `u32`.
+    type Reduced: ReducedDivisor<Self> + Debug + Default;
+}
+
+/// The strength-reduced divisor type for the unsigned primitive `T`.
+///
+/// `StrengthReduced<u32>` is the same type as `StrengthReducedU32`, and so on for every other unsigned primitive, so generic code can name the divisor type without macroing over the concrete names.
+///
+/// ```
+/// use strength_reduce::{Reducible, StrengthReduced, StrengthReducedU16, StrengthReducedU64};
+///
+/// fn digit_sum<T: Reducible + PartialEq + Default>(mut value: T, radix: StrengthReduced<T>) -> T where T: core::ops::Add<Output = T> {
+///     let mut sum = T::default();
+///     while value != T::default() {
+///         sum = sum + value % radix;
+///         value = value / radix;
+///     }
+///     sum
+/// }
+///
+/// assert_eq!(digit_sum(1234u16, StrengthReducedU16::new(10)), 10);
+/// assert_eq!(digit_sum(0xFFu64, StrengthReducedU64::new(16)), 30);
+/// ```
+pub type StrengthReduced<T> = <T as Reducible>::Reduced;
+
+/// Simultaneous division and remainder, in the style of the `Div` and `Rem` operator traits.
+///
+/// This is implemented for every primitive integer by its strength-reduced divisor type, and by itself, so generic code can ask for the quotient and
+/// the remainder in one call without caring which kind of divisor it was given.
+///
+/// ```
+/// use strength_reduce::{DivRem, StrengthReducedU32};
+///
+/// fn split<D: Copy>(index: u32, row_length: D) -> (u32, u32) where u32: DivRem<D, Output = u32> {
+///     index.div_rem(row_length)
+/// }
+///
+/// assert_eq!(split(17, 5), (3, 2));
+/// assert_eq!(split(17, StrengthReducedU32::new(5)), (3, 2));
+/// ```
+pub trait DivRem<Rhs = Self> {
+    /// The type of the quotient and the remainder.
+    type Output;
+
+    /// Divides `self` by `rhs`, and returns `(quotient, remainder)`.
+    fn div_rem(self, rhs: Rhs) -> (Self::Output, Self::Output);
+}
+
+/// Adds `strength_reduce()` to every unsigned primitive, as a shorthand for calling `new()` on its divisor type.
+///
+/// ```
+/// use strength_reduce::StrengthReduce;
+///
+/// let divisor = 7u64.strength_reduce();
+/// assert_eq!(100 / divisor, 14);
+/// assert_eq!(100 % divisor, 2);
+/// ```
+pub trait StrengthReduce: Reducible {
+    /// Creates a strength-reduced divisor from this value.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `self` is 0
+    fn strength_reduce(self) -> StrengthReduced<Self>;
+}
+
+impl<T: Reducible> StrengthReduce for T {
+    #[inline]
+    fn strength_reduce(self) -> StrengthReduced<Self> {
+        ReducedDivisor::new(self)
+    }
+}
+
+macro_rules! reducible_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl Reducible for $primitive_type {
+            type Reduced = $struct_name;
+        }
+    )
+}
+
+macro_rules! reduced_divisor_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl ReducedDivisor<$primitive_type> for $struct_name {
+            #[inline]
+            fn new(divisor: $primitive_type) -> Self {
+                $struct_name::new(divisor)
+            }
+
+            #[inline]
+            fn div(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator / *self
+            }
+
+            #[inline]
+            fn rem(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator % *self
+            }
+
+            #[inline]
+            fn div_rem(&self, numerator: $primitive_type) -> ($primitive_type, $primitive_type) {
+                $struct_name::div_rem(numerator, *self)
+            }
+
+            #[inline]
+            fn get(&self) -> $primitive_type {
+                $struct_name::get(self)
+            }
+        }
+    )
+}
+
+reduced_divisor_impl!(StrengthReducedU8, u8);
+reduced_divisor_impl!(StrengthReducedU16, u16);
+reduced_divisor_impl!(StrengthReducedU32, u32);
+reduced_divisor_impl!(StrengthReducedU64, u64);
+reduced_divisor_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+reduced_divisor_impl!(StrengthReducedU128, u128);
+reduced_divisor_impl!(StrengthReducedI8, i8);
+reduced_divisor_impl!(StrengthReducedI16, i16);
+reduced_divisor_impl!(StrengthReducedI32, i32);
+reduced_divisor_impl!(StrengthReducedI64, i64);
+reduced_divisor_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+reduced_divisor_impl!(StrengthReducedI128, i128);
+
+macro_rules! div_rem_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl DivRem<$struct_name> for $primitive_type {
+            type Output = $primitive_type;
+
+            #[inline]
+            fn div_rem(self, rhs: $struct_name) -> ($primitive_type, $primitive_type) {
+                $struct_name::div_rem(self, rhs)
+            }
+        }
+
+        impl DivRem for $primitive_type {
+            type Output = $primitive_type;
+
+            #[inline]
+            fn div_rem(self, rhs: $primitive_type) -> ($primitive_type, $primitive_type) {
+                (self / rhs, self % rhs)
+            }
+        }
+    )
+}
+
+div_rem_impl!(StrengthReducedU8, u8);
+div_rem_impl!(StrengthReducedU16, u16);
+div_rem_impl!(StrengthReducedU32, u32);
+div_rem_impl!(StrengthReducedU64, u64);
+div_rem_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+div_rem_impl!(StrengthReducedU128, u128);
+div_rem_impl!(StrengthReducedI8, i8);
+div_rem_impl!(StrengthReducedI16, i16);
+div_rem_impl!(StrengthReducedI32, i32);
+div_rem_impl!(StrengthReducedI64, i64);
+div_rem_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+div_rem_impl!(StrengthReducedI128, i128);
+
+reducible_impl!(StrengthReducedU8, u8);
+reducible_impl!(StrengthReducedU16, u16);
+reducible_impl!(StrengthReducedU32, u32);
+reducible_impl!(StrengthReducedU64, u64);
+reducible_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+reducible_impl!(StrengthReducedU128, u128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn div_rem_generic<T: Reducible>(numerator: T, divisor: StrengthReduced<T>) -> (T, T) {
+        (numerator / divisor, numerator % divisor)
+    }
+
+    fn reduced_divisor_generic<D: ReducedDivisor<T>, T: Copy>(divisor: T, numerator: T) -> (T, T, (T, T), T) {
+        let reduced_divisor = D::new(divisor);
+        (reduced_divisor.div(numerator), reduced_divisor.rem(numerator), reduced_divisor.div_rem(numerator), reduced_divisor.get())
+    }
+
+    macro_rules! reduced_divisor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let (min, max) = ($primitive_type::MIN, $primitive_type::MAX);
+                for &divisor in &[1, 2, 3, 7, 10, max / 2 + 1, max] {
+                    for &numerator in &[min, min + 1, 0, 1, 9, 10, 11, max / 3, max - 1, max] {
+                        let expected = (numerator / divisor, numerator % divisor, (numerator / divisor, numerator % divisor), divisor);
+                        assert_eq!(expected, reduced_divisor_generic::<$struct_name, $primitive_type>(divisor, numerator), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    reduced_divisor_test!(test_reduced_divisor_u8, StrengthReducedU8, u8);
+    reduced_divisor_test!(test_reduced_divisor_u32, StrengthReducedU32, u32);
+    reduced_divisor_test!(test_reduced_divisor_u64, StrengthReducedU64, u64);
+    reduced_divisor_test!(test_reduced_divisor_i16, StrengthReducedI16, i16);
+    reduced_divisor_test!(test_reduced_divisor_i64, StrengthReducedI64, i64);
+    #[cfg(not(strength_reduce_no_u128))]
+    reduced_divisor_test!(test_reduced_divisor_i128, StrengthReducedI128, i128);
+
+    fn div_rem_trait<T: DivRem<D>, D>(numerator: T, divisor: D) -> (T::Output, T::Output) {
+        numerator.div_rem(divisor)
+    }
+
+    #[test]
+    fn test_div_rem_trait() {
+        for &divisor in &[1u64, 2, 3, 10, u64::MAX] {
+            for &numerator in &[0u64, 1, 9, 10, 11, u64::MAX - 1, u64::MAX] {
+                let expected = (numerator / divisor, numerator % divisor);
+                assert_eq!(expected, div_rem_trait(numerator, divisor));
+                assert_eq!(expected, div_rem_trait(numerator, StrengthReducedU64::new(divisor)));
+            }
+        }
+        for &divisor in &[1i16, -1, 3, -7, i16::MAX, i16::MIN] {
+            for &numerator in &[0i16, 1, -1, 10, -10, i16::MAX, i16::MIN + 1] {
+                let expected = (numerator / divisor, numerator % divisor);
+                assert_eq!(expected, div_rem_trait(numerator, divisor));
+                assert_eq!(expected, div_rem_trait(numerator, StrengthReducedI16::new(divisor)));
+            }
+        }
+    }
+
+    macro_rules! generic_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                for &divisor in &[1, 2, 3, 7, 10, max / 2 + 1, max] {
+                    // StrengthReduced<T> is the concrete type, so values pass between the two names without any conversion
+                    let reduced_divisor: StrengthReduced<$primitive_type> = $struct_name::new(divisor);
+                    assert_eq!(divisor, divisor.strength_reduce().get());
+                    for &numerator in &[0, 1, 9, 10, 11, max / 3, max - 1, max] {
+                        assert_eq!((numerator / divisor, numerator % divisor), div_rem_generic(numerator, reduced_divisor), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    generic_test!(test_generic_u8, StrengthReducedU8, u8);
+    generic_test!(test_generic_u16, StrengthReducedU16, u16);
+    generic_test!(test_generic_u32, StrengthReducedU32, u32);
+    generic_test!(test_generic_u64, StrengthReducedU64, u64);
+    generic_test!(test_generic_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    generic_test!(test_generic_u128, StrengthReducedU128, u128);
+}
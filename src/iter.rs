@@ -0,0 +1,133 @@
+use core::iter::FusedIterator;
+use core::ops::{Div, Rem};
+
+/// Adds `map_div`, `map_rem`, and `map_div_rem` to every iterator.
+///
+/// These work with any divisor type that the iterator's items can be divided by, including all of the strength-reduced types in this crate, so iterator pipelines can use the fast path without a closure that captures the divisor.
+///
+/// ```
+/// use strength_reduce::{IteratorDivExt, StrengthReducedU32};
+///
+/// let divisor = StrengthReducedU32::new(10);
+/// let digits: Vec<u32> = [1234u32, 56, 7].iter().copied().map_rem(divisor).collect();
+/// assert_eq!(digits, [4, 6, 7]);
+/// ```
+pub trait IteratorDivExt: Iterator + Sized {
+    /// Divides every item by `divisor`.
+    #[inline]
+    fn map_div<D: Copy>(self, divisor: D) -> MapDiv<Self, D> where Self::Item: Div<D> {
+        MapDiv { iter: self, divisor }
+    }
+
+    /// Replaces every item with its remainder after dividing by `divisor`.
+    #[inline]
+    fn map_rem<D: Copy>(self, divisor: D) -> MapRem<Self, D> where Self::Item: Rem<D> {
+        MapRem { iter: self, divisor }
+    }
+
+    /// Replaces every item with its `(quotient, remainder)` after dividing by `divisor`.
+    #[inline]
+    fn map_div_rem<D: Copy>(self, divisor: D) -> MapDivRem<Self, D> where Self::Item: Copy + Div<D> + Rem<D> {
+        MapDivRem { iter: self, divisor }
+    }
+}
+
+impl<I: Iterator> IteratorDivExt for I {}
+
+// The adapters forward everything they can to the inner iterator, the same way `core::iter::Map` does
+macro_rules! map_adapter {
+    ($struct_name:ident, [$($bound:tt)+], $item:ty, |$value:ident, $divisor:ident| $op:expr) => (
+        impl<I: Iterator, D: Copy> Iterator for $struct_name<I, D> where I::Item: $($bound)+ {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let $divisor = self.divisor;
+                self.iter.next().map(|$value| $op)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+
+            #[inline]
+            fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+                let $divisor = self.divisor;
+                self.iter.fold(init, move |accumulator, $value| f(accumulator, $op))
+            }
+        }
+
+        impl<I: DoubleEndedIterator, D: Copy> DoubleEndedIterator for $struct_name<I, D> where I::Item: $($bound)+ {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let $divisor = self.divisor;
+                self.iter.next_back().map(|$value| $op)
+            }
+        }
+
+        impl<I: ExactSizeIterator, D: Copy> ExactSizeIterator for $struct_name<I, D> where I::Item: $($bound)+ {}
+
+        impl<I: FusedIterator, D: Copy> FusedIterator for $struct_name<I, D> where I::Item: $($bound)+ {}
+    )
+}
+
+/// An iterator that divides every item of `I` by a divisor. Created by [`IteratorDivExt::map_div`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MapDiv<I, D> {
+    iter: I,
+    divisor: D,
+}
+
+/// An iterator that replaces every item of `I` with its remainder after dividing by a divisor. Created by [`IteratorDivExt::map_rem`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MapRem<I, D> {
+    iter: I,
+    divisor: D,
+}
+
+/// An iterator that replaces every item of `I` with its `(quotient, remainder)` after dividing by a divisor. Created by [`IteratorDivExt::map_div_rem`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MapDivRem<I, D> {
+    iter: I,
+    divisor: D,
+}
+
+map_adapter!(MapDiv, [Div<D>], <I::Item as Div<D>>::Output, |value, divisor| value / divisor);
+map_adapter!(MapRem, [Rem<D>], <I::Item as Rem<D>>::Output, |value, divisor| value % divisor);
+// the strength-reduced types inline their Div and Rem impls, so the compiler can share the work between them
+map_adapter!(MapDivRem, [Copy + Div<D> + Rem<D>], (<I::Item as Div<D>>::Output, <I::Item as Rem<D>>::Output), |value, divisor| (value / divisor, value % divisor));
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use ::{StrengthReducedI32, StrengthReducedU16, StrengthReducedU64};
+
+    #[test]
+    fn test_map_div_rem() {
+        let divisor = StrengthReducedU64::new(7);
+        let values = [0u64, 1, 6, 7, 8, 48, 49, 50, u64::MAX];
+
+        assert!(values.iter().copied().map_div(divisor).eq(values.iter().map(|&value| value / 7)));
+        assert!(values.iter().copied().map_rem(divisor).eq(values.iter().map(|&value| value % 7)));
+        assert!(values.iter().copied().map_div_rem(divisor).eq(values.iter().map(|&value| (value / 7, value % 7))));
+
+        // the adapters keep the inner iterator's length and direction
+        let adapter = values.iter().copied().map_div(divisor);
+        assert_eq!(values.len(), adapter.len());
+        assert!(adapter.rev().eq(values.iter().rev().map(|&value| value / 7)));
+        assert_eq!(values.iter().map(|&value| value % 7).sum::<u64>(), values.iter().copied().map_rem(divisor).sum::<u64>());
+    }
+
+    #[test]
+    fn test_map_div_rem_other_types() {
+        let unsigned: u32 = (0u16..1000).map_div(StrengthReducedU16::new(10)).map(u32::from).sum();
+        assert_eq!((0u16..1000).map(|value| value / 10).map(u32::from).sum::<u32>(), unsigned);
+
+        let signed: [(i32, i32); 4] = [-7, -1, 1, 7].map(|value| (value / 3, value % 3));
+        assert!([-7i32, -1, 1, 7].iter().copied().map_div_rem(StrengthReducedI32::new(3)).eq(signed.iter().copied()));
+    }
+}
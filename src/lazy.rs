@@ -0,0 +1,144 @@
+use core::cell::OnceCell;
+use core::ops::{Div, Rem};
+
+use ::StrengthReducedU64;
+
+/// A `StrengthReducedU64` that waits until the first division to compute its multiplier.
+///
+/// Creating a `StrengthReducedU64` requires a 128-bit division, which is wasted if the divisor never ends up being used.
+/// This type only stores the divisor when it's created, and does the setup work the first time it's needed.
+///
+/// This uses `core::cell::OnceCell`, so it works without `std`, but it isn't `Sync`. To share a divisor between threads, create a `StrengthReducedU64` up front instead.
+#[derive(Clone, Debug)]
+pub struct LazyStrengthReducedU64 {
+    reduced: OnceCell<StrengthReducedU64>,
+    divisor: u64,
+}
+impl LazyStrengthReducedU64 {
+    /// Creates a new divisor instance, without computing the multiplier.
+    ///
+    /// # Panics:
+    /// 
+    /// Panics if `divisor` is 0
+    #[inline]
+    pub const fn new(divisor: u64) -> Self {
+        assert!(divisor > 0);
+        Self { reduced: OnceCell::new(), divisor }
+    }
+
+    /// Creates a new divisor instance, without computing the multiplier, or returns `None` if `divisor` is 0.
+    #[inline]
+    pub const fn try_new(divisor: u64) -> Option<Self> {
+        if divisor == 0 {
+            None
+        } else {
+            Some(Self { reduced: OnceCell::new(), divisor })
+        }
+    }
+
+    /// Returns the strength-reduced divisor, computing it first if this is the first time it's been needed.
+    #[inline]
+    pub fn reduced(&self) -> &StrengthReducedU64 {
+        self.reduced.get_or_init(|| StrengthReducedU64::new(self.divisor))
+    }
+
+    /// Returns true if the multiplier has already been computed.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.reduced.get().is_some()
+    }
+
+    /// Simultaneous truncated integer division and modulus.
+    /// Returns `(quotient, remainder)`.
+    #[inline]
+    pub fn div_rem(numerator: u64, denom: &Self) -> (u64, u64) {
+        StrengthReducedU64::div_rem(numerator, *denom.reduced())
+    }
+
+    /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+    #[inline]
+    pub fn divide(&self, numerator: u64) -> u64 {
+        numerator / self
+    }
+
+    /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+    #[inline]
+    pub fn remainder(&self, numerator: u64) -> u64 {
+        numerator % self
+    }
+
+    /// Retrieve the value used to create this struct
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.divisor
+    }
+}
+
+impl From<StrengthReducedU64> for LazyStrengthReducedU64 {
+    /// Wraps an already-computed divisor, so the first division doesn't need to do any setup work.
+    #[inline]
+    fn from(reduced: StrengthReducedU64) -> Self {
+        Self { reduced: OnceCell::from(reduced), divisor: reduced.get() }
+    }
+}
+
+impl<'a> Div<&'a LazyStrengthReducedU64> for u64 {
+    type Output = u64;
+
+    #[inline]
+    fn div(self, rhs: &'a LazyStrengthReducedU64) -> Self::Output {
+        self / *rhs.reduced()
+    }
+}
+
+impl<'a> Rem<&'a LazyStrengthReducedU64> for u64 {
+    type Output = u64;
+
+    #[inline]
+    fn rem(self, rhs: &'a LazyStrengthReducedU64) -> Self::Output {
+        self % *rhs.reduced()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_strength_reduced_u64() {
+        let max = u64::MAX;
+        let values = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 100, 1000, max / 2, max / 2 + 1, max - 1, max];
+
+        for &divisor in &values {
+            let lazy_divisor = LazyStrengthReducedU64::new(divisor);
+            assert_eq!(divisor, lazy_divisor.get());
+            assert!(!lazy_divisor.is_initialized());
+
+            for &numerator in values.iter().chain(&[0]) {
+                assert_eq!(numerator / divisor, numerator / &lazy_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor, numerator % &lazy_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!((numerator / divisor, numerator % divisor), LazyStrengthReducedU64::div_rem(numerator, &lazy_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!((numerator / divisor, numerator % divisor), (lazy_divisor.divide(numerator), lazy_divisor.remainder(numerator)), "divide/remainder failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+            assert!(lazy_divisor.is_initialized());
+
+            let converted = LazyStrengthReducedU64::from(StrengthReducedU64::new(divisor));
+            assert!(converted.is_initialized());
+            assert_eq!(divisor, converted.get());
+        }
+    }
+
+    #[test]
+    fn test_lazy_try_new() {
+        assert!(LazyStrengthReducedU64::try_new(0).is_none());
+        let lazy = LazyStrengthReducedU64::try_new(7).unwrap();
+        assert!(!lazy.is_initialized());
+        assert_eq!(100 / 7, 100 / &lazy);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lazy_zero() {
+        LazyStrengthReducedU64::new(0);
+    }
+}
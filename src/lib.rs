@@ -34,17 +34,199 @@
 //!
 //! The optimizations that this library provides are inherently dependent on architecture, compiler, and platform,
 //! so test before you use. 
+//!
+//! # Targets without hardware division:
+//!
+//! On targets like Cortex-M0 (`thumbv6m-none-eabi`), every `/` or `%` on a primitive is a software libcall such as `__aeabi_uldivmod`.
+//! `new()` still divides, but `Div`, `Rem`, and `div_rem` only multiply, shift, add, and compare, for every type in this crate. The `no_division_libcalls` test links
+//! all of them into a `thumbv6m-none-eabi` binary and fails if any division routine ends up in it, so it also catches divisions inside the helpers they call.
+//! It runs as part of `cargo test` when that target is installed. Each of those impls also denies clippy's `integer_division_remainder_used` lint, which catches
+//! a `/` or `%` written directly in their bodies on any target. The one exception is `LazyStrengthReducedU64`, whose first operation calls `new()`.
+//!
+//! # Generic code:
+//!
+//! [`StrengthReduced<T>`](StrengthReduced) names the divisor type for any unsigned primitive `T`, so `StrengthReduced<u32>` is `StrengthReducedU32`, and so on. The [`Reducible`] trait is implemented for every unsigned primitive,
+//! and guarantees that `T` can be divided by `StrengthReduced<T>`. For code that's generic over the divisor type itself, including the signed ones, every `StrengthReduced*` type implements [`ReducedDivisor`].
+//! [`DivRem`] mirrors the `Div` and `Rem` traits for computing both at once, and is implemented for every primitive by its divisor type and by itself.
+//! [`StrengthReduce`] adds a `strength_reduce()` method to every unsigned primitive, so `7u64.strength_reduce()` is the same as `StrengthReducedU64::new(7)`.
+//!
+//! # Dividing whole slices:
+//!
+//! [`bulk::div_slice`] and [`bulk::rem_slice`] divide every element of a slice by the same divisor, for any of the unsigned divisor types, and pick the fastest kernel that the CPU supports.
+//! [`bulk::div_rem_slice`] computes both the quotients and the remainders in one pass.
+//! [`bulk::SliceDivExt`] adds the same operations to slices as `div_all`, `rem_all`, and `div_rem_unzip` methods.
+//! For iterator pipelines, [`IteratorDivExt`] adds `map_div`, `map_rem`, and `map_div_rem` adapters to every iterator.
+//! The kernels themselves live in the [`simd`] module, which uses SSE2 for u8 and u16, AVX2 for u32, and, with the `avx512` feature, AVX-512 for u64 on x86 and x86_64 CPUs that support them, and NEON for u8, u16, and u32 on aarch64.
+//!
+//! On 16-bit targets like AVR and MSP430, even 32-bit multiplies are libcalls, so `StrengthReducedU8`, `StrengthReducedU16`, and `StrengthReducedUsize` switch to
+//! algorithms that keep their intermediates in 16 bits: Dividing needs at most one 32-bit multiply, and the remainder never needs a wider one.
+//!
+//! # Modular arithmetic:
+//!
+//! [`ReducedModulus`] wraps a strength-reduced modulus with `add_mod`, `sub_mod`, `neg_mod`, `mul_mod`, and `pow_mod`, so code that works modulo a runtime value doesn't have to compose `%` after every operation.
+//! For a one-off modular exponentiation, `StrengthReducedU64::pow_mod` does the same thing without a separate type.
+//! [`mod_inverse`] computes modular inverses with the extended Euclidean algorithm, for composing CRT or rational reconstruction.
+//! [`CrtBasis`] splits values into residues modulo several coprime moduli, and puts them back together with the Chinese remainder theorem, for multi-modular pipelines.
+//! [`BarrettU32`] and [`BarrettU64`] are the lower-level building block: They reduce double-width values, like the full product of two residues, modulo a fixed divisor.
+//! For long chains of multiplications modulo an odd number, [`MontgomeryU64`] keeps values in Montgomery form, where each multiplication needs no division at all.
+//!
+//! # Optional features:
+//!
+//! - `aarch64-asm`: On aarch64, computes the upper half of every 64x64 multiply with an inline UMULH instruction, instead of trusting the compiler to pick it. Has no effect on other architectures.
+//! - `alloc`: Adds `new_many` to the unsigned types, for creating many divisor instances at once, `DivisorPool`, a bounded cache of recently used divisors, `DivisorArrayU32`/`DivisorArrayU64`, structure-of-arrays containers for many divisors, `bulk::partition_by_rem`, which scatters the indices of a slice of keys by their remainders, and `bulk::sort_by_rem`, which stably groups a slice by remainder.
+//! - `avx512`: Uses AVX-512F and AVX-512DQ kernels for dividing u64 slices in `simd` and `bulk`, on x86 and x86_64 CPUs that support them. Requires rustc 1.89, which stabilized the AVX-512 intrinsics.
+//! - `capi`: Adds the `capi` module, which exports `extern "C"` functions and `#[repr(C)]` structs for using the `u32` and `u64` divisors from C and C++.
+//! - `constant-time`: Makes `Div`, `Rem`, and `div_rem` branch-free for every type, so that they can be used on secret data in cryptographic code. They always take the same multiply path, even for powers of two, which makes them a little slower.
+//!   The only remaining branch is the signed types' overflow panic for `MIN / -1`. `LazyStrengthReducedU64` still branches on whether its first operation has happened yet.
+//! - `num-bigint`: Adds `divide_biguint`, which divides a `BigUint` by a `StrengthReducedU64` one limb at a time, without any hardware division. Enables `alloc`. Has no effect with `strength_reduce_no_u128`.
+//! - `num-traits`: Adds `reduce`, which creates a strength-reduced divisor for any type that implements `PrimInt` and `Unsigned` from the `num-traits` crate, and `ReducibleInt`, which bundles those bounds with `Reducible` for generic code.
+//! - `portable_simd`: Requires nightly. Implements `Div` and `Rem` for `core::simd::Simd<u32, N>` by `StrengthReducedU32`, and for `core::simd::Simd<u64, N>` by `StrengthReducedU64`, dividing every lane by the same divisor. There's no 64-bit high multiply across lanes, so the u64 impls assemble it from 32-bit limbs.
+//!   Also adds `StrengthReducedU32xN`, which divides each lane of a `Simd<u32, N>` by a different divisor.
+//! - `proptest`: Implements proptest's `Arbitrary` for every divisor type, and adds the `strategies` module, with strategies for divisor values that favor edge cases like 1, powers of two, and `MAX`.
+//! - `rand`: Adds `div_stochastic` to the unsigned types, for division with stochastic rounding.
+//! - `rayon`: Adds `bulk::par_div_slice`, `bulk::par_rem_slice`, and `bulk::par_div_rem_slice`, which split large slices across rayon's thread pool. Enables `std`.
+//! - `serde`: Implements `Serialize` and `Deserialize` for every divisor type. Only the divisor is serialized, and deserializing recomputes the multiplier, rejecting a divisor of zero.
+//! - `small-divisor-tables`: Adds precomputed multiplier tables for divisors below 256, so that `new()` can skip its division for tiny divisors. The tables add about 7KB to the binary.
+//! - `std`: Adds `StrengthReducedU64::cached`, which reuses divisor instances from a thread-local cache.
+//!
+//! # Targets without 128-bit arithmetic:
+//!
+//! Building with `RUSTFLAGS="--cfg strength_reduce_no_u128"` is for targets where 128-bit arithmetic is missing or very slow. `StrengthReducedU64` and `StrengthReducedUsize` use a 64-bit "round up" multiplier instead of a 128-bit one, which makes division a little slower on 64-bit targets, but never touches `u128`.
+//! This changes the type of their `multiplier()` and raw parts, and removes `StrengthReducedU128`, `StrengthReducedI128`, `MulShift`, `BarrettU64`, `MontgomeryU64`, `ReducedModulus`, `mod_inverse`, `CrtBasis`, `StrengthReducedU64::pow_mod`, the number theory helpers, `DivisorArrayU64`, and the u64 versions of `fraction_bits` and `div_stochastic`.
+//! Since that isn't additive, it's a cfg that the final binary opts into, rather than a cargo feature that any dependency could turn on.
 #![no_std]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, feature = "num-bigint"))]
 extern crate num_bigint;
-#[cfg(test)]
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+#[cfg(feature = "proptest")]
+#[macro_use]
+extern crate proptest;
+#[cfg(any(test, feature = "rand"))]
 extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "std")]
+extern crate std;
 
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32};
+use core::num::{NonZeroU64, NonZeroUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use core::num::NonZeroU128;
 use core::ops::{Div, Rem};
 
+use constant_time::ConstantTimeInt;
+#[cfg(strength_reduce_no_u128)]
+use no_u128::{MultiplierU64, multiplier_u64, divide_u64, divide_u64_constant_time};
+
+// The divisors that the unit tests check each unsigned type against: every small divisor, a few composites, and the values near the top of the range, where the multipliers are largest
+#[cfg(test)]
+macro_rules! test_divisors {
+    ($primitive_type:ident) => ({
+        let max = $primitive_type::MAX;
+        [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,24,96,100,max/3,max/2,max/2+1,max-1,max]
+    })
+}
+
+mod barrett;
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(all(feature = "num-bigint", not(strength_reduce_no_u128)))]
+mod bigint;
+pub mod bulk;
+#[macro_use]
+mod bytes;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod cmp;
+mod const_divisor;
+mod constant_time;
+pub mod consts;
+#[cfg(not(strength_reduce_no_u128))]
+mod crt;
+mod divisibility;
+#[cfg(feature = "alloc")]
+mod divisor_array;
+mod error;
+mod exact;
+mod fraction;
+mod generic;
+mod iter;
+mod lazy;
+#[cfg(not(strength_reduce_no_u128))]
 mod long_division;
 mod long_multiplication;
+#[cfg(not(strength_reduce_no_u128))]
+mod modular;
+#[cfg(not(strength_reduce_no_u128))]
+mod montgomery;
+#[cfg(not(strength_reduce_no_u128))]
+mod mul_shift;
+mod narrow;
+#[cfg(strength_reduce_no_u128)]
+mod no_u128;
+#[cfg(not(strength_reduce_no_u128))]
+mod number_theory;
+#[cfg(feature = "num-traits")]
+mod prim_int;
+#[cfg(feature = "alloc")]
+mod pool;
+#[cfg(feature = "portable_simd")]
+mod portable_simd;
+mod rounding;
+#[cfg(feature = "serde")]
+mod serialization;
+mod signed;
+pub mod simd;
+#[cfg(feature = "rand")]
+mod stochastic;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod tables;
+
+pub use barrett::BarrettU32;
+#[cfg(not(strength_reduce_no_u128))]
+pub use barrett::BarrettU64;
+#[cfg(all(feature = "num-bigint", not(strength_reduce_no_u128)))]
+pub use bigint::divide_biguint;
+pub use const_divisor::ConstDivisor;
+#[cfg(not(strength_reduce_no_u128))]
+pub use crt::CrtBasis;
+pub use divisibility::{DivisibilityU8, DivisibilityU16, DivisibilityU32, DivisibilityU64, DivisibilityUsize};
+#[cfg(feature = "alloc")]
+pub use divisor_array::DivisorArrayU32;
+#[cfg(all(feature = "alloc", not(strength_reduce_no_u128)))]
+pub use divisor_array::DivisorArrayU64;
+pub use error::DivisorError;
+pub use exact::{ExactDivU32, ExactDivU64};
+pub use generic::{DivRem, Reducible, ReducedDivisor, StrengthReduce, StrengthReduced};
+pub use iter::{IteratorDivExt, MapDiv, MapDivRem, MapRem};
+pub use lazy::LazyStrengthReducedU64;
+#[cfg(not(strength_reduce_no_u128))]
+pub use modular::{mod_inverse, ReducedModulus};
+#[cfg(not(strength_reduce_no_u128))]
+pub use montgomery::MontgomeryU64;
+#[cfg(not(strength_reduce_no_u128))]
+pub use mul_shift::MulShift;
+pub use narrow::StrengthReducedU64Narrow;
+#[cfg(feature = "alloc")]
+pub use pool::DivisorPool;
+#[cfg(feature = "portable_simd")]
+pub use portable_simd::StrengthReducedU32xN;
+#[cfg(feature = "num-traits")]
+pub use prim_int::{reduce, ReducibleInt};
+pub use rounding::Rounding;
+pub use signed::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+pub use signed::StrengthReducedI128;
 
 /// Implements unsigned division and modulo via mutiplication and shifts.
 ///
@@ -53,51 +235,167 @@ mod long_multiplication;
 #[derive(Clone, Copy, Debug)]
 pub struct StrengthReducedU8 {
     multiplier: u16,
-    divisor: u8,
+    divisor: NonZeroU8,
+    // the trailing zeros of the divisor, so that power-of-two divisors don't need to recompute it for every division
+    shift: u8,
 }
 impl StrengthReducedU8 {
     /// Creates a new divisor instance.
     ///
     /// If possible, avoid calling new() from an inner loop: The intended usage is to create an instance of this struct outside the loop, and use it for divison and remainders inside the loop.
     ///
+    /// This is a `const fn`, so it can be used to build `static` or `const` divisors at compile time.
+    ///
     /// # Panics:
     /// 
     /// Panics if `divisor` is 0
     #[inline]
-    pub fn new(divisor: u8) -> Self {
-        assert!(divisor > 0);
+    pub const fn new(divisor: u8) -> Self {
+        match NonZeroU8::new(divisor) {
+            Some(divisor) => Self::new_impl(divisor),
+            None => panic!("attempt to create a divisor of zero"),
+        }
+    }
 
-        if divisor.is_power_of_two() { 
-            Self{ multiplier: 0, divisor }
-        } else {
-            let divided = core::u16::MAX / (divisor as u16);
-            Self{ multiplier: divided + 1, divisor }
+    #[inline]
+    const fn new_impl(nonzero_divisor: NonZeroU8) -> Self {
+        let divisor = nonzero_divisor.get();
+        // there are only 256 possible divisors, so we precompute all of their multipliers
+        Self{ multiplier: tables::MULTIPLIERS_U16[divisor as usize], divisor: nonzero_divisor, shift: divisor.trailing_zeros() as u8 }
+    }
+
+    /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+    ///
+    /// Use this instead of `new()` when the divisor comes from untrusted input.
+    #[inline]
+    pub const fn try_new(divisor: u8) -> Option<Self> {
+        match NonZeroU8::new(divisor) {
+            Some(divisor) => Some(Self::new_impl(divisor)),
+            None => None,
         }
     }
 
+    /// Creates a new divisor instance without checking that `divisor` is nonzero.
+    ///
+    /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+    ///
+    /// # Safety
+    ///
+    /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+    #[inline]
+    pub unsafe fn new_unchecked(divisor: u8) -> Self {
+        debug_assert!(divisor != 0);
+        Self::new_impl(NonZeroU8::new_unchecked(divisor))
+    }
+
     /// Simultaneous truncated integer division and modulus.
     /// Returns `(quotient, remainder)`.
+    ///
+    /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`].
     #[inline]
+    #[deny(clippy::integer_division_remainder_used)]
     pub fn div_rem(numerator: u8, denom: Self) -> (u8, u8) {
         let quotient = numerator / denom;
-        let remainder = numerator % denom;
+        let remainder = numerator - quotient * denom.divisor.get();
         (quotient, remainder)
     }
 
+    /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+    #[inline]
+    pub fn divide(&self, numerator: u8) -> u8 {
+        numerator / *self
+    }
+
+    /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+    #[inline]
+    pub fn remainder(&self, numerator: u8) -> u8 {
+        numerator % *self
+    }
+
     /// Retrieve the value used to create this struct
     #[inline]
     pub fn get(&self) -> u8 {
+        self.divisor.get()
+    }
+
+    /// Retrieve the value used to create this struct, as a nonzero integer
+    #[inline]
+    pub fn get_nonzero(&self) -> NonZeroU8 {
         self.divisor
     }
+
+    /// Returns the multiplier that this divisor uses in place of division, or 0 if the divisor is a power of two.
+    ///
+    /// Unless `is_shift_only()` is true, the multiplier is `floor(2^16 / divisor) + 1`, and the quotient of `n` is `(n * multiplier) >> shift()`, computed in 32 bits.
+    /// Together with `shift()` and `is_shift_only()`, this keeps the same meaning in every 0.3.x release, so code generators can rely on it.
+    #[inline]
+    pub const fn multiplier(&self) -> u16 {
+        self.multiplier
+    }
+
+    /// Returns the right shift that finishes a division by this divisor.
+    ///
+    /// For a power of two, this is its trailing zeros, and the quotient of `n` is `n >> shift()`. Otherwise, it's always 16, the number of fraction bits in the multiplier.
+    #[inline]
+    pub const fn shift(&self) -> u32 {
+        if self.multiplier == 0 { self.shift as u32 } else { 16 }
+    }
+
+    /// Returns true if the divisor is a power of two, so that dividing by it is just a right shift by `shift()`.
+    #[inline]
+    pub const fn is_shift_only(&self) -> bool {
+        self.multiplier == 0
+    }
+
+    /// Returns the precomputed constants of this divisor as `(multiplier, divisor, shift)`.
+    ///
+    /// Passing them to `from_raw_parts` rebuilds the divisor without recomputing anything, so JIT compilers and FFI layers can move divisors across a boundary as plain integers.
+    #[inline]
+    pub const fn into_raw_parts(self) -> (u16, u8, u8) {
+        (self.multiplier, self.divisor.get(), self.shift)
+    }
+
+    /// Rebuilds a divisor from the output of `into_raw_parts`, without recomputing the multiplier.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have come from `into_raw_parts` on this type, in a build of the same version of this crate with the same features. A `divisor` of 0 is undefined behavior, like in `new_unchecked`,
+    /// and any other mismatch makes every division return the wrong result. In debug builds, the divisor is checked with an assertion.
+    #[inline]
+    pub unsafe fn from_raw_parts(multiplier: u16, divisor: u8, shift: u8) -> Self {
+        debug_assert!(divisor != 0);
+        Self{ multiplier, divisor: NonZeroU8::new_unchecked(divisor), shift }
+    }
+}
+
+impl Default for StrengthReducedU8 {
+    /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+    #[inline]
+    fn default() -> Self {
+        Self::new(1)
+    }
 }
 
+impl From<NonZeroU8> for StrengthReducedU8 {
+    /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+    #[inline]
+    fn from(divisor: NonZeroU8) -> Self {
+        Self::new_impl(divisor)
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
 impl Div<StrengthReducedU8> for u8 {
     type Output = u8;
 
     #[inline]
     fn div(self, rhs: StrengthReducedU8) -> Self::Output {
-        if rhs.multiplier == 0 {
-            (self as u16 >> rhs.divisor.trailing_zeros()) as u8
+        if cfg!(feature = "constant-time") {
+            // Powers of two store a multiplier of 0, but their real multiplier is 2^(16 - shift), so we mask it back in instead of branching. For a divisor of 1, that's 2^16, so we multiply in 32 bits
+            let multiplier = rhs.multiplier as u32 | (u32::mask(rhs.multiplier == 0) & (1 << (16 - rhs.shift)));
+            ((self as u32 * multiplier) >> 16) as u8
+        } else if rhs.multiplier == 0 {
+            (self as u16 >> rhs.shift) as u8
         } else {
             let numerator = self as u16;
             let multiplied_hi = numerator * (rhs.multiplier >> 8);
@@ -108,16 +406,24 @@ impl Div<StrengthReducedU8> for u8 {
     }
 }
 
+#[deny(clippy::integer_division_remainder_used)]
 impl Rem<StrengthReducedU8> for u8 {
     type Output = u8;
 
     #[inline]
     fn rem(self, rhs: StrengthReducedU8) -> Self::Output {
-        if rhs.multiplier == 0 {
-            self & (rhs.divisor - 1)
+        if cfg!(feature = "constant-time") {
+            let quotient = self / rhs;
+            self - quotient * rhs.divisor.get()
+        } else if rhs.multiplier == 0 {
+            self & (rhs.divisor.get() - 1)
+        } else if cfg!(target_pointer_width = "16") {
+            // 8- and 16-bit microcontrollers don't have a fast 32-bit multiply, but the quotient only needs 8x8 multiplies, and so does multiplying it back
+            let quotient = self / rhs;
+            self - quotient.wrapping_mul(rhs.divisor.get())
         } else {
             let product = rhs.multiplier.wrapping_mul(self as u16) as u32;
-            let divisor = rhs.divisor as u32;
+            let divisor = rhs.divisor.get() as u32;
 
             let shifted = (product * divisor) >> 16;
             shifted as u8
@@ -125,9 +431,11 @@ impl Rem<StrengthReducedU8> for u8 {
     }
 }
 
+state_bytes_impl!(StrengthReducedU8, u8, [multiplier: u16, shift: u8]);
+
 // small types prefer to do work in the intermediate type
 macro_rules! strength_reduced_u16 {
-    ($struct_name:ident, $primitive_type:ident) => (
+    ($struct_name:ident, $primitive_type:ident, $nonzero_type:ident) => (
         /// Implements unsigned division and modulo via mutiplication and shifts.
         ///
         /// Creating a an instance of this struct is more expensive than a single division, but if the division is repeated,
@@ -135,51 +443,199 @@ macro_rules! strength_reduced_u16 {
         #[derive(Clone, Copy, Debug)]
         pub struct $struct_name {
             multiplier: u32,
-            divisor: $primitive_type,
+            divisor: $nonzero_type,
+            // the trailing zeros of the divisor, so that power-of-two divisors don't need to recompute it for every division
+            shift: u8,
+            // true if the divisor is greater than MAX / 2, so that every quotient is either 0 or 1
+            large: bool,
         }
         impl $struct_name {
             /// Creates a new divisor instance.
             ///
             /// If possible, avoid calling new() from an inner loop: The intended usage is to create an instance of this struct outside the loop, and use it for divison and remainders inside the loop.
             ///
+            /// This is a `const fn`, so it can be used to build `static` or `const` divisors at compile time.
+            ///
             /// # Panics:
             /// 
             /// Panics if `divisor` is 0
             #[inline]
-            pub fn new(divisor: $primitive_type) -> Self {
-                assert!(divisor > 0);
+            pub const fn new(divisor: $primitive_type) -> Self {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Self::new_impl(divisor),
+                    None => panic!("attempt to create a divisor of zero"),
+                }
+            }
+
+            #[inline]
+            const fn new_impl(nonzero_divisor: $nonzero_type) -> Self {
+                let divisor = nonzero_divisor.get();
+                let shift = divisor.trailing_zeros() as u8;
+                let large = divisor > $primitive_type::MAX / 2;
+                #[cfg(feature = "small-divisor-tables")]
+                {
+                    if divisor < 256 {
+                        return Self{ multiplier: tables::SMALL_MULTIPLIERS_U32[divisor as usize], divisor: nonzero_divisor, shift, large };
+                    }
+                }
 
                 if divisor.is_power_of_two() { 
-                    Self{ multiplier: 0, divisor }
+                    Self{ multiplier: 0, divisor: nonzero_divisor, shift, large }
                 } else {
-                    let divided = core::u32::MAX / (divisor as u32);
-                    Self{ multiplier: divided + 1, divisor }
+                    let divided = u32::MAX / (divisor as u32);
+                    Self{ multiplier: divided + 1, divisor: nonzero_divisor, shift, large }
                 }
             }
 
+            /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+            ///
+            /// Use this instead of `new()` when the divisor comes from untrusted input.
+            #[inline]
+            pub const fn try_new(divisor: $primitive_type) -> Option<Self> {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Some(Self::new_impl(divisor)),
+                    None => None,
+                }
+            }
+
+            /// Creates a new divisor instance without checking that `divisor` is nonzero.
+            ///
+            /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+            ///
+            /// # Safety
+            ///
+            /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+            #[inline]
+            pub unsafe fn new_unchecked(divisor: $primitive_type) -> Self {
+                debug_assert!(divisor != 0);
+                Self::new_impl($nonzero_type::new_unchecked(divisor))
+            }
+
             /// Simultaneous truncated integer division and modulus.
             /// Returns `(quotient, remainder)`.
+            ///
+            /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`].
             #[inline]
+            #[deny(clippy::integer_division_remainder_used)]
             pub fn div_rem(numerator: $primitive_type, denom: Self) -> ($primitive_type, $primitive_type) {
                 let quotient = numerator / denom;
-                let remainder = numerator - quotient * denom.divisor;
+                let remainder = numerator - quotient * denom.divisor.get();
                 (quotient, remainder)
             }
 
+            /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+            #[inline]
+            pub fn divide(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator / *self
+            }
+
+            /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+            #[inline]
+            pub fn remainder(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator % *self
+            }
+
             /// Retrieve the value used to create this struct
             #[inline]
             pub fn get(&self) -> $primitive_type {
+                self.divisor.get()
+            }
+
+            /// Retrieve the value used to create this struct, as a nonzero integer
+            #[inline]
+            pub fn get_nonzero(&self) -> $nonzero_type {
                 self.divisor
             }
+
+            /// Returns the multiplier that this divisor uses in place of division, or 0 if the divisor is a power of two.
+            ///
+            /// Unless `is_shift_only()` is true, the multiplier is `floor(2^32 / divisor) + 1`, and the quotient of `n` is `(n * multiplier) >> shift()`, computed in 64 bits.
+            /// Together with `shift()` and `is_shift_only()`, this keeps the same meaning in every 0.3.x release, so code generators can rely on it.
+            #[inline]
+            pub const fn multiplier(&self) -> u32 {
+                self.multiplier
+            }
+
+            /// Returns the right shift that finishes a division by this divisor.
+            ///
+            /// For a power of two, this is its trailing zeros, and the quotient of `n` is `n >> shift()`. Otherwise, it's always 32, the number of fraction bits in the multiplier.
+            #[inline]
+            pub const fn shift(&self) -> u32 {
+                if self.multiplier == 0 { self.shift as u32 } else { 32 }
+            }
+
+            /// Returns true if the divisor is a power of two, so that dividing by it is just a right shift by `shift()`.
+            #[inline]
+            pub const fn is_shift_only(&self) -> bool {
+                self.multiplier == 0
+            }
+
+            /// Returns the precomputed constants of this divisor as `(multiplier, divisor, shift)`.
+            ///
+            /// Passing them to `from_raw_parts` rebuilds the divisor without recomputing anything, so JIT compilers and FFI layers can move divisors across a boundary as plain integers.
+            #[inline]
+            pub const fn into_raw_parts(self) -> (u32, $primitive_type, u8) {
+                (self.multiplier, self.divisor.get(), self.shift)
+            }
+
+            /// Rebuilds a divisor from the output of `into_raw_parts`, without recomputing the multiplier.
+            ///
+            /// # Safety
+            ///
+            /// The parts must have come from `into_raw_parts` on this type, in a build of the same version of this crate with the same features. A `divisor` of 0 is undefined behavior, like in `new_unchecked`,
+            /// and any other mismatch makes every division return the wrong result. In debug builds, the divisor is checked with an assertion.
+            #[inline]
+            pub unsafe fn from_raw_parts(multiplier: u32, divisor: $primitive_type, shift: u8) -> Self {
+                debug_assert!(divisor != 0);
+                Self{ multiplier, divisor: $nonzero_type::new_unchecked(divisor), shift, large: divisor > $primitive_type::MAX / 2 }
+            }
+        }
+
+        impl Default for $struct_name {
+            /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+            #[inline]
+            fn default() -> Self {
+                Self::new(1)
+            }
+        }
+
+        impl From<$nonzero_type> for $struct_name {
+            /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+            #[inline]
+            fn from(divisor: $nonzero_type) -> Self {
+                Self::new_impl(divisor)
+            }
         }
 
+        #[deny(clippy::integer_division_remainder_used)]
         impl Div<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn div(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self >> rhs.divisor.trailing_zeros()
+                if cfg!(feature = "constant-time") {
+                    // mask the power of two multiplier back in, like StrengthReducedU8 does. The multiplier for a divisor of 1 needs 33 bits, so we multiply in 64 bits
+                    let multiplier = rhs.multiplier as u64 | (u64::mask(rhs.multiplier == 0) & (1 << (32 - rhs.shift)));
+                    ((self as u64 * multiplier) >> 32) as $primitive_type
+                } else if rhs.multiplier == 0 {
+                    self >> rhs.shift
+                } else if rhs.large {
+                    // the quotient is either 0 or 1, so a comparison is enough
+                    (self >= rhs.divisor.get()) as $primitive_type
+                } else if cfg!(target_pointer_width = "16") {
+                    // On 8- and 16-bit microcontrollers, every 32-bit multiply is a libcall, so instead of splitting the multiplier into two multiplies, we use Granlund and Montgomery's 17-bit "round up" multiplier, with its implicit top bit dropped.
+                    // It's floor(2^(16 + l) / divisor) + 1 where l is the divisor's bit length, which we can derive from the stored multiplier, and since it only depends on the divisor, it gets hoisted out of loops.
+                    // 32-bit shifts are also slow there, so we shift the halves of the stored multiplier separately. The divisor isn't a power of two or large, so the shift is between 1 and 14
+                    let bits = 16 - rhs.divisor.leading_zeros();
+                    let shifted_hi = ((rhs.multiplier - 1) >> 16) as u16;
+                    let shifted_lo = (rhs.multiplier - 1) as u16;
+                    let multiplier = ((shifted_hi << bits) | (shifted_lo >> (16 - bits))).wrapping_add(1);
+                    let numerator = self as u16;
+
+                    let upper = ((numerator as u32 * multiplier as u32) >> 16) as u16;
+
+                    // adding the implicit top bit's contribution directly could overflow, so average them instead, and shift one bit less
+                    ((upper + ((numerator - upper) >> 1)) >> (bits - 1)) as $primitive_type
                 } else {
                     let numerator = self as u32;
                     let multiplied_hi = numerator * (rhs.multiplier >> 16);
@@ -190,25 +646,40 @@ macro_rules! strength_reduced_u16 {
             }
         }
 
+        #[deny(clippy::integer_division_remainder_used)]
         impl Rem<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn rem(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self & (rhs.divisor - 1)
-                } else {
+                if cfg!(feature = "constant-time") {
+                    let quotient = self / rhs;
+                    self - quotient * rhs.divisor.get()
+                } else if rhs.multiplier == 0 {
+                    self & (rhs.divisor.get() - 1)
+                } else if rhs.large {
+                    if self >= rhs.divisor.get() { self - rhs.divisor.get() } else { self }
+                } else if cfg!(target_pointer_width = "16") {
+                    // the 64-bit multiply below would be a long libcall sequence on 8- and 16-bit microcontrollers, so multiply-subtract the quotient instead
                     let quotient = self / rhs;
-                    self - quotient * rhs.divisor
+                    self - quotient.wrapping_mul(rhs.divisor.get())
+                } else {
+                    let product = rhs.multiplier.wrapping_mul(self as u32) as u64;
+                    let divisor = rhs.divisor.get() as u64;
+
+                    let shifted = (product * divisor) >> 32;
+                    shifted as $primitive_type
                 }
             }
         }
+
+        state_bytes_impl!($struct_name, $primitive_type, [multiplier: u32, shift: u8, large: bool]);
     )
 }
 
 // small types prefer to do work in the intermediate type
 macro_rules! strength_reduced_u32 {
-    ($struct_name:ident, $primitive_type:ident) => (
+    ($struct_name:ident, $primitive_type:ident, $nonzero_type:ident) => (
         /// Implements unsigned division and modulo via mutiplication and shifts.
         ///
         /// Creating a an instance of this struct is more expensive than a single division, but if the division is repeated,
@@ -216,60 +687,213 @@ macro_rules! strength_reduced_u32 {
         #[derive(Clone, Copy, Debug)]
         pub struct $struct_name {
             multiplier: u64,
-            divisor: $primitive_type,
+            divisor: $nonzero_type,
+            // the trailing zeros of the divisor, so that power-of-two divisors don't need to recompute it for every division
+            shift: u8,
+            // true if the divisor is greater than MAX / 2, so that every quotient is either 0 or 1
+            large: bool,
         }
         impl $struct_name {
             /// Creates a new divisor instance.
             ///
             /// If possible, avoid calling new() from an inner loop: The intended usage is to create an instance of this struct outside the loop, and use it for divison and remainders inside the loop.
             ///
+            /// This is a `const fn`, so it can be used to build `static` or `const` divisors at compile time.
+            ///
             /// # Panics:
             /// 
             /// Panics if `divisor` is 0
             #[inline]
-            pub fn new(divisor: $primitive_type) -> Self {
-                assert!(divisor > 0);
+            pub const fn new(divisor: $primitive_type) -> Self {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Self::new_impl(divisor),
+                    None => panic!("attempt to create a divisor of zero"),
+                }
+            }
+
+            #[inline]
+            const fn new_impl(nonzero_divisor: $nonzero_type) -> Self {
+                let divisor = nonzero_divisor.get();
+                let shift = divisor.trailing_zeros() as u8;
+                #[cfg(feature = "small-divisor-tables")]
+                {
+                    if divisor < 256 {
+                        return Self::from_parts(tables::SMALL_MULTIPLIERS_U64[divisor as usize], nonzero_divisor, shift);
+                    }
+                }
 
                 if divisor.is_power_of_two() { 
-                    Self{ multiplier: 0, divisor }
+                    Self::from_parts(0, nonzero_divisor, shift)
                 } else {
-                    let divided = core::u64::MAX / (divisor as u64);
-                    Self{ multiplier: divided + 1, divisor }
+                    let divided = u64::MAX / (divisor as u64);
+                    Self::from_parts(divided + 1, nonzero_divisor, shift)
                 }
             }
 
+            // rebuilds an instance from its stored parts, and recomputes the flags that only depend on the divisor
+            #[inline]
+            pub(crate) const fn from_parts(multiplier: u64, divisor: $nonzero_type, shift: u8) -> Self {
+                Self{ multiplier, divisor, shift, large: divisor.get() > $primitive_type::MAX / 2 }
+            }
+
+            /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+            ///
+            /// Use this instead of `new()` when the divisor comes from untrusted input.
+            #[inline]
+            pub const fn try_new(divisor: $primitive_type) -> Option<Self> {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Some(Self::new_impl(divisor)),
+                    None => None,
+                }
+            }
+
+            /// Creates a new divisor instance without checking that `divisor` is nonzero.
+            ///
+            /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+            ///
+            /// # Safety
+            ///
+            /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+            #[inline]
+            pub unsafe fn new_unchecked(divisor: $primitive_type) -> Self {
+                debug_assert!(divisor != 0);
+                Self::new_impl($nonzero_type::new_unchecked(divisor))
+            }
+
             /// Simultaneous truncated integer division and modulus.
             /// Returns `(quotient, remainder)`.
+            ///
+            /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`].
             #[inline]
+            #[deny(clippy::integer_division_remainder_used)]
             pub fn div_rem(numerator: $primitive_type, denom: Self) -> ($primitive_type, $primitive_type) {
-                if denom.multiplier == 0 {
-                    (numerator >> denom.divisor.trailing_zeros(), numerator & (denom.divisor - 1))
+                if cfg!(feature = "constant-time") {
+                    let quotient = numerator / denom;
+                    (quotient, numerator - quotient * denom.divisor.get())
+                } else if denom.multiplier == 0 {
+                    (numerator >> denom.shift, numerator & (denom.divisor.get() - 1))
                 }
                 else {
-                    let numerator64 = numerator as u64;
-                    let multiplied_hi = numerator64 * (denom.multiplier >> 32);
-                    let multiplied_lo = numerator64 * (denom.multiplier as u32 as u64) >> 32;
-
-                    let quotient = ((multiplied_hi + multiplied_lo) >> 32) as $primitive_type;
-                    let remainder = numerator - quotient * denom.divisor;
+                    // Rem can derive the remainder from the fraction bits instead, but that needs a second wide multiply, and benchmarks show that multiply-subtracting the quotient is faster
+                    let quotient = numerator / denom;
+                    let remainder = numerator - quotient * denom.divisor.get();
                     (quotient, remainder)
                 }
             }
 
+            /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+            #[inline]
+            pub fn divide(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator / *self
+            }
+
+            /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+            #[inline]
+            pub fn remainder(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator % *self
+            }
+
             /// Retrieve the value used to create this struct
             #[inline]
             pub fn get(&self) -> $primitive_type {
+                self.divisor.get()
+            }
+
+            /// Retrieve the value used to create this struct, as a nonzero integer
+            #[inline]
+            pub fn get_nonzero(&self) -> $nonzero_type {
                 self.divisor
             }
+
+            /// Returns the multiplier that this divisor uses in place of division, or 0 if the divisor is a power of two.
+            ///
+            /// Unless `is_shift_only()` is true, the multiplier is `floor(2^64 / divisor) + 1`, and the quotient of `n` is `(n * multiplier) >> shift()`, computed in 128 bits.
+            /// Together with `shift()` and `is_shift_only()`, this keeps the same meaning in every 0.3.x release, so code generators can rely on it.
+            #[inline]
+            pub const fn multiplier(&self) -> u64 {
+                self.multiplier
+            }
+
+            /// Returns the right shift that finishes a division by this divisor.
+            ///
+            /// For a power of two, this is its trailing zeros, and the quotient of `n` is `n >> shift()`. Otherwise, it's always 64, the number of fraction bits in the multiplier.
+            #[inline]
+            pub const fn shift(&self) -> u32 {
+                if self.multiplier == 0 { self.shift as u32 } else { 64 }
+            }
+
+            /// Returns true if the divisor is a power of two, so that dividing by it is just a right shift by `shift()`.
+            #[inline]
+            pub const fn is_shift_only(&self) -> bool {
+                self.multiplier == 0
+            }
+
+            /// Returns the precomputed constants of this divisor as `(multiplier, divisor, shift)`.
+            ///
+            /// Passing them to `from_raw_parts` rebuilds the divisor without recomputing anything, so JIT compilers and FFI layers can move divisors across a boundary as plain integers.
+            #[inline]
+            pub const fn into_raw_parts(self) -> (u64, $primitive_type, u8) {
+                (self.multiplier, self.divisor.get(), self.shift)
+            }
+
+            /// Rebuilds a divisor from the output of `into_raw_parts`, without recomputing the multiplier.
+            ///
+            /// # Safety
+            ///
+            /// The parts must have come from `into_raw_parts` on this type, in a build of the same version of this crate with the same features. A `divisor` of 0 is undefined behavior, like in `new_unchecked`,
+            /// and any other mismatch makes every division return the wrong result. In debug builds, the divisor is checked with an assertion.
+            #[inline]
+            pub unsafe fn from_raw_parts(multiplier: u64, divisor: $primitive_type, shift: u8) -> Self {
+                debug_assert!(divisor != 0);
+                Self::from_parts(multiplier, $nonzero_type::new_unchecked(divisor), shift)
+            }
         }
 
+        impl Default for $struct_name {
+            /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+            #[inline]
+            fn default() -> Self {
+                Self::new(1)
+            }
+        }
+
+        impl From<$nonzero_type> for $struct_name {
+            /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+            #[inline]
+            fn from(divisor: $nonzero_type) -> Self {
+                Self::new_impl(divisor)
+            }
+        }
+
+        #[deny(clippy::integer_division_remainder_used)]
         impl Div<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn div(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self >> rhs.divisor.trailing_zeros()
+                if cfg!(feature = "constant-time") {
+                    // Mask the power of two multiplier back in, like StrengthReducedU8 does, and split it into two multiplies. The multiplier for a divisor of 1 would need 65 bits,
+                    // so we leave it at 0, and mask the numerator into the quotient instead
+                    let multiplier = rhs.multiplier | (u64::mask(rhs.multiplier == 0) & ((1 << (63 - rhs.shift)) << 1));
+                    let numerator = self as u64;
+                    let multiplied_hi = numerator * (multiplier >> 32);
+                    let multiplied_lo = (numerator * (multiplier as u32 as u64)) >> 32;
+
+                    ((multiplied_hi + multiplied_lo) >> 32) as $primitive_type | (self & $primitive_type::mask(rhs.divisor.get() == 1))
+                } else if rhs.multiplier == 0 {
+                    self >> rhs.shift
+                } else if rhs.large {
+                    // the quotient is either 0 or 1, so a comparison is enough
+                    (self >= rhs.divisor.get()) as $primitive_type
+                } else if rhs.shift > 0 {
+                    // Even divisor: pre-shift the numerator by the divisor's trailing zeros, and divide by the odd part. The shifted numerator has fewer than 32 bits,
+                    // so the odd part's multiplier fits in 32 bits and a single widening multiply is exact. We derive it from the stored multiplier, and since it only depends on the divisor, it gets hoisted out of loops.
+                    let shift = rhs.shift as u32;
+                    let odd_bits = 32 - rhs.divisor.leading_zeros() - shift;
+                    let odd_multiplier = (((rhs.multiplier - 1) >> (32 - odd_bits)) + 1) as u32 as u64;
+
+                    let numerator = (self >> shift) as u64;
+                    ((numerator * odd_multiplier) >> (32 - shift + odd_bits)) as $primitive_type
                 } else {
                     let numerator = self as u64;
                     let multiplied_hi = numerator * (rhs.multiplier >> 32);
@@ -280,123 +904,342 @@ macro_rules! strength_reduced_u32 {
             }
         }
 
+        #[deny(clippy::integer_division_remainder_used)]
         impl Rem<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn rem(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self & (rhs.divisor - 1)
+                if cfg!(feature = "constant-time") {
+                    let quotient = self / rhs;
+                    self - quotient * rhs.divisor.get()
+                } else if rhs.multiplier == 0 {
+                    self & (rhs.divisor.get() - 1)
+                } else if rhs.large {
+                    if self >= rhs.divisor.get() { self - rhs.divisor.get() } else { self }
                 } else {
                     let product = rhs.multiplier.wrapping_mul(self as u64) as u128;
-                    let divisor = rhs.divisor as u128;
+                    let divisor = rhs.divisor.get() as u128;
 
                     let shifted = (product * divisor) >> 64;
                     shifted as $primitive_type
                 }
             }
         }
+
+        state_bytes_impl!($struct_name, $primitive_type, [multiplier: u64, shift: u8, large: bool]);
     )
 }
 
+// By default, the 64-bit types store the 128-bit multiplier floor(2^128 / divisor) + 1. With strength_reduce_no_u128, no_u128.rs provides a 64-bit multiplier instead,
+// along with versions of these functions that never touch u128, so strength_reduced_u64 only differs in how it computes the multiplier and multiplies by it
+#[cfg(not(strength_reduce_no_u128))]
+type MultiplierU64 = u128;
+
+// computes the multiplier for a divisor that isn't a power of two
+#[cfg(not(strength_reduce_no_u128))]
+#[inline]
+const fn multiplier_u64(divisor: u64) -> u128 {
+    long_division::divide_128_max_by_64(divisor) + 1
+}
+
+// divides by a divisor that isn't a power of two, and isn't greater than u64::MAX / 2. everything derived from the divisor and multiplier gets hoisted out of loops
+#[cfg(not(strength_reduce_no_u128))]
+#[inline]
+fn divide_u64(numerator: u64, multiplier: u128, divisor: u64) -> u64 {
+    let shift = divisor.trailing_zeros();
+    if divisor <= u32::MAX as u64 && numerator <= u32::MAX as u64 {
+        // Both the divisor and the numerator fit in 32 bits, so the 64-bit multiplier ceil(2^64 / divisor) is exact, and we can split it into two 32x32->64 multiplies instead of doing any 128-bit math.
+        // We derive it from the top half of the stored multiplier
+        let multiplier = ((multiplier - 1) >> 64) as u64 + 1;
+
+        let multiplied_hi = numerator * (multiplier >> 32);
+        let multiplied_lo = (numerator * (multiplier as u32 as u64)) >> 32;
+
+        (multiplied_hi + multiplied_lo) >> 32
+    } else if shift > 0 {
+        // Even divisor: pre-shift the numerator by the divisor's trailing zeros, and divide by the odd part. The shifted numerator has fewer than 64 bits,
+        // so the odd part's multiplier fits in 64 bits and a single widening multiply is exact. We derive it from the stored multiplier
+        let odd_bits = 64 - divisor.leading_zeros() - shift;
+        let odd_multiplier = (((multiplier - 1) >> (64 - odd_bits)) + 1) as u64;
+
+        // Clearing the numerator's low bits instead of shifting them out lines the product up on a 64-bit boundary, so that we only need its upper half
+        let numerator = numerator >> shift << shift;
+        long_multiplication::multiply_64_by_64_upperbits(numerator, odd_multiplier) >> odd_bits
+    } else {
+        // Instead of splitting the 128-bit multiplier into two multiplies, we use Granlund and Montgomery's 65-bit "round up" multiplier, with its implicit top bit dropped.
+        // It's floor(2^(64 + l) / divisor) + 1 where l is the divisor's bit length, which we can derive from the stored multiplier, like the even path above
+        let odd_bits = 64 - divisor.leading_zeros();
+        let multiplier = (((multiplier - 1) >> (64 - odd_bits)) + 1) as u64;
+
+        let upper = long_multiplication::multiply_64_by_64_upperbits(numerator, multiplier);
+
+        // adding the implicit top bit's contribution directly could overflow, so average them instead, and shift one bit less
+        (upper + ((numerator - upper) >> 1)) >> (odd_bits - 1)
+    }
+}
+
+// divides by any divisor without branching, for the constant-time feature
+#[cfg(not(strength_reduce_no_u128))]
+#[inline]
+fn divide_u64_constant_time(numerator: u64, multiplier: u128, divisor: u64) -> u64 {
+    // same as StrengthReducedU32, with a 128-bit multiplier
+    let multiplier = multiplier | (u128::mask(multiplier == 0) & ((1 << (127 - divisor.trailing_zeros())) << 1));
+    let numerator_wide = numerator as u128;
+    let multiplied_hi = numerator_wide * (multiplier >> 64);
+    let multiplied_lo = (numerator_wide * (multiplier as u64 as u128)) >> 64;
+
+    ((multiplied_hi + multiplied_lo) >> 64) as u64 | (numerator & u64::mask(divisor == 1))
+}
+
 macro_rules! strength_reduced_u64 {
-    ($struct_name:ident, $primitive_type:ident) => (
+    ($struct_name:ident, $primitive_type:ident, $nonzero_type:ident) => (
         /// Implements unsigned division and modulo via mutiplication and shifts.
         ///
         /// Creating a an instance of this struct is more expensive than a single division, but if the division is repeated,
         /// this version will be several times faster than naive division.
         #[derive(Clone, Copy, Debug)]
         pub struct $struct_name {
-            multiplier: u128,
-            divisor: $primitive_type,
+            multiplier: MultiplierU64,
+            divisor: $nonzero_type,
+            // the trailing zeros of the divisor, so that power-of-two divisors don't need to recompute it for every division
+            shift: u8,
+            // true if the divisor is greater than MAX / 2, so that every quotient is either 0 or 1
+            large: bool,
         }
         impl $struct_name {
             /// Creates a new divisor instance.
             ///
             /// If possible, avoid calling new() from an inner loop: The intended usage is to create an instance of this struct outside the loop, and use it for divison and remainders inside the loop.
             ///
+            /// This is a `const fn`, so it can be used to build `static` or `const` divisors at compile time.
+            ///
             /// # Panics:
             /// 
             /// Panics if `divisor` is 0
             #[inline]
-            pub fn new(divisor: $primitive_type) -> Self {
-                assert!(divisor > 0);
+            pub const fn new(divisor: $primitive_type) -> Self {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Self::new_impl(divisor),
+                    None => panic!("attempt to create a divisor of zero"),
+                }
+            }
+
+            #[inline]
+            const fn new_impl(nonzero_divisor: $nonzero_type) -> Self {
+                let divisor = nonzero_divisor.get();
+                let shift = divisor.trailing_zeros() as u8;
+                #[cfg(all(feature = "small-divisor-tables", not(strength_reduce_no_u128)))]
+                {
+                    if divisor < 256 {
+                        return Self::from_parts(tables::SMALL_MULTIPLIERS_U128[divisor as usize], nonzero_divisor, shift);
+                    }
+                }
 
                 if divisor.is_power_of_two() { 
-                    Self{ multiplier: 0, divisor }
+                    Self::from_parts(0, nonzero_divisor, shift)
                 } else {
-                    let quotient = long_division::divide_128_max_by_64(divisor as u64);
-                    Self{ multiplier: quotient + 1, divisor }
+                    Self::from_parts(multiplier_u64(divisor as u64), nonzero_divisor, shift)
+                }
+            }
+
+            // rebuilds an instance from its stored parts, and recomputes the flags that only depend on the divisor
+            #[inline]
+            pub(crate) const fn from_parts(multiplier: MultiplierU64, divisor: $nonzero_type, shift: u8) -> Self {
+                Self{ multiplier, divisor, shift, large: divisor.get() > $primitive_type::MAX / 2 }
+            }
+
+            /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+            ///
+            /// Use this instead of `new()` when the divisor comes from untrusted input.
+            #[inline]
+            pub const fn try_new(divisor: $primitive_type) -> Option<Self> {
+                match $nonzero_type::new(divisor) {
+                    Some(divisor) => Some(Self::new_impl(divisor)),
+                    None => None,
                 }
             }
+
+            /// Creates a new divisor instance without checking that `divisor` is nonzero.
+            ///
+            /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+            ///
+            /// # Safety
+            ///
+            /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+            #[inline]
+            pub unsafe fn new_unchecked(divisor: $primitive_type) -> Self {
+                debug_assert!(divisor != 0);
+                Self::new_impl($nonzero_type::new_unchecked(divisor))
+            }
             /// Simultaneous truncated integer division and modulus.
             /// Returns `(quotient, remainder)`.
+            ///
+            /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`].
             #[inline]
+            #[deny(clippy::integer_division_remainder_used)]
             pub fn div_rem(numerator: $primitive_type, denom: Self) -> ($primitive_type, $primitive_type) {
-                if denom.multiplier == 0 {
-                    (numerator >> denom.divisor.trailing_zeros(), numerator & (denom.divisor - 1))
+                if cfg!(feature = "constant-time") {
+                    let quotient = numerator / denom;
+                    (quotient, numerator - quotient * denom.divisor.get())
+                } else if denom.multiplier == 0 {
+                    (numerator >> denom.shift, numerator & (denom.divisor.get() - 1))
                 }
                 else {
-                    let numerator128 = numerator as u128;
-                    let multiplied_hi = numerator128 * (denom.multiplier >> 64);
-                    let multiplied_lo = numerator128 * (denom.multiplier as u64 as u128) >> 64;
-
-                    let quotient = ((multiplied_hi + multiplied_lo) >> 64) as $primitive_type;
-                    let remainder = numerator - quotient * denom.divisor;
+                    // Rem can derive the remainder from the fraction bits instead, but that needs a second wide multiply, and benchmarks show that multiply-subtracting the quotient is faster
+                    let quotient = numerator / denom;
+                    let remainder = numerator - quotient * denom.divisor.get();
                     (quotient, remainder)
                 }
             }
 
+            /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+            #[inline]
+            pub fn divide(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator / *self
+            }
+
+            /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+            #[inline]
+            pub fn remainder(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator % *self
+            }
+
             /// Retrieve the value used to create this struct
             #[inline]
             pub fn get(&self) -> $primitive_type {
+                self.divisor.get()
+            }
+
+            /// Retrieve the value used to create this struct, as a nonzero integer
+            #[inline]
+            pub fn get_nonzero(&self) -> $nonzero_type {
                 self.divisor
             }
+
+            /// Returns the multiplier that this divisor uses in place of division, or 0 if the divisor is a power of two.
+            ///
+            #[cfg_attr(not(strength_reduce_no_u128), doc = "Unless `is_shift_only()` is true, the multiplier is `floor(2^128 / divisor) + 1`, and the quotient of `n` is `(n * multiplier) >> shift()`, computed in 256 bits.")]
+            #[cfg_attr(not(strength_reduce_no_u128), doc = "Together with `shift()` and `is_shift_only()`, this keeps the same meaning in every 0.3.x release, so code generators can rely on it.")]
+            #[cfg_attr(strength_reduce_no_u128, doc = "Unless `is_shift_only()` is true, this is Granlund and Montgomery's 65-bit \"round up\" multiplier without its implicit top bit: With `l = ceil(log2(divisor))`, it's `floor(2^(64 + l) / divisor) + 1 - 2^64`.")]
+            #[cfg_attr(strength_reduce_no_u128, doc = "The quotient of `n` is `(hi + ((n - hi) >> 1)) >> shift()`, where `hi` is the upper 64 bits of `n * multiplier`.")]
+            #[cfg_attr(strength_reduce_no_u128, doc = "")]
+            #[cfg_attr(strength_reduce_no_u128, doc = "Both the type and the meaning differ from the 128-bit multiplier of a build without `strength_reduce_no_u128`, so code generators have to be built with the same cfg as the code that creates the divisor.")]
+            #[inline]
+            pub const fn multiplier(&self) -> MultiplierU64 {
+                self.multiplier
+            }
+
+            /// Returns the right shift that finishes a division by this divisor.
+            ///
+            #[cfg_attr(not(strength_reduce_no_u128), doc = "For a power of two, this is its trailing zeros, and the quotient of `n` is `n >> shift()`. Otherwise, it's always 128, the number of fraction bits in the multiplier.")]
+            #[cfg_attr(strength_reduce_no_u128, doc = "This is `floor(log2(divisor))`. For a power of two, that's its trailing zeros, and the quotient of `n` is `n >> shift()`.")]
+            #[inline]
+            pub const fn shift(&self) -> u32 {
+                #[cfg(not(strength_reduce_no_u128))]
+                let fraction_bits = 128;
+                #[cfg(strength_reduce_no_u128)]
+                let fraction_bits = $primitive_type::BITS - 1 - self.divisor.leading_zeros();
+                if self.multiplier == 0 { self.shift as u32 } else { fraction_bits }
+            }
+
+            /// Returns true if the divisor is a power of two, so that dividing by it is just a right shift by `shift()`.
+            #[inline]
+            pub const fn is_shift_only(&self) -> bool {
+                self.multiplier == 0
+            }
+
+            /// Returns the precomputed constants of this divisor as `(multiplier, divisor, shift)`.
+            ///
+            /// Passing them to `from_raw_parts` rebuilds the divisor without recomputing anything, so JIT compilers and FFI layers can move divisors across a boundary as plain integers.
+            #[inline]
+            pub const fn into_raw_parts(self) -> (MultiplierU64, $primitive_type, u8) {
+                (self.multiplier, self.divisor.get(), self.shift)
+            }
+
+            /// Rebuilds a divisor from the output of `into_raw_parts`, without recomputing the multiplier.
+            ///
+            /// # Safety
+            ///
+            /// The parts must have come from `into_raw_parts` on this type, in a build of the same version of this crate with the same features. A `divisor` of 0 is undefined behavior, like in `new_unchecked`,
+            /// and any other mismatch makes every division return the wrong result. In debug builds, the divisor is checked with an assertion.
+            #[inline]
+            pub unsafe fn from_raw_parts(multiplier: MultiplierU64, divisor: $primitive_type, shift: u8) -> Self {
+                debug_assert!(divisor != 0);
+                Self::from_parts(multiplier, $nonzero_type::new_unchecked(divisor), shift)
+            }
+        }
+
+        impl Default for $struct_name {
+            /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+            #[inline]
+            fn default() -> Self {
+                Self::new(1)
+            }
+        }
+
+        impl From<$nonzero_type> for $struct_name {
+            /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+            #[inline]
+            fn from(divisor: $nonzero_type) -> Self {
+                Self::new_impl(divisor)
+            }
         }
 
+        #[deny(clippy::integer_division_remainder_used)]
         impl Div<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn div(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self >> rhs.divisor.trailing_zeros()
+                if cfg!(feature = "constant-time") {
+                    divide_u64_constant_time(self as u64, rhs.multiplier, rhs.divisor.get() as u64) as $primitive_type
+                } else if rhs.multiplier == 0 {
+                    self >> rhs.shift
+                } else if rhs.large {
+                    // the quotient is either 0 or 1, so a comparison is enough
+                    (self >= rhs.divisor.get()) as $primitive_type
                 } else {
-                    let numerator = self as u128;
-                    let multiplied_hi = numerator * (rhs.multiplier >> 64);
-                    let multiplied_lo = numerator * (rhs.multiplier as u64 as u128) >> 64;
-
-                    ((multiplied_hi + multiplied_lo) >> 64) as $primitive_type
+                    divide_u64(self as u64, rhs.multiplier, rhs.divisor.get() as u64) as $primitive_type
                 }
             }
         }
 
+        #[deny(clippy::integer_division_remainder_used)]
         impl Rem<$struct_name> for $primitive_type {
             type Output = $primitive_type;
 
             #[inline]
             fn rem(self, rhs: $struct_name) -> Self::Output {
-                if rhs.multiplier == 0 {
-                    self & (rhs.divisor - 1)
+                if cfg!(feature = "constant-time") {
+                    let quotient = self / rhs;
+                    self - quotient * rhs.divisor.get()
+                } else if rhs.multiplier == 0 {
+                    self & (rhs.divisor.get() - 1)
+                } else if rhs.large {
+                    if self >= rhs.divisor.get() { self - rhs.divisor.get() } else { self }
                 } else {
                     let quotient = self / rhs;
-                    self - quotient * rhs.divisor
+                    self - quotient * rhs.divisor.get()
                 }
             }
         }
+
+        state_bytes_impl!($struct_name, $primitive_type, [multiplier: MultiplierU64, shift: u8, large: bool]);
     )
 }
 
+#[cfg(not(strength_reduce_no_u128))]
 /// Implements unsigned division and modulo via mutiplication and shifts.
 ///
 /// Creating a an instance of this struct is more expensive than a single division, but if the division is repeated,
 /// this version will be several times faster than naive division.
 #[derive(Clone, Copy, Debug)]
 pub struct StrengthReducedU128 {
-    multiplier_hi: u128,
-    multiplier_lo: u128,
-    divisor: u128,
+    multiplier: u128,
+    divisor: NonZeroU128,
 }
+#[cfg(not(strength_reduce_no_u128))]
 impl StrengthReducedU128 {
     /// Creates a new divisor instance.
     ///
@@ -407,73 +1250,235 @@ impl StrengthReducedU128 {
     /// Panics if `divisor` is 0
     #[inline]
     pub fn new(divisor: u128) -> Self {
-        assert!(divisor > 0);
+        match NonZeroU128::new(divisor) {
+            Some(divisor) => Self::new_impl(divisor),
+            None => panic!("attempt to create a divisor of zero"),
+        }
+    }
 
+    #[inline]
+    fn new_impl(nonzero_divisor: NonZeroU128) -> Self {
+        let divisor = nonzero_divisor.get();
         if divisor.is_power_of_two() { 
-            Self{ multiplier_hi: 0, multiplier_lo: 0, divisor }
+            Self{ multiplier: 0, divisor: nonzero_divisor }
         } else {
-            let (quotient_hi, quotient_lo) = long_division::divide_256_max_by_128(divisor);
-            let multiplier_lo = quotient_lo.wrapping_add(1);
-            let multiplier_hi = if multiplier_lo == 0 { quotient_hi + 1 } else { quotient_hi };
-            Self{ multiplier_hi, multiplier_lo, divisor }
+            // We can't fit a 256-bit multiplier in this struct, so we use Granlund and Montgomery's 129-bit "round up" multiplier instead, and drop its implicit top bit.
+            // With l = ceil(log2(divisor)), the full multiplier is floor(2^(128 + l) / divisor) + 1. Shifting the divisor up to use all 128 bits turns that into 2^256 / shifted_divisor, which is the shifted divisor's reciprocal.
+            let shifted_divisor = divisor << divisor.leading_zeros();
+            let reciprocal = long_division::reciprocal_256_by_128(shifted_divisor);
+            Self{ multiplier: reciprocal + 1, divisor: nonzero_divisor }
         }
     }
 
+    /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+    ///
+    /// Use this instead of `new()` when the divisor comes from untrusted input.
+    #[inline]
+    pub fn try_new(divisor: u128) -> Option<Self> {
+        NonZeroU128::new(divisor).map(Self::new_impl)
+    }
+
+    /// Creates a new divisor instance without checking that `divisor` is nonzero.
+    ///
+    /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+    ///
+    /// # Safety
+    ///
+    /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+    #[inline]
+    pub unsafe fn new_unchecked(divisor: u128) -> Self {
+        debug_assert!(divisor != 0);
+        Self::new_impl(NonZeroU128::new_unchecked(divisor))
+    }
+
     /// Simultaneous truncated integer division and modulus.
     /// Returns `(quotient, remainder)`.
+    ///
+    /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`].
     #[inline]
+    #[deny(clippy::integer_division_remainder_used)]
     pub fn div_rem(numerator: u128, denom: Self) -> (u128, u128) {
         let quotient = numerator / denom;
-        let remainder = numerator - quotient * denom.divisor;
+        let remainder = if cfg!(feature = "constant-time") {
+            numerator - quotient * denom.divisor.get()
+        } else if numerator >> 64 == 0 || denom.divisor.get() >> 64 == 0 {
+            // same as Rem: the remainder fits in 64 bits, so the multiply-subtract can too
+            (numerator as u64).wrapping_sub((quotient as u64).wrapping_mul(denom.divisor.get() as u64)) as u128
+        } else {
+            numerator - quotient * denom.divisor.get()
+        };
         (quotient, remainder)
     }
 
+    /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+    #[inline]
+    pub fn divide(&self, numerator: u128) -> u128 {
+        numerator / *self
+    }
+
+    /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+    #[inline]
+    pub fn remainder(&self, numerator: u128) -> u128 {
+        numerator % *self
+    }
+
     /// Retrieve the value used to create this struct
     #[inline]
     pub fn get(&self) -> u128 {
+        self.divisor.get()
+    }
+
+    /// Retrieve the value used to create this struct, as a nonzero integer
+    #[inline]
+    pub fn get_nonzero(&self) -> NonZeroU128 {
         self.divisor
     }
+
+    /// Returns the multiplier that this divisor uses in place of division, or 0 if the divisor is a power of two.
+    ///
+    /// Unless `is_shift_only()` is true, this is Granlund and Montgomery's 129-bit "round up" multiplier without its implicit top bit: With `l = ceil(log2(divisor))`, it's `floor(2^(128 + l) / divisor) + 1 - 2^128`.
+    /// The quotient of `n` is `(hi + ((n - hi) >> 1)) >> shift()`, where `hi` is the upper 128 bits of `n * multiplier`.
+    /// Together with `shift()` and `is_shift_only()`, this keeps the same meaning in every 0.3.x release, so code generators can rely on it.
+    #[inline]
+    pub const fn multiplier(&self) -> u128 {
+        self.multiplier
+    }
+
+    /// Returns the right shift that finishes a division by this divisor, which is `floor(log2(divisor))`.
+    ///
+    /// For a power of two, that's its trailing zeros, and the quotient of `n` is `n >> shift()`.
+    #[inline]
+    pub const fn shift(&self) -> u32 {
+        127 - self.divisor.get().leading_zeros()
+    }
+
+    /// Returns true if the divisor is a power of two, so that dividing by it is just a right shift by `shift()`.
+    #[inline]
+    pub const fn is_shift_only(&self) -> bool {
+        self.multiplier == 0
+    }
+
+    /// Returns the precomputed constants of this divisor as `(multiplier, divisor)`.
+    ///
+    /// Passing them to `from_raw_parts` rebuilds the divisor without recomputing anything, so JIT compilers and FFI layers can move divisors across a boundary as plain integers.
+    #[inline]
+    pub const fn into_raw_parts(self) -> (u128, u128) {
+        (self.multiplier, self.divisor.get())
+    }
+
+    /// Rebuilds a divisor from the output of `into_raw_parts`, without recomputing the multiplier.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have come from `into_raw_parts` on this type, in a build of the same version of this crate with the same features. A `divisor` of 0 is undefined behavior, like in `new_unchecked`,
+    /// and any other mismatch makes every division return the wrong result. In debug builds, the divisor is checked with an assertion.
+    #[inline]
+    pub unsafe fn from_raw_parts(multiplier: u128, divisor: u128) -> Self {
+        debug_assert!(divisor != 0);
+        Self{ multiplier, divisor: NonZeroU128::new_unchecked(divisor) }
+    }
+}
+
+#[cfg(not(strength_reduce_no_u128))]
+state_bytes_impl!(StrengthReducedU128, u128, [multiplier: u128]);
+
+#[cfg(not(strength_reduce_no_u128))]
+impl Default for StrengthReducedU128 {
+    /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+    #[inline]
+    fn default() -> Self {
+        Self::new(1)
+    }
 }
 
+#[cfg(not(strength_reduce_no_u128))]
+impl From<NonZeroU128> for StrengthReducedU128 {
+    /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+    #[inline]
+    fn from(divisor: NonZeroU128) -> Self {
+        Self::new_impl(divisor)
+    }
+}
+
+#[cfg(not(strength_reduce_no_u128))]
+#[deny(clippy::integer_division_remainder_used)]
 impl Div<StrengthReducedU128> for u128 {
     type Output = u128;
 
     #[inline]
     fn div(self, rhs: StrengthReducedU128) -> Self::Output {
-        if rhs.multiplier_hi == 0 {
-            self >> rhs.divisor.trailing_zeros()
+        // for powers of two, this is the same as trailing_zeros
+        let shift = 127 - rhs.divisor.get().leading_zeros();
+        if cfg!(feature = "constant-time") {
+            // always take the full 128-bit multiply, and compute the power of two quotient alongside it, so that neither the numerator nor the divisor picks a branch
+            let upper = long_multiplication::multiply_128_by_128_upperbits(rhs.multiplier, self);
+            u128::select(rhs.multiplier == 0, self >> shift, (upper + ((self - upper) >> 1)) >> shift)
+        } else if rhs.multiplier == 0 {
+            self >> shift
+        } else if self >> 64 == 0 {
+            // most numerators fit in 64 bits, and then we only need two of the four partial products
+            let numerator = self as u64;
+            let upper = long_multiplication::multiply_128_by_64_upperbits(rhs.multiplier, numerator);
+            (upper + ((self - upper) >> 1)) >> shift
         } else {
-            long_multiplication::multiply_256_by_128_upperbits(rhs.multiplier_hi, rhs.multiplier_lo, self)
+            // the multiplier has an implicit 129th bit, so the full product is upper + self. adding them directly could overflow, so average them instead, and shift one bit less
+            let upper = long_multiplication::multiply_128_by_128_upperbits(rhs.multiplier, self);
+            (upper + ((self - upper) >> 1)) >> shift
         }
     }
 }
 
+#[cfg(not(strength_reduce_no_u128))]
+#[deny(clippy::integer_division_remainder_used)]
 impl Rem<StrengthReducedU128> for u128 {
     type Output = u128;
 
     #[inline]
     fn rem(self, rhs: StrengthReducedU128) -> Self::Output {
-        if rhs.multiplier_hi == 0 {
-            self & (rhs.divisor - 1)
+        if cfg!(feature = "constant-time") {
+            let quotient = self / rhs;
+            self - quotient * rhs.divisor.get()
+        } else if rhs.multiplier == 0 {
+            self & (rhs.divisor.get() - 1)
         } else {
-             let quotient = long_multiplication::multiply_256_by_128_upperbits(rhs.multiplier_hi, rhs.multiplier_lo, self);
-             self - quotient * rhs.divisor
+            let quotient = self / rhs;
+            if self >> 64 == 0 || rhs.divisor.get() >> 64 == 0 {
+                // the remainder is smaller than both the numerator and the divisor, so if either of them fits in 64 bits, we can multiply-subtract with 64-bit wrapping arithmetic
+                (self as u64).wrapping_sub((quotient as u64).wrapping_mul(rhs.divisor.get() as u64)) as u128
+            } else {
+                self - quotient * rhs.divisor.get()
+            }
         }
     }
 }
 
 // We just hardcoded u8 and u128 since they will never be a usize. for the rest, we have macros, so we can reuse the same code for usize
-strength_reduced_u16!(StrengthReducedU16, u16);
-strength_reduced_u32!(StrengthReducedU32, u32);
-strength_reduced_u64!(StrengthReducedU64, u64);
+strength_reduced_u16!(StrengthReducedU16, u16, NonZeroU16);
+strength_reduced_u32!(StrengthReducedU32, u32, NonZeroU32);
+strength_reduced_u64!(StrengthReducedU64, u64, NonZeroU64);
+
+#[cfg(any(feature = "portable_simd", all(feature = "avx512", any(target_arch = "x86", target_arch = "x86_64"))))]
+impl StrengthReducedU64 {
+    // A 128-bit multiplier would need even more limbs, so the SIMD kernels use Granlund and Montgomery's 65-bit "round up" multiplier, with its implicit top bit dropped.
+    // This derives it and its shift from the stored multiplier, like the scalar odd path. The result is garbage for powers of two, but the shift is still in range
+    #[inline]
+    pub(crate) fn round_up_parts(&self) -> (u64, u32) {
+        let bits = 64 - self.divisor.leading_zeros();
+        #[cfg(not(strength_reduce_no_u128))]
+        let multiplier = ((self.multiplier.wrapping_sub(1) >> (64 - bits)) as u64).wrapping_add(1);
+        #[cfg(strength_reduce_no_u128)]
+        let multiplier = self.multiplier;
+        (multiplier, bits - 1)
+    }
+}
 
 // Our definition for usize will depend on how big usize is
 #[cfg(target_pointer_width = "16")]
-strength_reduced_u16!(StrengthReducedUsize, usize);
+strength_reduced_u16!(StrengthReducedUsize, usize, NonZeroUsize);
 #[cfg(target_pointer_width = "32")]
-strength_reduced_u32!(StrengthReducedUsize, usize);
+strength_reduced_u32!(StrengthReducedUsize, usize, NonZeroUsize);
 #[cfg(target_pointer_width = "64")]
-strength_reduced_u64!(StrengthReducedUsize, usize);
+strength_reduced_u64!(StrengthReducedUsize, usize, NonZeroUsize);
 
 #[cfg(test)]
 mod unit_tests {
@@ -483,7 +1488,7 @@ mod unit_tests {
         ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
             #[test]
             fn $test_name() {
-                let max = core::$primitive_type::MAX;
+                let max = $primitive_type::MAX;
                 let divisors = [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,max-1,max];
                 let numerators = [0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20];
 
@@ -510,10 +1515,297 @@ mod unit_tests {
         )
     }
 
+    static REDUCED_1000: StrengthReducedU32 = StrengthReducedU32::new(1000);
+    const REDUCED_TABLE: [StrengthReducedU64; 4] = [StrengthReducedU64::new(3), StrengthReducedU64::new(1 << 20), StrengthReducedU64::new(u64::MAX - 1), StrengthReducedU64::new(u64::MAX)];
+    const REDUCED_U8: Option<StrengthReducedU8> = StrengthReducedU8::try_new(7);
+    const REDUCED_U16_ZERO: Option<StrengthReducedU16> = StrengthReducedU16::try_new(0);
+
+    #[cfg(not(strength_reduce_no_u128))]
+    #[test]
+    fn test_struct_sizes() {
+        // the cached shift should fit in the padding after the divisor
+        assert_eq!(core::mem::size_of::<StrengthReducedU8>(), 4);
+        assert_eq!(core::mem::size_of::<StrengthReducedU16>(), 8);
+        assert_eq!(core::mem::size_of::<StrengthReducedU32>(), 16);
+        assert_eq!(core::mem::size_of::<StrengthReducedU64>(), 2 * core::mem::size_of::<u128>());
+        assert_eq!(core::mem::size_of::<StrengthReducedU128>(), 2 * core::mem::size_of::<u128>());
+
+        // the divisor is stored as a NonZero, so Option shouldn't need any extra space
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedU8>>(), core::mem::size_of::<StrengthReducedU8>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedU16>>(), core::mem::size_of::<StrengthReducedU16>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedU32>>(), core::mem::size_of::<StrengthReducedU32>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedU64>>(), core::mem::size_of::<StrengthReducedU64>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedUsize>>(), core::mem::size_of::<StrengthReducedUsize>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedU128>>(), core::mem::size_of::<StrengthReducedU128>());
+        assert_eq!(core::mem::size_of::<Option<StrengthReducedI64>>(), core::mem::size_of::<StrengthReducedI64>());
+    }
+
+    #[cfg(not(strength_reduce_no_u128))]
+    #[test]
+    fn test_strength_reduced_u128_edge_cases() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let max = u128::MAX;
+        let mut rng = StdRng::seed_from_u64(128);
+        let edge_divisors = [3, 5, 7, 10, 641, u64::MAX as u128, u64::MAX as u128 + 2, (1 << 127) - 1, (1 << 127) + 1, max / 3, max - 1, max];
+        let random_divisors: [u128; 200] = core::array::from_fn(|_| (rng.gen::<u128>() >> rng.gen_range(0..127)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU128::new(divisor);
+            let edge_numerators = [0, 1, divisor - 1, divisor, u64::MAX as u128 - 1, u64::MAX as u128, u64::MAX as u128 + 1, max - max % divisor, (max - max % divisor).wrapping_sub(1), max - 1, max];
+            let random_numerators: [u128; 50] = core::array::from_fn(|_| rng.gen());
+            // numerators that fit in 64 bits take a separate path
+            let small_numerators: [u128; 50] = core::array::from_fn(|_| rng.gen::<u64>() as u128);
+            for &numerator in edge_numerators.iter().chain(&random_numerators).chain(&small_numerators) {
+                assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+
+    macro_rules! even_divisor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let odd_parts = [3, 5, 7, 641, max / 3, max >> 1];
+
+                for &odd_part in &odd_parts {
+                    // every power of two that keeps the divisor in range, so the pre-shifted numerator covers every width
+                    for shift in 1..=odd_part.leading_zeros() {
+                        let divisor = odd_part << shift;
+                        let reduced_divisor = $struct_name::new(divisor);
+                        for &numerator in &[0, 1, divisor - 1, divisor, divisor + 1, max - max % divisor, max - max % divisor - 1, max - 1, max] {
+                            assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                            assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                            assert_eq!((numerator / divisor, numerator % divisor), $struct_name::div_rem(numerator, reduced_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                        }
+                    }
+                }
+            }
+        )
+    }
+    even_divisor_test!(test_even_divisors_u32, StrengthReducedU32, u32);
+    even_divisor_test!(test_even_divisors_u64, StrengthReducedU64, u64);
+    even_divisor_test!(test_even_divisors_usize, StrengthReducedUsize, usize);
+
+    macro_rules! large_divisor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let half = max / 2;
+                for &divisor in &[half - 1, half, half + 1, half + 2, half + 3, max / 3 * 2, max - 2, max - 1, max] {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    assert_eq!(divisor > half, reduced_divisor.large);
+                    for &numerator in &[0, 1, half, half + 1, divisor - 1, divisor, divisor + (max - divisor) / 2, max - 1, max] {
+                        assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!((numerator / divisor, numerator % divisor), $struct_name::div_rem(numerator, reduced_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+    large_divisor_test!(test_large_divisors_u16, StrengthReducedU16, u16);
+    large_divisor_test!(test_large_divisors_u32, StrengthReducedU32, u32);
+    #[cfg(not(strength_reduce_no_u128))]
+    large_divisor_test!(test_large_divisors_u64, StrengthReducedU64, u64);
+    large_divisor_test!(test_large_divisors_usize, StrengthReducedUsize, usize);
+
+    macro_rules! small_divisor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let small_max = u32::MAX as $primitive_type;
+                for &divisor in &[3, 6, 7, 10, 641, 12345, small_max / 3, small_max - 2, small_max - 1, small_max, small_max + 1, small_max + 2] {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in &[0, 1, divisor - 1, divisor, divisor + 1, small_max - 1, small_max, small_max + 1, $primitive_type::MAX - 1, $primitive_type::MAX] {
+                        assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!((numerator / divisor, numerator % divisor), $struct_name::div_rem(numerator, reduced_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+    small_divisor_test!(test_small_divisors_u64, StrengthReducedU64, u64);
+    #[cfg(target_pointer_width = "64")]
+    small_divisor_test!(test_small_divisors_usize, StrengthReducedUsize, usize);
+
+    #[test]
+    fn test_const_constructors() {
+        assert_eq!(123456 / 1000, 123456 / REDUCED_1000);
+        assert_eq!(123456 % 1000, 123456 % REDUCED_1000);
+        for reduced_divisor in &REDUCED_TABLE {
+            let runtime_divisor = StrengthReducedU64::new(reduced_divisor.get());
+            for &numerator in &[0, 1, 12345678, u64::MAX - 1, u64::MAX] {
+                assert_eq!(numerator / runtime_divisor, numerator / *reduced_divisor);
+                assert_eq!(numerator % runtime_divisor, numerator % *reduced_divisor);
+            }
+        }
+        assert_eq!(100 / 7, 100 / REDUCED_U8.unwrap());
+        assert!(REDUCED_U16_ZERO.is_none());
+    }
+
+    macro_rules! default_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let reduced_divisor = $struct_name::default();
+                assert_eq!(1, reduced_divisor.get());
+                for &numerator in &[0, 1, 2, 3, 100, $primitive_type::MAX - 1, $primitive_type::MAX] {
+                    assert_eq!(numerator, numerator / reduced_divisor);
+                    assert_eq!(0, numerator % reduced_divisor);
+                }
+            }
+        )
+    }
+
+    default_test!(test_default_u8, StrengthReducedU8, u8);
+    default_test!(test_default_u16, StrengthReducedU16, u16);
+    default_test!(test_default_u32, StrengthReducedU32, u32);
+    default_test!(test_default_u64, StrengthReducedU64, u64);
+    default_test!(test_default_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    default_test!(test_default_u128, StrengthReducedU128, u128);
+
     reduction_test!(test_strength_reduced_u8, StrengthReducedU8, u8);
     reduction_test!(test_strength_reduced_u16, StrengthReducedU16, u16);
     reduction_test!(test_strength_reduced_u32, StrengthReducedU32, u32);
     reduction_test!(test_strength_reduced_u64, StrengthReducedU64, u64);
     reduction_test!(test_strength_reduced_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
     reduction_test!(test_strength_reduced_u128, StrengthReducedU128, u128);
+
+    macro_rules! constructor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                assert!($struct_name::try_new(0).is_none());
+
+                for &divisor in &test_divisors!($primitive_type) {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    assert_eq!(Some(divisor), $struct_name::try_new(divisor).map(|d| d.get()));
+                    assert_eq!(divisor, unsafe { $struct_name::new_unchecked(divisor) }.get());
+                    assert_eq!(divisor, reduced_divisor.get_nonzero().get());
+
+                    let nonzero_divisor = $struct_name::from(reduced_divisor.get_nonzero());
+                    for &numerator in &[0, 1, 2, divisor - 1, divisor, max / 2, max - 1, max] {
+                        assert_eq!(numerator / divisor, numerator / nonzero_divisor, "Divide failed with numerator: {}, nonzero divisor: {}", numerator, divisor);
+                        assert_eq!(numerator % divisor, numerator % nonzero_divisor, "Modulo failed with numerator: {}, nonzero divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    constructor_test!(test_constructors_u8, StrengthReducedU8, u8);
+    constructor_test!(test_constructors_u16, StrengthReducedU16, u16);
+    constructor_test!(test_constructors_u32, StrengthReducedU32, u32);
+    constructor_test!(test_constructors_u64, StrengthReducedU64, u64);
+    constructor_test!(test_constructors_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    constructor_test!(test_constructors_u128, StrengthReducedU128, u128);
+
+    macro_rules! method_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                for &divisor in &test_divisors!($primitive_type) {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in &[0, 1, 2, divisor - 1, divisor, max / 2, max - 1, max] {
+                        let expected_div = numerator / divisor;
+                        let expected_rem = numerator % divisor;
+                        assert_eq!(expected_div, reduced_divisor.divide(numerator), "divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_rem, reduced_divisor.remainder(numerator), "remainder failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!((expected_div, expected_rem), reduced_divisor.div_rem(numerator), "div_rem method failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    method_test!(test_methods_u8, StrengthReducedU8, u8);
+    method_test!(test_methods_u16, StrengthReducedU16, u16);
+    method_test!(test_methods_u32, StrengthReducedU32, u32);
+    method_test!(test_methods_u64, StrengthReducedU64, u64);
+    method_test!(test_methods_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    method_test!(test_methods_u128, StrengthReducedU128, u128);
+
+    macro_rules! raw_parts_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident, $parts:tt) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                for &divisor in &[1, 2, 3, 7, 10, 64, max / 2, max / 2 + 1, max - 1, max] {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    let $parts = reduced_divisor.into_raw_parts();
+                    let rebuilt = unsafe { $struct_name::from_raw_parts $parts };
+                    assert_eq!(reduced_divisor.to_le_bytes(), rebuilt.to_le_bytes(), "Raw parts round trip failed with divisor: {}", divisor);
+                    assert_eq!(max / divisor, max / rebuilt);
+                }
+            }
+        )
+    }
+
+    raw_parts_test!(test_raw_parts_u8, StrengthReducedU8, u8, (multiplier, divisor, shift));
+    raw_parts_test!(test_raw_parts_u16, StrengthReducedU16, u16, (multiplier, divisor, shift));
+    raw_parts_test!(test_raw_parts_u32, StrengthReducedU32, u32, (multiplier, divisor, shift));
+    raw_parts_test!(test_raw_parts_u64, StrengthReducedU64, u64, (multiplier, divisor, shift));
+    raw_parts_test!(test_raw_parts_usize, StrengthReducedUsize, usize, (multiplier, divisor, shift));
+    #[cfg(not(strength_reduce_no_u128))]
+    raw_parts_test!(test_raw_parts_u128, StrengthReducedU128, u128, (multiplier, divisor));
+
+    macro_rules! magic_constants_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident, |$numerator:ident, $multiplier:ident, $shift:ident| $quotient:expr) => (
+            #[test]
+            fn $test_name() {
+                use num_bigint::BigUint;
+
+                let max = $primitive_type::MAX;
+                for &divisor in &[1, 2, 3, 7, 10, 64, 100, max / 3, max / 2, max / 2 + 1, max - 1, max] {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    assert_eq!(divisor.is_power_of_two(), reduced_divisor.is_shift_only());
+                    for &numerator in &[0, 1, 9, 10, 11, max / 3, max / 2, max - 1, max] {
+                        let actual: BigUint = if reduced_divisor.is_shift_only() {
+                            BigUint::from(numerator >> reduced_divisor.shift())
+                        } else {
+                            let $numerator = BigUint::from(numerator);
+                            let $multiplier = BigUint::from(reduced_divisor.multiplier());
+                            let $shift = reduced_divisor.shift() as usize;
+                            $quotient
+                        };
+                        assert_eq!(BigUint::from(numerator / divisor), actual, "Magic constants failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    magic_constants_test!(test_magic_constants_u8, StrengthReducedU8, u8, |numerator, multiplier, shift| (numerator * multiplier) >> shift);
+    magic_constants_test!(test_magic_constants_u16, StrengthReducedU16, u16, |numerator, multiplier, shift| (numerator * multiplier) >> shift);
+    magic_constants_test!(test_magic_constants_u32, StrengthReducedU32, u32, |numerator, multiplier, shift| (numerator * multiplier) >> shift);
+    #[cfg(not(strength_reduce_no_u128))]
+    magic_constants_test!(test_magic_constants_u64, StrengthReducedU64, u64, |numerator, multiplier, shift| (numerator * multiplier) >> shift);
+    #[cfg(strength_reduce_no_u128)]
+    magic_constants_test!(test_magic_constants_u64, StrengthReducedU64, u64, |numerator, multiplier, shift| {
+        let hi = (&numerator * multiplier) >> 64;
+        (&hi + ((numerator - &hi) >> 1)) >> shift
+    });
+    #[cfg(not(strength_reduce_no_u128))]
+    magic_constants_test!(test_magic_constants_u128, StrengthReducedU128, u128, |numerator, multiplier, shift| {
+        let hi = (&numerator * multiplier) >> 128;
+        (&hi + ((numerator - &hi) >> 1)) >> shift
+    });
+
+    #[test]
+    fn test_signed_abs_divisor() {
+        let reduced_divisor = StrengthReducedI32::new(-10);
+        assert_eq!(10, reduced_divisor.abs_divisor().get());
+        assert_eq!(StrengthReducedU32::new(10).multiplier(), reduced_divisor.abs_divisor().multiplier());
+    }
 }
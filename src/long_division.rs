@@ -1,15 +1,15 @@
 extern crate core;
 
-const U32_MAX: u64 = core::u32::MAX as u64;
-const U64_MAX: u128 = core::u64::MAX as u128;
+const U32_MAX: u64 = u32::MAX as u64;
 
-use ::StrengthReducedU64;
 use ::long_multiplication;
+use ::tables;
 
 // divides a 128-bit number by a 64-bit divisor, returning the quotient as a 64-bit number
 // assumes that the divisor and numerator have both already been bit-shifted so that divisor.leading_zeros() == 0
+// this is a const fn so that the 64-bit strength-reduced constructors can be const, so it can't use core::cmp::min
 #[inline]
-fn divide_128_by_64_preshifted(numerator_hi: u64, numerator_lo: u64, divisor: u64) -> u64 {
+const fn divide_128_by_64_preshifted(numerator_hi: u64, numerator_lo: u64, divisor: u64) -> u64 {
     let numerator_mid = (numerator_lo >> 32) as u128;
     let numerator_lo = numerator_lo as u32 as u128;
     let divisor_full_128 = divisor as u128;
@@ -19,504 +19,238 @@ fn divide_128_by_64_preshifted(numerator_hi: u64, numerator_lo: u64, divisor: u6
     // but the problem is, full_upper_numerator is a 96-bit number, meaning we would need to use u128 to do the division all at once, and the whole point of this is that we don't want to do 128 bit divison because it's slow
 	// so instead, we'll shift both the numerator and divisor right by 32, giving us a 64 bit / 32 bit division. This won't give us the exact quotient -- but it will be close.
     let full_upper_numerator = ((numerator_hi as u128) << 32) | numerator_mid;
-    let mut quotient_hi = core::cmp::min(numerator_hi / divisor_hi, U32_MAX);
-    let mut product_hi = quotient_hi as u128 * divisor_full_128;
+    let mut quotient_hi = numerator_hi / divisor_hi;
+    if quotient_hi > U32_MAX {
+        quotient_hi = U32_MAX;
+    }
+    let product_hi = quotient_hi as u128 * divisor_full_128;
 
     // quotient_hi contains our guess at what the quotient is! the problem is that we got this by ignoring the lower 32 bits of the divisor. when we account for that, the quotient might be slightly lower
-    // we will know our quotient is too high if quotient * divisor > numerator. if it is, decrement until it's in range
-    while product_hi > full_upper_numerator {
-        quotient_hi -= 1;
-        product_hi -= divisor_full_128;
-    }
+    // we will know our quotient is too high if quotient * divisor > numerator. since the divisor is normalized, Knuth's theorem B says the guess is at most 2 too high,
+    // so instead of looping until it's in range, we apply two branchless corrections
+    let (quotient_hi, product_hi) = correct_quotient_estimate(quotient_hi, product_hi, full_upper_numerator, divisor_full_128);
+    let (quotient_hi, product_hi) = correct_quotient_estimate(quotient_hi, product_hi, full_upper_numerator, divisor_full_128);
     let remainder_hi = full_upper_numerator - product_hi;
 
 
     // repeat the process using the lower half of the numerator
     let full_lower_numerator = (remainder_hi << 32) | numerator_lo;
-    let mut quotient_lo = core::cmp::min((remainder_hi as u64) / divisor_hi, U32_MAX);
-    let mut product_lo = quotient_lo as u128 * divisor_full_128;
-
-    // again, quotient_lo is just a guess at this point, it might be slightly too large
-    while product_lo > full_lower_numerator {
-        quotient_lo -= 1;
-        product_lo -= divisor_full_128;
-    }
-
-    // We now have our separate quotients, now we just have to add them together
-    (quotient_hi << 32) | quotient_lo
-}
-
-// divides a 128-bit number by a 64-bit divisor, returning the quotient as a 64-bit number
-// assumes that the divisor and numerator have both already been bit-shifted to maximize the number of bits in divisor_hi
-// divisor_hi should be the upper 32 bits, and divisor_lo should be the lower 32 bits
-#[inline]
-fn divide_128_by_64_preshifted_reduced(numerator_hi: u64, numerator_lo: u64, divisor_hi: StrengthReducedU64, divisor_full: u64) -> u64 {
-    let numerator_mid = (numerator_lo >> 32) as u128;
-    let numerator_lo = numerator_lo as u32 as u128;
-    let divisor_full_128 = divisor_full as u128;
-
-    // To get the upper 32 bits of the quotient, we want to divide 'full_upper_numerator' by 'divisor'
-    // but the problem is, full_upper_numerator is a 96-bit number, meaning we would need to use u128 to do the division all at once, and the whole point of this is that we don't want to do 128 bit divison because it's slow
-	// so instead, we'll shift both the numerator and divisor right by 32, giving us a 64 bit / 32 bit division. This won't give us the exact quotient -- but it will be close.
-    let full_upper_numerator = ((numerator_hi as u128) << 32) | numerator_mid;
-    let mut quotient_hi = core::cmp::min(numerator_hi / divisor_hi, U32_MAX);
-    let mut product_hi = quotient_hi as u128 * divisor_full_128;
-
-    // quotient_hi contains our guess at what the quotient is! the problem is that we got this by ignoring the lower 32 bits of the divisor. when we account for that, the quotient might be slightly lower
-    // we will know our quotient is too high if quotient * divisor > numerator. if it is, decrement until it's in range
-    while product_hi > full_upper_numerator {
-        quotient_hi -= 1;
-        product_hi -= divisor_full_128;
+    let mut quotient_lo = (remainder_hi as u64) / divisor_hi;
+    if quotient_lo > U32_MAX {
+        quotient_lo = U32_MAX;
     }
-    let full_upper_remainder = full_upper_numerator - product_hi;
-
+    let product_lo = quotient_lo as u128 * divisor_full_128;
 
-    // repeat the process using the lower half of the numerator
-    let full_lower_numerator = (full_upper_remainder << 32) | numerator_lo;
-    let mut quotient_lo = core::cmp::min((full_upper_remainder as u64) / divisor_hi, U32_MAX);
-    let mut product_lo = quotient_lo as u128 * divisor_full_128;
-
-    // again, quotient_lo is just a guess at this point, it might be slightly too large
-    while product_lo > full_lower_numerator {
-        quotient_lo -= 1;
-        product_lo -= divisor_full_128;
-    }
+    // again, quotient_lo is just a guess at this point, it might be up to 2 too large
+    let (quotient_lo, product_lo) = correct_quotient_estimate(quotient_lo, product_lo, full_lower_numerator, divisor_full_128);
+    let (quotient_lo, _) = correct_quotient_estimate(quotient_lo, product_lo, full_lower_numerator, divisor_full_128);
 
     // We now have our separate quotients, now we just have to add them together
     (quotient_hi << 32) | quotient_lo
 }
 
-// divides a 128-bit number by a 128-bit divisor
-pub fn divide_128(numerator: u128, divisor: u128) -> u128 {
-	if divisor <= U64_MAX {
-		let divisor64 = divisor as u64;
-		let upper_numerator = (numerator >> 64) as u64;
-		if divisor64 > upper_numerator {
-			divide_128_by_64_helper(numerator, divisor64) as u128
-		}
-		else {
-			let upper_quotient = upper_numerator / divisor64;
-			let upper_remainder = upper_numerator - upper_quotient * divisor64;
-
-			let intermediate_numerator = ((upper_remainder as u128) << 64) | (numerator as u64 as u128);
-			let lower_quotient = divide_128_by_64_helper(intermediate_numerator, divisor64);
-
-			((upper_quotient as u128) << 64) | (lower_quotient as u128)
-		}
-	}
-	else {
-		let shift_size = divisor.leading_zeros();
-		let shifted_divisor = divisor << shift_size;
-
-		let shifted_numerator = numerator >> 1;
-
-		let upper_quotient = divide_128_by_64_helper(shifted_numerator, (shifted_divisor >> 64) as u64);
-		let mut quotient = upper_quotient >> (63 - shift_size);
-		if quotient > 0 {
-			quotient -= 1;
-		}
-
-		let remainder = numerator - quotient as u128 * divisor;
-		if remainder >= divisor {
-			quotient += 1;
-		}
-		quotient as u128
-	}
-}
-
-// divides a 128-bit number by a 64-bit divisor, returning the quotient as a 64-bit number. Panics if the quotient doesn't fit in a 64-bit number
-fn divide_128_by_64_helper(numerator: u128, divisor: u64) -> u64 {
-	// Assert that the upper half of the numerator is less than the denominator. This will guarantee that the quotient fits inside the numerator.
-	// Sadly this will give us some false negatives! TODO: Find a quick test we can do that doesn't have false negatives
-	// false negative example: numerator = u64::MAX * u64::MAX / u64::MAX
-	assert!(divisor > (numerator >> 64) as u64, "The numerator is too large for the denominator; the quotient might not fit inside a u64.");
-
-	if divisor <= U32_MAX {
-		return divide_128_by_32_helper(numerator, divisor as u32);
-	}
-
-    let shift_size = divisor.leading_zeros();
-	let shifted_divisor = divisor << shift_size;
-	let shifted_numerator = numerator << shift_size;
-	let divisor_hi = shifted_divisor >> 32;
-    let divisor_lo = shifted_divisor as u32 as u64;
-
-    // split the numerator into 3 chunks: the top 64-bits, the next 32-bits, and the lowest 32-bits
-    let numerator_hi : u64 = (shifted_numerator >> 64) as u64;
-    let numerator_mid : u64 = (shifted_numerator >> 32) as u32 as u64;
-    let numerator_lo : u64 = shifted_numerator as u32 as u64;
-
-    // we're essentially going to do a long division algorithm with 2 divisions, one on numerator_hi << 32 | numerator_mid, and the second on the remainder of the first | numerator_lo
-    // but numerator_hi << 32 | numerator_mid is a 96-bit number, and we only have 64 bits to work with. so instead we split the divisor into 2 chunks, and divde by the upper chunk, and then check against the lower chunk in a while loop
-
-    // step 1: divide the top chunk of the numerator by the divisor
-    // IDEALLY, we would divide (numerator_hi << 32) | numerator_mid by shifted_divisor, but that would require a 128-bit numerator, which is the whole thing we're trying to avoid
-    // so instead we're going to split the second division into two sub-phases. in 1a, we divide numerator_hi by divisor_hi, and then in 1b we decrement the quotient to account for the fact that it'll be smaller when you take divisor_lo into account
-
-    // keep in mind that for all of step 2, the full numerator we're using will be
-    // complete_first_numerator  = (numerator_midbits << 32) | numerator_mid
-
-    // step 1a: divide the upper part of the middle numerator by the upper part of the divisor
-    let mut quotient_hi = core::cmp::min(numerator_hi / divisor_hi, U32_MAX);
-    let mut partial_remainder_hi = numerator_hi - quotient_hi * divisor_hi;
-
-    // step 1b: we know sort of what the quotient is, but it's slightly too large because it doesn't account for divisor_lo, nor numerator_mid, so decrement the quotient until it fits
-    // note that if we do some algebra on the condition in this while loop,
-    // ie "quotient_hi * divisor_lo > (partial_remainder_hi << 32) | numerator_mid"
-    // we end up getting "quotient_hi * shifted_divisor < (numerator_midbits << 32) | numerator_mid". remember that the right side of the inequality sign is complete_first_numerator from above.
-    // which deminstrates that we're decrementing the quotient until the quotient multipled by the complete divisor is less than the complete numerator
-    while partial_remainder_hi <= U32_MAX && quotient_hi * divisor_lo > (partial_remainder_hi << 32) | numerator_mid {
-        quotient_hi -= 1;
-        partial_remainder_hi += divisor_hi;
-    }
-
-    // step 2: Divide the bottom part of the numerator. We're going to have the same problem as step 1, where we want the numerator to be a 96-bit number, so again we're going to split it into 2 substeps
-	// the full numeratoe for step 3 will be:
-	// complete_second_numerator = (first_division_remainder << 32) | numerator_lo
-
-    // step 2a: divide the upper part of the lower numerator by the upper part of the divisor
-    // To get the numerator, complate the calculation of the full remainder by subtracing the quotient times the lower bits of the divisor
-    // TODO: a warpping subtract is necessary here. why does this work, and why is it necessary?
-    let full_remainder_hi = ((partial_remainder_hi << 32) | numerator_mid).wrapping_sub(quotient_hi * divisor_lo);
-
-    let mut quotient_lo = core::cmp::min(full_remainder_hi / divisor_hi, U32_MAX);
-    let mut partial_remainder_lo = full_remainder_hi - quotient_lo * divisor_hi;
-
-    // step 2b: just like step 1b, decrement the final quotient until it's correctr when accounting for the full divisor
-    while partial_remainder_lo <= U32_MAX && quotient_lo * divisor_lo > (partial_remainder_lo << 32) | numerator_lo {
-        quotient_lo -= 1;
-        partial_remainder_lo += divisor_hi;
-    }
-
-    // We now have our separate quotients, now we just have to add them together
-    (quotient_hi << 32) | quotient_lo
+// if the product of the quotient estimate and the divisor is bigger than the numerator, the estimate is too high, so subtract one from it and one divisor from the product
+// the comparison becomes an all-ones or all-zeros mask instead of a branch, so there is nothing to mispredict when the estimate is off
+#[inline(always)]
+const fn correct_quotient_estimate(quotient: u64, product: u128, numerator: u128, divisor: u128) -> (u64, u128) {
+    let too_high = (product > numerator) as u64;
+    let mask = 0u128.wrapping_sub(too_high as u128);
+    (quotient - too_high, product - (divisor & mask))
 }
 
+// computes floor((2^128 - 1) / divisor) - 2^64, ie the reciprocal of a 64-bit divisor with its implicit top bit dropped
+// assumes that the divisor has already been bit-shifted so that divisor.leading_zeros() == 0
+// This is the RECIPROCAL_WORD algorithm from Möller and Granlund's "Improved division by invariant integers": an 11-bit table lookup, followed by Newton iterations that only need multiplies
+#[inline]
+pub(crate) fn reciprocal_128_by_64(divisor: u64) -> u64 {
+	let divisor_40 = (divisor >> 24) + 1;
+	let divisor_63 = (divisor >> 1) + (divisor & 1);
 
-// Same as divide_128_by_64_into_64, but optimized for scenarios where the divisor fits in a u32. Still panics if the quotient doesn't fit in a u64
-fn divide_128_by_32_helper(numerator: u128, divisor: u32) -> u64 {
-	// Assert that the upper half of the numerator is less than the denominator. This will guarantee that the quotient fits inside the numerator.
-	// Sadly this will give us some false negatives! TODO: Find a quick test we can do that doesn't have false negatives
-	// false negative example: numerator = u64::MAX * u64::MAX / u64::MAX
-	assert!(divisor as u64 > (numerator >> 64) as u64, "The numerator is too large for the denominator; the quotient might not fit inside a u64.");
-
-    let shift_size = divisor.leading_zeros();
-	let shifted_divisor = (divisor << shift_size) as u64;
-	let shifted_numerator = numerator << (shift_size + 32);
-
-    // split the numerator into 3 chunks: the top 64-bits, the next 32-bits, and the lowest 32-bits
-    let numerator_hi : u64 = (shifted_numerator >> 64) as u64;
-    let numerator_mid : u64 = (shifted_numerator >> 32) as u32 as u64;
-
-    // we're essentially going to do a long division algorithm with 2 divisions, one on numerator_hi << 32 | numerator_mid, and the second on the remainder of the first | numerator_lo
-    // but numerator_hi << 32 | numerator_mid is a 96-bit number, and we only have 64 bits to work with. so instead we split the divisor into 2 chunks, and divde by the upper chunk, and then check against the lower chunk in a while loop
-
-    // step 1: divide the top chunk of the numerator by the divisor
-    // IDEALLY, we would divide (numerator_hi << 32) | numerator_mid by shifted_divisor, but that would require a 128-bit numerator, which is the whole thing we're trying to avoid
-    // so instead we're going to split the second division into two sub-phases. in 1a, we divide numerator_hi by divisor_hi, and then in 1b we decrement the quotient to account for the fact that it'll be smaller when you take divisor_lo into account
-
-    // keep in mind that for all of step 1, the full numerator we're using will be
-    // complete_first_numerator  = (numerator_hi << 32) | numerator_mid
-
-    // step 1a: divide the upper part of the middle numerator by the upper part of the divisor
-    let quotient_hi = numerator_hi / shifted_divisor;
-    let remainder_hi = numerator_hi - quotient_hi * shifted_divisor;
-
-    // step 2: Divide the bottom part of the numerator. We're going to have the same problem as step 1, where we want the numerator to be a 96-bit number, so again we're going to split it into 2 substeps
-	// the full numeratoe for step 3 will be:
-	// complete_second_numerator = (first_division_remainder << 32) | numerator_lo
-
-    // step 2a: divide the upper part of the lower numerator by the upper part of the divisor
-    // To get the numerator, complate the calculation of the full remainder by subtracing the quotient times the lower bits of the divisor
-    // TODO: a warpping subtract is necessary here. why does this work, and why is it necessary?
-    let final_numerator = (remainder_hi) << 32 | numerator_mid;
-    let quotient_lo = final_numerator / shifted_divisor;
-
-    // We now have our separate quotients, now we just have to add them together
-    (quotient_hi << 32) | quotient_lo
-}
+	let reciprocal_11 = tables::RECIPROCAL_SEEDS[(divisor >> 55) as usize - 256] as u64;
+	let reciprocal_21 = (reciprocal_11 << 11) - ((reciprocal_11 * reciprocal_11 * divisor_40) >> 40) - 1;
+	let reciprocal_34 = (reciprocal_21 << 13) + (((reciprocal_21 as u128 * ((1u64 << 60) - reciprocal_21 * divisor_40) as u128) >> 47) as u64);
 
-#[inline(never)]
-fn long_division(numerator_slice: &[u64], reduced_divisor: &StrengthReducedU64, quotient: &mut [u64]) {
-	let mut remainder = 0;
-	for (numerator_element, quotient_element) in numerator_slice.iter().zip(quotient.iter_mut()).rev() {
-		if remainder > 0 {
-			// Do one division that includes the running remainder and the upper half of this numerator element, 
-			// then a second division for the first division's remainder combinedwith the lower half
-			let upper_numerator = (remainder << 32) | (*numerator_element >> 32);
-			let (upper_quotient, upper_remainder) = StrengthReducedU64::div_rem(upper_numerator, *reduced_divisor);
-
-			let lower_numerator = (upper_remainder << 32) | (*numerator_element as u32 as u64);
-			let (lower_quotient, lower_remainder) = StrengthReducedU64::div_rem(lower_numerator, *reduced_divisor);
-
-			*quotient_element = (upper_quotient << 32) | lower_quotient;
-			remainder = lower_remainder;
-		} else {
-			// The remainder is zero, which means we can take a shortcut and only do a single division!
-			let (digit_quotient, digit_remainder) = StrengthReducedU64::div_rem(*numerator_element, *reduced_divisor);
-
-			*quotient_element = digit_quotient;
-			remainder = digit_remainder;
-		}
-	}
-}
+	// the last Newton iteration uses the full divisor, with its lowest bit folded into divisor_63
+	let error = ((reciprocal_34 >> 1) & 0u64.wrapping_sub(divisor & 1)).wrapping_sub(reciprocal_34.wrapping_mul(divisor_63));
+	let reciprocal_65 = (reciprocal_34 << 31).wrapping_add(((reciprocal_34 as u128 * error as u128) >> 65) as u64);
 
-#[inline]
-fn normalize_slice(input: &mut [u64]) -> &mut [u64] {
-	let input_len = input.len();
-	let trailing_zero_chunks = input.iter().rev().take_while(|e| **e == 0).count();
-	&mut input[..input_len - trailing_zero_chunks]
+	// the reciprocal is now either exact or one too small, so adjust it based on the sign of the remainder
+	let product = (reciprocal_65 as u128 + 1) * divisor as u128;
+	reciprocal_65.wrapping_sub(((product >> 64) as u64).wrapping_add(divisor))
 }
 
+// divides numerator_hi:numerator_lo by the divisor, returning (quotient, remainder). The divisor's reciprocal must come from reciprocal_128_by_64
+// assumes that the divisor has already been bit-shifted so that divisor.leading_zeros() == 0, and that numerator_hi < divisor, so the quotient fits in 64 bits
+// This is the DIV_2BY1 algorithm from the same paper: one multiply by the reciprocal gives a quotient estimate that's off by at most one in either direction
 #[inline]
-fn is_slice_greater(a: &[u64], b: &[u64]) -> bool {
-    if a.len() > b.len() {
-        return true;
-    }
-    if b.len() > a.len() {
-    	return false;
-    }
-
-    for (&ai, &bi) in a.iter().zip(b.iter()).rev() {
-        if ai < bi {
-            return false;
-        }
-        if ai > bi {
-            return true;
-        }
-    }
-   	false
-}
-// subtract b from a, and store the result in a
-#[inline]
-fn sub_assign(a: &mut [u64], b: &[u64]) {
-	let mut borrow: i128 = 0;
-
-	// subtract b from a, keeping track of borrows as we go
-	let (a_lo, a_hi) = a.split_at_mut(b.len());
-
-	for (a, b) in a_lo.iter_mut().zip(b) {
-		borrow += *a as i128;
-		borrow -= *b as i128;
-		*a = borrow as u64;
-		borrow >>= 64;
+pub(crate) fn divide_128_by_64_preinv(numerator_hi: u64, numerator_lo: u64, divisor: u64, reciprocal: u64) -> (u64, u64) {
+	let numerator = ((numerator_hi as u128) << 64) | numerator_lo as u128;
+	let product = (reciprocal as u128 * numerator_hi as u128).wrapping_add(numerator);
+
+	let mut quotient = ((product >> 64) as u64).wrapping_add(1);
+	let mut remainder = numerator_lo.wrapping_sub(quotient.wrapping_mul(divisor));
+	// the remainder's wrapping tells us which way the estimate is off
+	if remainder > product as u64 {
+		quotient = quotient.wrapping_sub(1);
+		remainder = remainder.wrapping_add(divisor);
 	}
+	if remainder >= divisor {
+		quotient += 1;
+		remainder -= divisor;
+	}
+	(quotient, remainder)
+}
 
-	// We're done subtracting, we just need to finish carrying
-	let mut a_element = a_hi.iter_mut();
-	while borrow != 0 {
-		let a_element = a_element.next().expect("borrow underflow during sub_assign");
-		borrow += *a_element as i128;
-
-		*a_element = borrow as u64;
-		borrow >>= 64;
+// divides a little-endian multi-limb numerator by a single limb, writing the quotient's limbs to `quotient` and returning the remainder
+// the divisor must already be shifted left by `shift` so that its top bit is set, and its reciprocal must come from reciprocal_128_by_64,
+// so callers that divide by the same limb over and over only compute them once
+#[cfg(feature = "num-bigint")]
+#[inline(never)]
+pub(crate) fn long_division_preinv(numerator_slice: &[u64], shifted_divisor: u64, reciprocal: u64, shift: u32, quotient: &mut [u64]) -> u64 {
+	// We shift the numerator up to match the divisor as we go. the bits shifted out of the top element become our starting remainder, and they're always smaller than the shifted divisor
+	let mut remainder = match numerator_slice.last() {
+		Some(&top) if shift > 0 => top >> (64 - shift),
+		_ => 0,
+	};
+	for (index, quotient_element) in quotient[..numerator_slice.len()].iter_mut().enumerate().rev() {
+		let lower_bits = if shift > 0 && index > 0 { numerator_slice[index - 1] >> (64 - shift) } else { 0 };
+		let shifted_element = (numerator_slice[index] << shift) | lower_bits;
+
+		// since the remainder is smaller than the divisor, each quotient digit fits in a u64, so we only need one 2/1 division per digit
+		let (digit_quotient, digit_remainder) = divide_128_by_64_preinv(remainder, shifted_element, shifted_divisor, reciprocal);
+		*quotient_element = digit_quotient;
+		remainder = digit_remainder;
 	}
+	remainder >> shift
 }
 
-pub(crate) fn divide_128_max_by_64(divisor: u64) -> u128 {
-	let quotient_hi = core::u64::MAX / divisor;
-	let remainder_hi = core::u64::MAX - quotient_hi * divisor;
+pub(crate) const fn divide_128_max_by_64(divisor: u64) -> u128 {
+	let quotient_hi = u64::MAX / divisor;
+	let remainder_hi = u64::MAX - quotient_hi * divisor;
 
 	let leading_zeros = divisor.leading_zeros();
 	let quotient_lo = if leading_zeros >= 32 {
-		let numerator_mid = (remainder_hi << 32) | core::u32::MAX as u64;
+		let numerator_mid = (remainder_hi << 32) | u32::MAX as u64;
 		let quotient_mid = numerator_mid / divisor;
 		let remainder_mid = numerator_mid - quotient_mid * divisor;
 
-		let numerator_lo = (remainder_mid << 32) | core::u32::MAX as u64;
+		let numerator_lo = (remainder_mid << 32) | u32::MAX as u64;
 		let quotient_lo = numerator_lo / divisor;
 
 		(quotient_mid << 32) | quotient_lo
 	}
 	else {
-		let numerator_hi = if leading_zeros > 0 { (remainder_hi << leading_zeros) | (core::u64::MAX >> (64 - leading_zeros)) } else { remainder_hi };
-		let numerator_lo = core::u64::MAX << leading_zeros;
+		// We tried replacing this with Möller and Granlund's multiply-only reciprocal, but on x86_64 hardware with fast 64-bit division, its chain of dependent multiplies took almost twice as long as these three divisions
+		let numerator_hi = if leading_zeros > 0 { (remainder_hi << leading_zeros) | (u64::MAX >> (64 - leading_zeros)) } else { remainder_hi };
+		let numerator_lo = u64::MAX << leading_zeros;
 
 		divide_128_by_64_preshifted(numerator_hi, numerator_lo, divisor << leading_zeros)
 	};
 	((quotient_hi as u128) << 64) | (quotient_lo as u128)
 }
 
-fn divide_256_max_by_32(divisor: u32) -> (u128, u128) {
-	let reduced_divisor = StrengthReducedU64::new(divisor as u64);
-	let mut numerator_chunks = [core::u64::MAX; 4];
-	let mut quotient_chunks = [0; 4];
-	long_division(&mut numerator_chunks, &reduced_divisor, &mut quotient_chunks);
-
-	// quotient_chunks now contains the quotient! all we have to do is recombine it into u128s
-	let quotient_lo = (quotient_chunks[0] as u128) | ((quotient_chunks[1] as u128) << 64);
-	let quotient_hi = (quotient_chunks[2] as u128) | ((quotient_chunks[3] as u128) << 64);
-
-	(quotient_hi, quotient_lo)
-}
-
-pub(crate) fn divide_256_max_by_128(divisor: u128) -> (u128, u128) {
-	let leading_zeros = divisor.leading_zeros();
-
-	// if the divisor fits inside a u32, we can use a much faster algorithm
-	if leading_zeros >= 96 {
-		return divide_256_max_by_32(divisor as u32);
+// computes floor((2^256 - 1) / divisor) - 2^128, ie the reciprocal of a 128-bit divisor with its implicit top bit dropped
+// assumes that the divisor has already been bit-shifted so that divisor.leading_zeros() == 0, and that it isn't a power of two
+// Instead of long division, we start from a 64-bit reciprocal of the divisor's upper half, and refine it with two Newton-Raphson steps, which square the error each time.
+//
+// Write R = (2^256 - 1) / divisor for the exact reciprocal, X = 2^128 + reciprocal for our estimate, and g = R - X for its error. Every estimate is at most R, so g >= 0, and floor(R) - X <= g.
+// - The seed is floor((2^128 - 1) / divisor_hi) * 2^64. Ignoring the lower half of the divisor makes it between 2^66 too large and 2^64 + 2 too small, so we subtract 2^67 to make it an underestimate, with g < 2^68.
+// - Each Newton step adds X * (2^256 - 1 - X * divisor) / 2^256, which can't pass R. It leaves an error of at most g^2 / 2^128 + 2^-188, and rounding the step down adds less than 1.
+// - So after the first step g < 2^8 + 2, and after the second g < 1 + 2^-111, which means the reciprocal is either exact or one too small, and a single correction finishes it.
+pub(crate) fn reciprocal_256_by_128(divisor: u128) -> u128 {
+	// the divisor is normalized, so its upper half is too, and its 2/1 reciprocal is exactly floor((2^128 - 1) / divisor_hi) - 2^64, without any hardware division
+	let seed = reciprocal_128_by_64((divisor >> 64) as u64);
+	let mut reciprocal = (seed.saturating_sub(8) as u128) << 64;
+
+	for _ in 0..2 {
+		let (remainder_hi, remainder_lo) = reciprocal_remainder(reciprocal, divisor);
+
+		// the step is (2^128 + reciprocal) * (remainder_hi * 2^128 + remainder_lo) / 2^256. We take the whole parts of the products exactly,
+		// and add up the fractional parts that carry into the whole part, so all we lose is the fraction that's left over, which is less than 1
+		let step_lo = reciprocal.wrapping_mul(remainder_hi);
+		let (fraction, carry_a) = step_lo.overflowing_add(remainder_lo);
+		let (_, carry_b) = fraction.overflowing_add(long_multiplication::multiply_128_by_128_upperbits(reciprocal, remainder_lo));
+		let step = remainder_hi + long_multiplication::multiply_128_by_128_upperbits(reciprocal, remainder_hi) + carry_a as u128 + carry_b as u128;
+		reciprocal += step;
 	}
 
-	let empty_divisor_chunks = (leading_zeros / 64) as usize;
-	let shift_amount = leading_zeros % 64;
-
-	// Shift the divisor and chunk it up into U32s
-	let divisor_shifted = divisor << shift_amount;
-	let divisor_chunks = [
-		divisor_shifted as u64,
-		(divisor_shifted >> 64) as u64,
-	];
-	let divisor_slice = &divisor_chunks[..(divisor_chunks.len() - empty_divisor_chunks)];
-
-	// We're gonna be doing a ton of u64/u64 divisions, so we're gonna eat our own dog food and set up a strength-reduced division instance
-	// the only actual **divisions* we'll be doing will be with the largest 32 bits of the full divisor, not the full divisor
-	let reduced_divisor_hi = StrengthReducedU64::new(*divisor_slice.last().unwrap() >> 32);
-	let divisor_hi = *divisor_slice.last().unwrap();
-
-	// Build our numerator, represented by u32 chunks. at first it will be full of u32::MAX, but we will iteratively take chunks out of it as we divide
-	let mut numerator_chunks = [core::u64::MAX; 5];
-	let mut numerator_max_idx = if shift_amount > 0 {
-		numerator_chunks[4] >>= 64 - shift_amount;
-		numerator_chunks[0] <<= shift_amount;
-		5
-	}
-	else {
-		4
-	};
-
-	// allocate the biggest-possible quotient, even if it might be smaller -- we just won't fill out the biggest parts
-	let num_quotient_chunks = 3 + empty_divisor_chunks;
-	let mut quotient_chunks = [0; 4];
-	for quotient_idx in (0..num_quotient_chunks).rev() {
-		/*
-         * When calculating our next guess q0, we don't need to consider the digits below j
-         * + b.data.len() - 1: we're guessing digit j of the quotient (i.e. q0 << j) from
-         * digit bn of the divisor (i.e. bn << (b.data.len() - 1) - so the product of those
-         * two numbers will be zero in all digits up to (j + b.data.len() - 1).
-         */
-
-        let numerator_slice = &mut numerator_chunks[..numerator_max_idx];
-     	let numerator_start_idx = quotient_idx + divisor_slice.len() - 1;
-     	if numerator_start_idx >= numerator_slice.len() {
-            continue;
-		}
-		
-
-		// scope for borrow checker
-		{
-			// divide the uppermost bits of the remaining numerator to get "sub_quotient" which will be our guess for this quotient element
-			let numerator_hi = if numerator_slice.len() - numerator_start_idx > 1 { numerator_slice[numerator_start_idx + 1] } else { 0 };
-			let numerator_lo = numerator_slice[numerator_start_idx];
-			let mut sub_quotient = divide_128_by_64_preshifted_reduced(numerator_hi, numerator_lo, reduced_divisor_hi, divisor_hi);
-
-			let mut tmp_product = [0; 3];
-			long_multiplication::long_multiply(&divisor_slice, sub_quotient, &mut tmp_product);
-			let sub_product = normalize_slice(&mut tmp_product);
-
-			// our sub_quotient is just a guess at the quotient -- it only accounts for the topmost bits of the divisor. when we take the bottom bits of the divisor into account, the actual quotient will be smaller
-			// we will know if our guess is too large if (quotient_guess * full_divisor) (aka sub_product) is greater than this iteration's numerator slice. ifthat's the case, decrement it until it's less than or equal.
-			while is_slice_greater(sub_product, &numerator_slice[quotient_idx..]) {
-				sub_assign(sub_product, &divisor_slice);
-				sub_quotient -= 1;
-			}
-
-			// sub_quotient is now the correct sub-quotient for this iteration. add it to the full quotient, and subtract the product from the full numerator, so that what remains in the numerator is the remainder of this division
-			quotient_chunks[quotient_idx] = sub_quotient;
-			sub_assign(&mut numerator_slice[quotient_idx..], sub_product);
-		}
-
-
-		// slice off any zeroes at the end of the numerator. we're not calling normalize_slice here because of borrow checker obnoxiousness
-		numerator_max_idx -= numerator_slice.iter().rev().take_while(|e| **e == 0).count();
+	// the reciprocal is now exact, or one too small, in which case the remainder is at least one divisor
+	let (remainder_hi, remainder_lo) = reciprocal_remainder(reciprocal, divisor);
+	if remainder_hi != 0 || remainder_lo >= divisor {
+		let (corrected_lo, borrow) = remainder_lo.overflowing_sub(divisor);
+		debug_assert!(remainder_hi == borrow as u128 && corrected_lo < divisor, "the reciprocal of {} was more than one too small after the Newton steps", divisor);
+		reciprocal + 1
+	} else {
+		reciprocal
 	}
-
-	
-	// quotient_chunks now contains the quotient! all we have to do is recombine it into u128s
-	let quotient_lo = (quotient_chunks[0] as u128)
-		| ((quotient_chunks[1] as u128) << 64);
-	let quotient_hi = (quotient_chunks[2] as u128)
-		| ((quotient_chunks[3] as u128) << 64);
-
-	(quotient_hi, quotient_lo)
 }
 
-
+// computes the remainder (2^256 - 1) - (2^128 + reciprocal) * divisor, as (upper half, lower half). The reciprocal must not be too large, so that the remainder isn't negative
+// subtracting from 2^256 - 1 is the same as a bitwise not, so that's what we do for both halves of the product
+#[inline]
+fn reciprocal_remainder(reciprocal: u128, divisor: u128) -> (u128, u128) {
+	let product_lo = reciprocal.wrapping_mul(divisor);
+	let product_hi = divisor + long_multiplication::multiply_128_by_128_upperbits(reciprocal, divisor);
+	(!product_hi, !product_lo)
+}
 
 #[cfg(test)]
 mod unit_tests {
 	use num_bigint::BigUint;
 
-	#[test]
-	fn test_divide_128_by_64() {
-		for divisor in core::u64::MAX..=core::u64::MAX {
-			let divisor_128 = core::u64::MAX as u128;
-
-			let numerator = divisor_128 * divisor_128 + (divisor_128 - 1);
-			//for numerator in core::u128::MAX - 10..core::u128::MAX {
-		        let expected_quotient = numerator / divisor as u128;
-		        assert!(expected_quotient == core::u64::MAX as u128);
-
-		        let actual_quotient = super::divide_128_by_64_helper(numerator as u128, divisor);
-
-		        
-
-		        let expected_upper = (expected_quotient >> 32) as u64;
-		        let expected_lower = expected_quotient as u32 as u64;
-		        let actual_upper = (actual_quotient >> 32) as u64;
-		        let actual_lower = actual_quotient as u32 as u64;
+	#[allow(unused_imports)]
+	use rand::{rngs::StdRng, Rng, SeedableRng, distributions::Distribution, distributions::Uniform};
 
-		        assert_eq!(expected_upper, actual_upper, "wrong quotient for {}/{}", numerator, divisor);
-		        assert_eq!(expected_lower, actual_lower, "wrong quotient for {}/{}", numerator, divisor);
-		    //}
-	    }
+	#[test]
+	fn test_divide_128_by_64_preshifted() {
+		let mut gen = StdRng::seed_from_u64(5673573);
+		let dist = Uniform::new_inclusive(1u64 << 63, u64::MAX);
+		let edge_divisors = [1 << 63, (1 << 63) + 1, (1 << 63) | u32::MAX as u64, u64::MAX - (1 << 32), u64::MAX - 1, u64::MAX];
+		let random_divisors: [u64; 200] = core::array::from_fn(|_| dist.sample(&mut gen));
+		for &divisor in edge_divisors.iter().chain(&random_divisors) {
+			let reciprocal = super::reciprocal_128_by_64(divisor);
+			assert_eq!(u128::MAX / divisor as u128 - (1 << 64), reciprocal as u128, "wrong reciprocal for {}", divisor);
+
+			let numerator_his = [0, 1, divisor >> 1, divisor - 1, Uniform::new(0, divisor).sample(&mut gen)];
+			for &numerator_hi in &numerator_his {
+				for &numerator_lo in &[0, 1, u32::MAX as u64, u64::MAX - 1, u64::MAX, gen.gen()] {
+					let numerator = ((numerator_hi as u128) << 64) | numerator_lo as u128;
+					let expected = (numerator / divisor as u128) as u64;
+					assert_eq!(expected, super::divide_128_by_64_preshifted(numerator_hi, numerator_lo, divisor), "wrong quotient for {}/{}", numerator, divisor);
+					let expected_remainder = (numerator % divisor as u128) as u64;
+					assert_eq!((expected, expected_remainder), super::divide_128_by_64_preinv(numerator_hi, numerator_lo, divisor, reciprocal), "wrong 2/1 division for {}/{}", numerator, divisor);
+				}
+			}
+		}
 	}
 
-	fn test_divisor_128(divisor: u128) {
-		let big_numerator = BigUint::from_slice(&[core::u32::MAX; 8]);
-		let big_quotient = big_numerator / divisor;
-
-		//let (actual_hi, actual_lo) = super::divide_256_max_by_128_direct(divisor);
-		let (actual64_hi, actual64_lo) = super::divide_256_max_by_128(divisor);
-
-		//let actual_big = (BigUint::from(actual_hi) << 128) | BigUint::from(actual_lo);
-		let actual64_big = (BigUint::from(actual64_hi) << 128) | BigUint::from(actual64_lo);
-
-		//assert_eq!(big_quotient, actual_big, "Actual quotient didn't match expected quotient for max/{}", divisor);
-		assert_eq!(big_quotient, actual64_big, "Actual64 quotient didn't match expected quotient for max/{}", divisor);
+	fn test_reciprocal_128(divisor: u128) {
+		let shifted_divisor = divisor << divisor.leading_zeros();
+		let big_numerator = BigUint::from_slice(&[u32::MAX; 8]);
+		let expected = big_numerator / shifted_divisor - (BigUint::from(1u32) << 128);
+		assert_eq!(expected, BigUint::from(super::reciprocal_256_by_128(shifted_divisor)), "Wrong reciprocal for {}", shifted_divisor);
 	}
 
-	#[allow(unused_imports)]
-	use rand::{rngs::StdRng, SeedableRng, distributions::Distribution, distributions::Uniform};
-
 	#[test]
-	fn test_max_256() {
-		let log2_tests_per_bit = 6;
-
-		for divisor in 1..(1 << log2_tests_per_bit) {
-			test_divisor_128(divisor);
-		}
-
+	fn test_reciprocal_256_by_128() {
 		let mut gen = StdRng::seed_from_u64(5673573);
-		for bits in log2_tests_per_bit..128 {
-			let lower_start = 1 << bits;
-			let lower_stop = lower_start + (1 << (log2_tests_per_bit - 3));
-			let upper_stop = 1u128.checked_shl(bits + 1).map_or(core::u128::MAX, |v| v - 1);
-			let upper_start = upper_stop - (1 << (log2_tests_per_bit - 3)) + 1;
-
-			for divisor in lower_start..lower_stop {
-				test_divisor_128(divisor);
+		for bits in 4..128 {
+			let lower_start = (1u128 << bits) + 1;
+			let upper_stop = 1u128.checked_shl(bits + 1).map_or(u128::MAX, |v| v - 1);
+			for divisor in lower_start..lower_start + 8 {
+				test_reciprocal_128(divisor);
 			}
-			for divisor in upper_start..=upper_stop {
-				test_divisor_128(divisor);
+			for divisor in upper_stop - 7..=upper_stop {
+				test_reciprocal_128(divisor);
 			}
 
-			let random_count = 1 << log2_tests_per_bit;
-			let dist = Uniform::new(lower_stop + 1, upper_start);
-			for _ in 0..random_count {
-				let divisor = dist.sample(&mut gen);
-				test_divisor_128(divisor);
+			let dist = Uniform::new(lower_start, upper_stop);
+			for _ in 0..64 {
+				test_reciprocal_128(dist.sample(&mut gen));
 			}
 		}
+		for &divisor in &[3, 5, 7, (1u128 << 127) + 1, (1u128 << 127) | (1 << 64), (1u128 << 127) | u64::MAX as u128, u128::MAX - (1 << 64), u128::MAX - 1, u128::MAX] {
+			test_reciprocal_128(divisor);
+		}
 	}
 }
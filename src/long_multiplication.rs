@@ -1,87 +1,167 @@
 
-// multiply the 256-bit number 'a' by the 128-bit number 'b' and return the uppermost 128 bits of the product
-// ripped directly from num-biguint's long multiplication algorithm (mac3, mac_with_carry, adc), but with fixed-size arrays instead of slices
+// multiply the 128-bit number 'a' by the 128-bit number 'b' and return the upper 128 bits of the product
+#[cfg(all(not(all(target_arch = "x86_64", target_feature = "bmi2", target_feature = "adx")), not(strength_reduce_no_u128)))]
 #[inline]
-pub(crate) fn multiply_256_by_128_upperbits(a_hi: u128, a_lo: u128, b: u128) -> u128 {
-	// Break a and b into little-endian 64-bit chunks
-	let a_chunks = [
-		a_lo as u64,
-		(a_lo >> 64) as u64,
-		a_hi as u64,
-		(a_hi >> 64) as u64,
-	];
-	let b_chunks = [
-		b as u64,
-		(b >> 64) as u64,
-	];
-
-	// Multiply b by a, one chink of b at a time
-	let mut product = [0; 6];
-	for (b_index, &b_digit) in b_chunks.iter().enumerate() {
-		multiply_256_by_64_helper(&mut product[b_index..], &a_chunks, b_digit);
-	}
+pub(crate) fn multiply_128_by_128_upperbits(a: u128, b: u128) -> u128 {
+	let a_lo = a as u64;
+	let a_hi = (a >> 64) as u64;
+	let b_lo = b as u64;
+	let b_hi = (b >> 64) as u64;
+
+	// compute the four 64x64 partial products, and carry the middle ones into the upper half
+	let lo_lo = multiply_64_by_64(a_lo, b_lo);
+	let lo_hi = multiply_64_by_64(a_lo, b_hi);
+	let hi_lo = multiply_64_by_64(a_hi, b_lo);
+	let hi_hi = multiply_64_by_64(a_hi, b_hi);
 
-	// the last 2 elements of the array have the part of the productthat we care about
-	((product[5] as u128) << 64) | (product[4] as u128)
+	let middle = (lo_lo >> 64) + (lo_hi as u64 as u128) + (hi_lo as u64 as u128);
+	hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (middle >> 64)
 }
 
+// When the target has BMI2 and ADX (ie when compiling with `-C target-cpu=native` on Broadwell or newer), MULX leaves the flags alone, so the partial products can be summed with two interleaved ADCX/ADOX carry chains instead of going through 128-bit adds
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2", target_feature = "adx", not(strength_reduce_no_u128)))]
 #[inline]
-fn multiply_256_by_64_helper(product: &mut [u64], a: &[u64;4], b: u64) {
-	if b == 0 {
-		return;
-	}
+pub(crate) fn multiply_128_by_128_upperbits(a: u128, b: u128) -> u128 {
+	use core::arch::x86_64::{_addcarryx_u64, _mulx_u64};
 
-	let mut carry = 0;
-	let (product_lo, product_hi) = product.split_at_mut(a.len());
+	let a_lo = a as u64;
+	let a_hi = (a >> 64) as u64;
+	let b_lo = b as u64;
+	let b_hi = (b >> 64) as u64;
 
-	// Multiply each of the digits in a by b, adding them into the 'product' value.
-	// We don't zero out product, because we this will be called multiple times, so it probably contains a previous iteration's partial product, and we're adding + carrying on top of it
-	for (p, &a_digit) in product_lo.iter_mut().zip(a) {
-		carry += *p as u128;
-		carry += (a_digit as u128) * (b as u128);
+	// Safety: these intrinsics only touch registers and the locals we pass in, and the cfg above guarantees that the CPU supports them
+	unsafe {
+		// compute the four 64x64 partial products. we only need the upper half of the lowest one
+		let mut lo_lo_hi = 0;
+		_mulx_u64(a_lo, b_lo, &mut lo_lo_hi);
+		let mut lo_hi_hi = 0;
+		let lo_hi_lo = _mulx_u64(a_lo, b_hi, &mut lo_hi_hi);
+		let mut hi_lo_hi = 0;
+		let hi_lo_lo = _mulx_u64(a_hi, b_lo, &mut hi_lo_hi);
+		let mut hi_hi_hi = 0;
+		let hi_hi_lo = _mulx_u64(a_hi, b_hi, &mut hi_hi_hi);
 
-		*p = carry as u64;
-		carry >>= 64;
-	}
+		// the first carry chain adds the lo_hi product into the middle columns, and the second one adds the hi_lo product
+		let mut middle = 0;
+		let mut result_lo = 0;
+		let mut result_hi = 0;
+		let carry = _addcarryx_u64(0, lo_lo_hi, lo_hi_lo, &mut middle);
+		let carry = _addcarryx_u64(carry, hi_hi_lo, lo_hi_hi, &mut result_lo);
+		_addcarryx_u64(carry, hi_hi_hi, 0, &mut result_hi);
 
-	// We're done multiplying, we just need to finish carrying through the rest of the product.
-	let mut p = product_hi.iter_mut();
-	while carry != 0 {
-		let p = p.next().expect("carry overflow during multiplication!");
-		carry += *p as u128;
+		let mut unused = 0;
+		let carry = _addcarryx_u64(0, middle, hi_lo_lo, &mut unused);
+		let carry = _addcarryx_u64(carry, result_lo, hi_lo_hi, &mut result_lo);
+		_addcarryx_u64(carry, result_hi, 0, &mut result_hi);
 
-		*p = carry as u64;
-		carry >>= 64;
+		((result_hi as u128) << 64) | result_lo as u128
 	}
 }
 
-// compute product += a * b
+// multiply the 128-bit number 'a' by the 64-bit number 'b' and return the upper 128 bits of the product
+#[cfg(not(strength_reduce_no_u128))]
 #[inline]
-pub(crate) fn long_multiply(a: &[u64], b: u64, product: &mut [u64]) {
-	if b == 0 {
-		return;
-	}
+pub(crate) fn multiply_128_by_64_upperbits(a: u128, b: u64) -> u128 {
+	let a_lo = a as u64;
+	let a_hi = (a >> 64) as u64;
+
+	// with only two partial products, carrying the lower one into the upper one can't overflow
+	(multiply_64_by_64(a_hi, b) + (multiply_64_by_64(a_lo, b) >> 64)) >> 64
+}
 
-	let mut carry = 0;
-	let (product_lo, product_hi) = product.split_at_mut(a.len());
+// multiply the 64-bit number 'a' by the 64-bit number 'b' and return the full 128-bit product
+#[cfg(all(not(all(feature = "aarch64-asm", target_arch = "aarch64")), not(strength_reduce_no_u128)))]
+#[inline]
+pub(crate) fn multiply_64_by_64(a: u64, b: u64) -> u128 {
+	a as u128 * b as u128
+}
 
-	// Multiply each of the digits in a by b, adding them into the 'product' value.
-	// We don't zero out product, because we this will be called multiple times, so it probably contains a previous iteration's partial product, and we're adding + carrying on top of it
-	for (p, &a_digit) in product_lo.iter_mut().zip(a) {
-		carry += *p as u128;
-		carry += (a_digit as u128) * (b as u128);
+// LLVM usually turns a widening multiply into MUL and UMULH on its own, but with the `aarch64-asm` feature, we spell the upper half out, so that it's guaranteed to be a single instruction
+#[cfg(all(feature = "aarch64-asm", target_arch = "aarch64", not(strength_reduce_no_u128)))]
+#[inline]
+pub(crate) fn multiply_64_by_64(a: u64, b: u64) -> u128 {
+	((umulh(a, b) as u128) << 64) | a.wrapping_mul(b) as u128
+}
 
-		*p = carry as u64;
-		carry >>= 64;
+#[cfg(all(feature = "aarch64-asm", target_arch = "aarch64"))]
+#[inline(always)]
+fn umulh(a: u64, b: u64) -> u64 {
+	let upper;
+	// Safety: UMULH only reads and writes registers
+	unsafe {
+		::core::arch::asm!("umulh {upper}, {a}, {b}", a = in(reg) a, b = in(reg) b, upper = lateout(reg) upper, options(pure, nomem, nostack, preserves_flags));
 	}
+	upper
+}
+
+// multiply the 64-bit number 'a' by the 64-bit number 'b' and return the upper 64 bits of the product
+// 64-bit targets can do this with a single widening multiply, or with a single UMULH on aarch64 with the `aarch64-asm` feature
+#[cfg(all(target_pointer_width = "64", not(target_family = "wasm"), not(strength_reduce_no_u128), not(all(feature = "aarch64-asm", target_arch = "aarch64"))))]
+#[inline]
+pub(crate) fn multiply_64_by_64_upperbits(a: u64, b: u64) -> u64 {
+	(multiply_64_by_64(a, b) >> 64) as u64
+}
+
+#[cfg(all(feature = "aarch64-asm", target_arch = "aarch64"))]
+#[inline]
+pub(crate) fn multiply_64_by_64_upperbits(a: u64, b: u64) -> u64 {
+	umulh(a, b)
+}
+
+// On 32-bit targets and WebAssembly, there's no widening multiply, so a 128-bit multiply is a slow libcall, and we assemble the upper half from 32-bit limbs instead
+#[cfg(all(any(not(target_pointer_width = "64"), target_family = "wasm", strength_reduce_no_u128), not(all(feature = "aarch64-asm", target_arch = "aarch64"))))]
+#[inline]
+pub(crate) fn multiply_64_by_64_upperbits(a: u64, b: u64) -> u64 {
+	multiply_64_by_64_upperbits_limbs(a, b)
+}
 
-	// We're done multiplying, we just need to finish carrying through the rest of the product.
-	let mut p = product_hi.iter_mut();
-	while carry != 0 {
-		let p = p.next().expect("carry overflow during multiplication!");
-		carry += *p as u128;
+// multiply the 64-bit number 'a' by the 64-bit number 'b' and return the upper 64 bits of the product, using only 32x32->64 multiplies
+#[cfg_attr(any(all(target_pointer_width = "64", not(target_family = "wasm"), not(strength_reduce_no_u128)), all(feature = "aarch64-asm", target_arch = "aarch64")), allow(dead_code))]
+#[inline]
+fn multiply_64_by_64_upperbits_limbs(a: u64, b: u64) -> u64 {
+	let a_lo = a as u32 as u64;
+	let a_hi = a >> 32;
+	let b_lo = b as u32 as u64;
+	let b_hi = b >> 32;
+
+	// compute the four 32x32 partial products, and carry the middle ones into the upper half
+	let lo_lo = a_lo * b_lo;
+	let lo_hi = a_lo * b_hi;
+	let hi_lo = a_hi * b_lo;
+	let hi_hi = a_hi * b_hi;
+
+	let middle = (lo_lo >> 32) + (lo_hi as u32 as u64) + (hi_lo as u32 as u64);
+	hi_hi + (lo_hi >> 32) + (hi_lo >> 32) + (middle >> 32)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	#[cfg(not(strength_reduce_no_u128))]
+	use num_bigint::BigUint;
+
+	#[test]
+	#[cfg(not(strength_reduce_no_u128))]
+	fn test_multiply_128_by_128_upperbits() {
+		let values = [0, 1, 3, u64::MAX as u128, 1 << 64, (1 << 64) + 1, u128::MAX / 3, u128::MAX / 3 * 2 + 1, 0x8000_0000_0000_0001_FFFF_FFFF_FFFF_FFFF, u128::MAX - 1, u128::MAX];
+		for &a in &values {
+			for &b in &values {
+				let expected: BigUint = (BigUint::from(a) * BigUint::from(b)) >> 128;
+				assert_eq!(expected, BigUint::from(super::multiply_128_by_128_upperbits(a, b)), "wrong product for {} * {}", a, b);
+			}
+		}
+	}
 
-		*p = carry as u64;
-		carry >>= 64;
+	#[test]
+	fn test_multiply_64_by_64_upperbits() {
+		let values = [0, 1, 2, 3, 0xFFFF_FFFF, 0x1_0000_0000, 0x1_0000_0001, 0x5555_5555_5555_5555, 0xAAAA_AAAA_AAAA_AAAB, u64::MAX / 3, u64::MAX - 1, u64::MAX];
+		for &a in &values {
+			for &b in &values {
+				let expected = ((a as u128 * b as u128) >> 64) as u64;
+				assert_eq!(expected, super::multiply_64_by_64_upperbits(a, b), "wrong product for {} * {}", a, b);
+				assert_eq!(expected, super::multiply_64_by_64_upperbits_limbs(a, b), "wrong limb product for {} * {}", a, b);
+				#[cfg(not(strength_reduce_no_u128))]
+				assert_eq!(a as u128 * b as u128, super::multiply_64_by_64(a, b), "wrong full product for {} * {}", a, b);
+			}
+		}
 	}
 }
@@ -0,0 +1,314 @@
+use ::{Reducible, StrengthReduced, StrengthReducedU64};
+use long_division;
+use long_multiplication;
+
+/// Modular arithmetic with a fixed modulus.
+///
+/// Wraps the strength-reduced divisor for the modulus, so every operation that would otherwise end in a `%` is a multiply instead.
+/// `mul_mod` and `pow_mod` widen each product to 128 bits, and reduce it with a precomputed reciprocal of the modulus, so they never touch a hardware divide.
+///
+/// Apart from `reduce` and the base of `pow_mod`, the operands must already be reduced, ie smaller than the modulus. In debug builds, passing an unreduced operand panics.
+///
+/// ```
+/// use strength_reduce::ReducedModulus;
+///
+/// let modulus = ReducedModulus::<u64>::new(1_000_000_007);
+/// let a = modulus.reduce(123_456_789_012);
+/// assert_eq!(a, 123_456_789_012 % 1_000_000_007);
+/// assert_eq!(modulus.add_mod(a, 999_999_999), (a + 999_999_999) % 1_000_000_007);
+/// assert_eq!(modulus.sub_mod(3, 5), 1_000_000_005);
+/// assert_eq!(modulus.mul_mod(a, a), ((a as u128 * a as u128) % 1_000_000_007) as u64);
+///
+/// // Fermat's little theorem
+/// assert_eq!(modulus.pow_mod(2, 1_000_000_006), 1);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ReducedModulus<T: Reducible> {
+    divisor: StrengthReduced<T>,
+    // the reciprocal of the modulus after shifting it left until its top bit is set, for reducing double-width products
+    reciprocal: T,
+}
+
+impl ReducedModulus<u64> {
+    /// Creates a new modulus instance.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `modulus` is 0
+    #[inline]
+    pub fn new(modulus: u64) -> Self {
+        assert!(modulus > 0);
+        Self::from(StrengthReducedU64::new(modulus))
+    }
+
+    /// Creates a new modulus instance, or returns `None` if `modulus` is 0.
+    #[inline]
+    pub fn try_new(modulus: u64) -> Option<Self> {
+        StrengthReducedU64::try_new(modulus).map(Self::from)
+    }
+
+    /// Retrieve the value used to create this struct
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.divisor.get()
+    }
+
+    /// Returns the strength-reduced divisor for the modulus.
+    #[inline]
+    pub fn divisor(&self) -> StrengthReducedU64 {
+        self.divisor
+    }
+
+    /// Computes `value % modulus`, for any `value`.
+    #[inline]
+    pub fn reduce(&self, value: u64) -> u64 {
+        value % self.divisor
+    }
+
+    /// Computes `(a + b) % modulus`.
+    #[inline]
+    pub fn add_mod(&self, a: u64, b: u64) -> u64 {
+        self.debug_assert_reduced(a);
+        self.debug_assert_reduced(b);
+
+        // if the sum wrapped around, subtracting the modulus wraps it back
+        let (sum, overflowed) = a.overflowing_add(b);
+        if overflowed || sum >= self.get() {
+            sum.wrapping_sub(self.get())
+        } else {
+            sum
+        }
+    }
+
+    /// Computes `(a - b) % modulus`, wrapping around to the top of the range instead of going negative.
+    #[inline]
+    pub fn sub_mod(&self, a: u64, b: u64) -> u64 {
+        self.debug_assert_reduced(a);
+        self.debug_assert_reduced(b);
+
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.get())
+        }
+    }
+
+    /// Computes `-a % modulus`, ie the value that gives 0 when added to `a`.
+    #[inline]
+    pub fn neg_mod(&self, a: u64) -> u64 {
+        self.debug_assert_reduced(a);
+
+        if a == 0 {
+            0
+        } else {
+            self.get() - a
+        }
+    }
+
+    /// Computes `(a * b) % modulus`, without overflowing.
+    #[inline]
+    pub fn mul_mod(&self, a: u64, b: u64) -> u64 {
+        self.debug_assert_reduced(a);
+        self.debug_assert_reduced(b);
+
+        // both operands are smaller than the modulus, so the product is smaller than modulus^2, and its upper half is smaller than the modulus
+        self.reduce_product(long_multiplication::multiply_64_by_64(a, b))
+    }
+
+    // Computes value % modulus, for any value whose upper half is smaller than the modulus.
+    // Shifting both up by the modulus's leading zeros keeps the upper half of the value smaller than the shifted modulus, which is all the reciprocal division needs, and shifting the remainder back down undoes it
+    #[inline]
+    pub(crate) fn reduce_product(&self, value: u128) -> u64 {
+        let shift = self.get().leading_zeros();
+        let value = value << shift;
+        let (_, remainder) = long_division::divide_128_by_64_preinv((value >> 64) as u64, value as u64, self.get() << shift, self.reciprocal);
+        remainder >> shift
+    }
+
+    // Computes value % modulus, for any 128-bit value
+    #[inline]
+    pub(crate) fn reduce_wide(&self, value: u128) -> u64 {
+        let upper_remainder = self.reduce((value >> 64) as u64);
+        self.reduce_product(((upper_remainder as u128) << 64) | value as u64 as u128)
+    }
+
+    /// Computes `base.pow(exponent) % modulus`, without overflowing.
+    ///
+    /// Unlike the other operations, `base` doesn't need to be reduced. `pow_mod(0, 0)` is 1, or 0 if the modulus is 1.
+    #[inline]
+    pub fn pow_mod(&self, base: u64, exponent: u64) -> u64 {
+        let mut base = self.reduce(base);
+        let mut exponent = exponent;
+        let mut result = self.reduce(1);
+
+        // square-and-multiply, from the lowest bit of the exponent up
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul_mod(result, base);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = self.mul_mod(base, base);
+            }
+        }
+        result
+    }
+
+    #[inline]
+    fn debug_assert_reduced(&self, value: u64) {
+        debug_assert!(value < self.get(), "operand {} is not reduced modulo {}", value, self.get());
+    }
+}
+
+impl From<StrengthReducedU64> for ReducedModulus<u64> {
+    /// Converts without redoing the division for the strength-reduced divisor. Only the reciprocal for `mul_mod` is computed, which takes a table lookup and a few multiplies.
+    #[inline]
+    fn from(divisor: StrengthReducedU64) -> Self {
+        let modulus = divisor.get();
+        Self { divisor, reciprocal: long_division::reciprocal_128_by_64(modulus << modulus.leading_zeros()) }
+    }
+}
+
+/// Computes the modular inverse of `a`, ie the value `x` such that `a * x % modulus == 1`, or returns `None` if `a` and the modulus aren't coprime.
+///
+/// `a` doesn't need to be reduced. This runs the extended Euclidean algorithm, and its first step, which divides `a` by the modulus, uses the strength-reduced divisor.
+/// The later steps divide by values that change every iteration, so they use plain division.
+///
+/// ```
+/// use strength_reduce::{mod_inverse, ReducedModulus};
+///
+/// let modulus = ReducedModulus::<u64>::new(1_000_000_007);
+/// let inverse = mod_inverse(123_456_789, &modulus).unwrap();
+/// assert_eq!(modulus.mul_mod(123_456_789, inverse), 1);
+///
+/// assert_eq!(mod_inverse(4, &ReducedModulus::<u64>::new(10)), None);
+/// ```
+#[inline]
+pub fn mod_inverse(a: u64, modulus: &ReducedModulus<u64>) -> Option<u64> {
+    let (gcd, x, _) = modulus.divisor().extended_gcd(a);
+    if gcd == 1 {
+        Some((x as i128).rem_euclid(modulus.get() as i128) as u64)
+    } else {
+        None
+    }
+}
+
+impl StrengthReducedU64 {
+    /// Computes `base.pow(exponent) % divisor`, without overflowing.
+    ///
+    /// This runs square-and-multiply, and reduces every step's 128-bit product the same way as [`ReducedModulus::mul_mod`]. Setting that up costs a table lookup and a few multiplies,
+    /// so when raising many values to a power with the same modulus, create a [`ReducedModulus`] once instead.
+    ///
+    /// ```
+    /// use strength_reduce::StrengthReducedU64;
+    ///
+    /// let modulus = StrengthReducedU64::new(1_000_000_007);
+    /// assert_eq!(modulus.pow_mod(3, 4), 81);
+    /// assert_eq!(modulus.pow_mod(2, 1_000_000_006), 1);
+    /// ```
+    #[inline]
+    pub fn pow_mod(&self, base: u64, exponent: u64) -> u64 {
+        ReducedModulus::from(*self).pow_mod(base, exponent)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+        let modulus = modulus as u128;
+        let mut result = 1 % modulus;
+        for _ in 0..exponent {
+            result = result * base as u128 % modulus;
+        }
+        result as u64
+    }
+
+    fn naive_gcd(mut a: u64, mut b: u64) -> u64 {
+        while b != 0 {
+            let remainder = a % b;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    #[test]
+    fn test_reduced_modulus() {
+        let mut rng = StdRng::seed_from_u64(1102);
+        let max = u64::MAX;
+        let edge_moduli = [1, 2, 3, 7, 10, 1 << 32, (1 << 32) + 1, 1_000_000_007, (1 << 61) - 1, max / 2, max / 2 + 1, max - 1, max];
+        let random_moduli: [u64; 50] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..64)).max(1));
+
+        for &modulus in edge_moduli.iter().chain(&random_moduli) {
+            let reduced_modulus = ReducedModulus::<u64>::new(modulus);
+            assert_eq!(modulus, reduced_modulus.get());
+
+            let edge_operands = [0, 1, modulus / 2, modulus - 1];
+            let random_operands: [u64; 20] = core::array::from_fn(|_| rng.gen_range(0..modulus));
+            for &a in edge_operands.iter().chain(&random_operands) {
+                let a = a % modulus;
+                assert_eq!((modulus - a) % modulus, reduced_modulus.neg_mod(a), "neg_mod failed with a: {}, modulus: {}", a, modulus);
+                for &b in edge_operands.iter().chain(&random_operands) {
+                    let b = b % modulus;
+                    let (wide_a, wide_b, wide_modulus) = (a as u128, b as u128, modulus as u128);
+                    assert_eq!(((wide_a + wide_b) % wide_modulus) as u64, reduced_modulus.add_mod(a, b), "add_mod failed with a: {}, b: {}, modulus: {}", a, b, modulus);
+                    assert_eq!(((wide_a + wide_modulus - wide_b) % wide_modulus) as u64, reduced_modulus.sub_mod(a, b), "sub_mod failed with a: {}, b: {}, modulus: {}", a, b, modulus);
+                    assert_eq!((wide_a * wide_b % wide_modulus) as u64, reduced_modulus.mul_mod(a, b), "mul_mod failed with a: {}, b: {}, modulus: {}", a, b, modulus);
+                }
+            }
+
+            for &base in &[0, 1, 2, 3, modulus - 1, max] {
+                for exponent in 0..20 {
+                    assert_eq!(naive_pow_mod(base, exponent, modulus), reduced_modulus.pow_mod(base, exponent), "pow_mod failed with base: {}, exponent: {}, modulus: {}", base, exponent, modulus);
+                    assert_eq!(naive_pow_mod(base, exponent, modulus), reduced_modulus.divisor().pow_mod(base, exponent));
+                }
+            }
+        }
+
+        assert!(ReducedModulus::<u64>::try_new(0).is_none());
+    }
+
+    #[test]
+    fn test_pow_mod_large_exponent() {
+        // Fermat's little theorem, for a few primes
+        for &prime in &[3u64, 65537, 1_000_000_007, (1 << 61) - 1, 18446744073709551557] {
+            let reduced_modulus = ReducedModulus::<u64>::new(prime);
+            for &base in &[2, 5, 12347, prime - 1] {
+                assert_eq!(1, reduced_modulus.pow_mod(base, prime - 1));
+                assert_eq!(base % prime, reduced_modulus.pow_mod(base, prime));
+                assert_eq!(base % prime, StrengthReducedU64::new(prime).pow_mod(base, prime));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        let mut rng = StdRng::seed_from_u64(1106);
+        let max = u64::MAX;
+        let moduli = [1, 2, 3, 10, 12, 1 << 32, 1_000_000_007, (1 << 61) - 1, max / 2 + 1, max - 1, max];
+        for &modulus in &moduli {
+            let reduced_modulus = ReducedModulus::<u64>::new(modulus);
+            let random_values: [u64; 50] = core::array::from_fn(|_| rng.gen());
+            for &a in [0, 1, 2, 3, modulus - 1, modulus, max].iter().chain(&random_values) {
+                let gcd = naive_gcd(a, modulus);
+                match mod_inverse(a, &reduced_modulus) {
+                    Some(inverse) => {
+                        assert!(inverse < modulus);
+                        assert_eq!(1 % modulus, (a as u128 * inverse as u128 % modulus as u128) as u64, "mod_inverse failed with a: {}, modulus: {}", a, modulus);
+                    }
+                    None => assert_ne!(1, gcd, "mod_inverse returned None with a: {}, modulus: {}", a, modulus),
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_unreduced_operand() {
+        ReducedModulus::<u64>::new(10).mul_mod(10, 3);
+    }
+}
@@ -0,0 +1,170 @@
+use ::{BarrettU64, ExactDivU64};
+use long_multiplication;
+
+/// Implements modular multiplication in Montgomery form, for odd moduli.
+///
+/// Values are converted into Montgomery form with `to_montgomery`, ie multiplied by 2^64 modulo the modulus. In that form, each `mul` costs three 64x64 multiplies and no division at all,
+/// which makes long chains of modular multiplications, like the exponentiations in a Miller-Rabin test, faster than reducing each product with a [`ReducedModulus`](crate::ReducedModulus).
+/// Converting in and out costs a multiplication each, so it only pays off when values stay in Montgomery form for several operations.
+///
+/// The constants come from the same places as the rest of the crate: The inverse of the modulus mod 2^64 is the one [`ExactDivU64`] uses, and 2^64 and 2^128 are reduced modulo the modulus with a [`BarrettU64`].
+///
+/// ```
+/// use strength_reduce::MontgomeryU64;
+///
+/// let modulus = MontgomeryU64::new(1_000_000_007);
+/// let a = modulus.to_montgomery(123_456_789);
+/// let b = modulus.to_montgomery(987_654_321);
+/// assert_eq!(modulus.from_montgomery(modulus.mul(a, b)), 123_456_789 * 987_654_321 % 1_000_000_007);
+///
+/// // Fermat's little theorem
+/// let two = modulus.to_montgomery(2);
+/// assert_eq!(modulus.from_montgomery(modulus.pow(two, 1_000_000_006)), 1);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MontgomeryU64 {
+    modulus: u64,
+    // the inverse of the modulus mod 2^64
+    inverse: u64,
+    // 2^64 % modulus, ie 1 in Montgomery form
+    one: u64,
+    // 2^128 % modulus, for converting into Montgomery form with a single reduction
+    r_squared: u64,
+}
+
+impl MontgomeryU64 {
+    /// Creates a new Montgomery multiplication instance.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `modulus` is even, including 0
+    #[inline]
+    pub fn new(modulus: u64) -> Self {
+        Self::try_new(modulus).expect("the modulus for Montgomery multiplication must be odd")
+    }
+
+    /// Creates a new Montgomery multiplication instance, or returns `None` if `modulus` is even, including 0.
+    #[inline]
+    pub fn try_new(modulus: u64) -> Option<Self> {
+        if modulus & 1 == 0 {
+            return None;
+        }
+
+        let inverse = ExactDivU64::new(modulus).inverse();
+        let barrett = BarrettU64::new(modulus);
+        let one = barrett.reduce(1 << 64);
+        let r_squared = barrett.reduce(one as u128 * one as u128);
+        Some(Self { modulus, inverse, one, r_squared })
+    }
+
+    /// Retrieve the value used to create this struct
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Converts `value` into Montgomery form, ie computes `value * 2^64 % modulus`. `value` doesn't need to be smaller than the modulus.
+    #[inline]
+    pub fn to_montgomery(&self, value: u64) -> u64 {
+        // value < 2^64 and r_squared < modulus, so the product is in range for a single reduction
+        self.redc(long_multiplication::multiply_64_by_64(value, self.r_squared))
+    }
+
+    /// Converts `value` out of Montgomery form, ie computes `value / 2^64 % modulus`.
+    #[inline]
+    pub fn from_montgomery(&self, value: u64) -> u64 {
+        self.redc(value as u128)
+    }
+
+    /// Multiplies two values in Montgomery form, returning the product in Montgomery form.
+    ///
+    /// Both operands must be smaller than the modulus, like the results of `to_montgomery` are. In debug builds, passing an unreduced operand panics.
+    #[inline]
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        debug_assert!(a < self.modulus && b < self.modulus, "operands {} and {} are not reduced modulo {}", a, b, self.modulus);
+        self.redc(long_multiplication::multiply_64_by_64(a, b))
+    }
+
+    /// Raises `base`, in Montgomery form, to the power of `exponent`, returning the result in Montgomery form.
+    ///
+    /// `base` must be smaller than the modulus, like the results of `to_montgomery` are. In debug builds, passing an unreduced base panics.
+    #[inline]
+    pub fn pow(&self, base: u64, exponent: u64) -> u64 {
+        let mut base = base;
+        let mut exponent = exponent;
+        let mut result = self.one;
+
+        // square-and-multiply, from the lowest bit of the exponent up
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = self.mul(base, base);
+            }
+        }
+        result
+    }
+
+    // Montgomery reduction: computes value / 2^64 % modulus, for any value < modulus * 2^64
+    // multiplier * modulus has the same low 64 bits as value, so their difference is an exact multiple of 2^64, and we only need the upper halves to compute it.
+    // Both upper halves are smaller than the modulus, so the difference is off by at most one modulus, and this form never overflows, even for moduli close to 2^64
+    #[inline]
+    fn redc(&self, value: u128) -> u64 {
+        let multiplier = (value as u64).wrapping_mul(self.inverse);
+        let subtrahend = long_multiplication::multiply_64_by_64_upperbits(multiplier, self.modulus);
+        let value_hi = (value >> 64) as u64;
+        if value_hi >= subtrahend {
+            value_hi - subtrahend
+        } else {
+            value_hi.wrapping_sub(subtrahend).wrapping_add(self.modulus)
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_montgomery() {
+        let mut rng = StdRng::seed_from_u64(1105);
+        let max = u64::MAX;
+        let edge_moduli = [1, 3, 5, 7, 11, 101, (1 << 32) + 1, 1_000_000_007, (1 << 61) - 1, max / 2, max / 2 + 2, max - 2, max];
+        let random_moduli: [u64; 50] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..64)) | 1);
+
+        for &modulus in edge_moduli.iter().chain(&random_moduli) {
+            let montgomery = MontgomeryU64::new(modulus);
+            assert_eq!(modulus, montgomery.get());
+            let wide_modulus = modulus as u128;
+
+            let edge_values = [0, 1, 2, modulus / 2, modulus - 1, modulus, max];
+            let random_values: [u64; 20] = core::array::from_fn(|_| rng.gen());
+            for &a in edge_values.iter().chain(&random_values) {
+                let mont_a = montgomery.to_montgomery(a);
+                assert_eq!(((a as u128) << 64) % wide_modulus, mont_a as u128, "to_montgomery failed with value: {}, modulus: {}", a, modulus);
+                assert_eq!(a % modulus, montgomery.from_montgomery(mont_a), "from_montgomery failed with value: {}, modulus: {}", a, modulus);
+
+                for &b in edge_values.iter().chain(&random_values) {
+                    let product = montgomery.from_montgomery(montgomery.mul(mont_a, montgomery.to_montgomery(b)));
+                    assert_eq!((a as u128 * b as u128 % wide_modulus) as u64, product, "mul failed with a: {}, b: {}, modulus: {}", a, b, modulus);
+                }
+
+                let mut expected = 1 % wide_modulus;
+                for exponent in 0..20 {
+                    assert_eq!(expected as u64, montgomery.from_montgomery(montgomery.pow(mont_a, exponent)), "pow failed with base: {}, exponent: {}, modulus: {}", a, exponent, modulus);
+                    expected = expected * a as u128 % wide_modulus;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_even_modulus() {
+        assert!(MontgomeryU64::try_new(0).is_none());
+        assert!(MontgomeryU64::try_new(2).is_none());
+        assert!(MontgomeryU64::try_new(1 << 63).is_none());
+    }
+}
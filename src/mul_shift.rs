@@ -0,0 +1,109 @@
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32};
+
+/// A fixed-point multiply-and-shift: computes `(x * multiplier + add) >> shift` using a 128-bit intermediate.
+///
+/// This is the primitive operation behind strength-reduced division, and it's also what fixed-point scaling, hash finalizers,
+/// and hand-rolled division schemes need. The intermediate product can't overflow, so any combination of inputs is valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MulShift {
+    multiplier: u64,
+    add: u64,
+    shift: u32,
+}
+impl MulShift {
+    /// Creates a new instance that computes `(x * multiplier) >> shift`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `shift` is 128 or greater
+    #[inline]
+    pub fn new(multiplier: u64, shift: u32) -> Self {
+        Self::with_add(multiplier, shift, 0)
+    }
+
+    /// Creates a new instance that computes `(x * multiplier + add) >> shift`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `shift` is 128 or greater
+    #[inline]
+    pub fn with_add(multiplier: u64, shift: u32, add: u64) -> Self {
+        assert!(shift < 128);
+        Self { multiplier, add, shift }
+    }
+
+    /// Computes `(x * multiplier + add) >> shift`, truncated to the lower 64 bits.
+    #[inline]
+    pub fn apply(&self, x: u64) -> u64 {
+        // (2^64 - 1)^2 + (2^64 - 1) < 2^128, so this can't overflow
+        let product = (x as u128) * (self.multiplier as u128) + (self.add as u128);
+        (product >> self.shift) as u64
+    }
+
+    /// Retrieve the multiplier used to create this struct
+    #[inline]
+    pub fn multiplier(&self) -> u64 {
+        self.multiplier
+    }
+
+    /// Retrieve the shift amount used to create this struct
+    #[inline]
+    pub fn shift(&self) -> u32 {
+        self.shift
+    }
+
+    /// Retrieve the addend used to create this struct
+    #[inline]
+    pub fn add(&self) -> u64 {
+        self.add
+    }
+}
+
+// The types whose multiplier fits in a u64 can hand theirs out directly: applying the result to a numerator computes the quotient.
+macro_rules! mul_shift_from {
+    ($struct_name:ident, $multiplier_bits:expr) => (
+        impl From<$struct_name> for MulShift {
+            #[inline]
+            fn from(reduced: $struct_name) -> Self {
+                if reduced.multiplier == 0 {
+                    Self::new(1, reduced.shift as u32)
+                } else {
+                    Self::new(reduced.multiplier as u64, $multiplier_bits)
+                }
+            }
+        }
+    )
+}
+
+mul_shift_from!(StrengthReducedU8, 16);
+mul_shift_from!(StrengthReducedU16, 32);
+mul_shift_from!(StrengthReducedU32, 64);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_shift_apply() {
+        assert_eq!(MulShift::new(3, 1).apply(5), 7);
+        assert_eq!(MulShift::with_add(3, 1, 1).apply(5), 8);
+        assert_eq!(MulShift::new(u64::MAX, 64).apply(u64::MAX), u64::MAX - 1);
+        assert_eq!(MulShift::with_add(u64::MAX, 127, u64::MAX).apply(u64::MAX), 1);
+    }
+
+    #[test]
+    fn test_mul_shift_from_reduced() {
+        let divisors = [1, 2, 3, 5, 7, 8, 10, 100, 255];
+        let numerators = [0, 1, 2, 3, 99, 100, 101, 254, 255];
+        for &divisor in &divisors {
+            let mul_shift_u8 = MulShift::from(StrengthReducedU8::new(divisor as u8));
+            let mul_shift_u16 = MulShift::from(StrengthReducedU16::new(divisor as u16 * 257));
+            let mul_shift_u32 = MulShift::from(StrengthReducedU32::new(divisor as u32 * 65537));
+            for &numerator in &numerators {
+                assert_eq!(mul_shift_u8.apply(numerator), numerator / divisor);
+                assert_eq!(mul_shift_u16.apply(numerator * 257), (numerator * 257) / (divisor * 257));
+                assert_eq!(mul_shift_u32.apply(numerator * 65537 + 3), (numerator * 65537 + 3) / (divisor * 65537));
+            }
+        }
+    }
+}
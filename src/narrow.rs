@@ -0,0 +1,208 @@
+use core::num::NonZeroU64;
+use core::ops::{Div, Rem};
+
+use ::constant_time::ConstantTimeInt;
+
+/// Implements division of 32-bit numerators by a 64-bit divisor, via multiplication and shifts.
+///
+/// `StrengthReducedU64` needs a 128-bit multiplier to handle every 64-bit numerator. When the numerators are known to fit in a `u32`, a 64-bit multiplier is enough,
+/// so this uses the same cheaper math as `StrengthReducedU32`, while still accepting any `u64` divisor.
+///
+/// # Example:
+/// ```
+/// use strength_reduce::StrengthReducedU64Narrow;
+///
+/// let row_length = StrengthReducedU64Narrow::new(1920);
+/// let pixel_index: u32 = 1_000_000;
+/// assert_eq!(pixel_index / row_length, 520);
+/// assert_eq!(pixel_index % row_length, 1600);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StrengthReducedU64Narrow {
+    multiplier: u64,
+    divisor: NonZeroU64,
+}
+impl StrengthReducedU64Narrow {
+    /// Creates a new divisor instance.
+    ///
+    /// # Panics:
+    /// 
+    /// Panics if `divisor` is 0
+    #[inline]
+    pub const fn new(divisor: u64) -> Self {
+        match NonZeroU64::new(divisor) {
+            Some(divisor) => Self::new_impl(divisor),
+            None => panic!("attempt to create a divisor of zero"),
+        }
+    }
+
+    #[inline]
+    const fn new_impl(nonzero_divisor: NonZeroU64) -> Self {
+        let divisor = nonzero_divisor.get();
+
+        // Lemire's proof for the 2N-bit multiplier only relies on the numerator being N bits, so ceil(2^64 / divisor) works for any 32-bit numerator.
+        // If the divisor doesn't fit in 32 bits, the multiplier is small enough that every quotient comes out as 0, which is also correct.
+        if divisor.is_power_of_two() {
+            Self{ multiplier: 0, divisor: nonzero_divisor }
+        } else {
+            Self{ multiplier: u64::MAX / divisor + 1, divisor: nonzero_divisor }
+        }
+    }
+
+    /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+    ///
+    /// Use this instead of `new()` when the divisor comes from untrusted input.
+    #[inline]
+    pub const fn try_new(divisor: u64) -> Option<Self> {
+        match NonZeroU64::new(divisor) {
+            Some(divisor) => Some(Self::new_impl(divisor)),
+            None => None,
+        }
+    }
+
+    /// Creates a new divisor instance without checking that `divisor` is nonzero.
+    ///
+    /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+    ///
+    /// # Safety
+    ///
+    /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+    #[inline]
+    pub unsafe fn new_unchecked(divisor: u64) -> Self {
+        debug_assert!(divisor != 0);
+        Self::new_impl(NonZeroU64::new_unchecked(divisor))
+    }
+
+    /// Simultaneous truncated integer division and modulus.
+    /// Returns `(quotient, remainder)`.
+    #[inline]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub fn div_rem(numerator: u32, denom: Self) -> (u32, u32) {
+        let quotient = numerator / denom;
+        let remainder = (numerator as u64 - quotient as u64 * denom.divisor.get()) as u32;
+        (quotient, remainder)
+    }
+
+    /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+    #[inline]
+    pub fn divide(&self, numerator: u32) -> u32 {
+        numerator / *self
+    }
+
+    /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+    #[inline]
+    pub fn remainder(&self, numerator: u32) -> u32 {
+        numerator % *self
+    }
+
+    /// Retrieve the value used to create this struct
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.divisor.get()
+    }
+
+    /// Retrieve the value used to create this struct, as a nonzero integer
+    #[inline]
+    pub fn get_nonzero(&self) -> NonZeroU64 {
+        self.divisor
+    }
+}
+
+impl Default for StrengthReducedU64Narrow {
+    /// Creates a divisor of 1. Like every other power of two, 1 takes the shift-and-mask path, so dividing by it never multiplies.
+    #[inline]
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl From<NonZeroU64> for StrengthReducedU64Narrow {
+    /// Creates a new divisor instance. The divisor is already known to be nonzero, so unlike `new()`, this never panics.
+    #[inline]
+    fn from(divisor: NonZeroU64) -> Self {
+        Self::new_impl(divisor)
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl Div<StrengthReducedU64Narrow> for u32 {
+    type Output = u32;
+
+    #[inline]
+    fn div(self, rhs: StrengthReducedU64Narrow) -> Self::Output {
+        if cfg!(feature = "constant-time") {
+            // Same as StrengthReducedU32: mask the power of two multiplier back in, except for a divisor of 1, whose multiplier needs 65 bits, so we mask the numerator into the quotient instead
+            let multiplier = rhs.multiplier | (u64::mask(rhs.multiplier == 0) & ((1 << (63 - rhs.divisor.trailing_zeros())) << 1));
+            let numerator = self as u64;
+            let multiplied_hi = numerator * (multiplier >> 32);
+            let multiplied_lo = (numerator * (multiplier as u32 as u64)) >> 32;
+
+            ((multiplied_hi + multiplied_lo) >> 32) as u32 | (self & u32::mask(rhs.divisor.get() == 1))
+        } else if rhs.multiplier == 0 {
+            (self as u64 >> rhs.divisor.trailing_zeros()) as u32
+        } else {
+            let numerator = self as u64;
+            let multiplied_hi = numerator * (rhs.multiplier >> 32);
+            let multiplied_lo = (numerator * (rhs.multiplier as u32 as u64)) >> 32;
+
+            ((multiplied_hi + multiplied_lo) >> 32) as u32
+        }
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl Rem<StrengthReducedU64Narrow> for u32 {
+    type Output = u32;
+
+    #[inline]
+    fn rem(self, rhs: StrengthReducedU64Narrow) -> Self::Output {
+        StrengthReducedU64Narrow::div_rem(self, rhs).1
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_strength_reduced_u64_narrow() {
+        let mut rng = StdRng::seed_from_u64(64);
+        let max = u64::MAX;
+        let edge_divisors = [1, 2, 3, 5, 7, 10, 641, 1920, u32::MAX as u64 - 1, u32::MAX as u64, u32::MAX as u64 + 1, u32::MAX as u64 + 2, max / 3, max - 1, max];
+        let random_divisors: [u64; 200] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..64)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU64Narrow::new(divisor);
+            assert_eq!(divisor, reduced_divisor.get());
+
+            let edge_numerators = [0, 1, 2, 1919, 1920, 1921, u32::MAX / 2, u32::MAX - 1, u32::MAX];
+            let random_numerators: [u32; 100] = core::array::from_fn(|_| rng.gen());
+            for &numerator in edge_numerators.iter().chain(&random_numerators) {
+                let expected_div = (numerator as u64 / divisor) as u32;
+                let expected_rem = (numerator as u64 % divisor) as u32;
+                assert_eq!(expected_div, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected_rem, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!((expected_div, expected_rem), StrengthReducedU64Narrow::div_rem(numerator, reduced_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!((expected_div, expected_rem), (reduced_divisor.divide(numerator), reduced_divisor.remainder(numerator)), "divide/remainder failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_narrow_constructors() {
+        assert!(StrengthReducedU64Narrow::try_new(0).is_none());
+        assert_eq!(1, StrengthReducedU64Narrow::default().get());
+        for &divisor in &[1, 2, 3, 1920, u32::MAX as u64 + 1, u64::MAX] {
+            let reduced_divisor = StrengthReducedU64Narrow::new(divisor);
+            assert_eq!(Some(divisor), StrengthReducedU64Narrow::try_new(divisor).map(|d| d.get()));
+            assert_eq!(divisor, reduced_divisor.get_nonzero().get());
+            assert_eq!(divisor, unsafe { StrengthReducedU64Narrow::new_unchecked(divisor) }.get());
+            let nonzero_divisor = StrengthReducedU64Narrow::from(reduced_divisor.get_nonzero());
+            for &numerator in &[0, 1, 1919, 1920, u32::MAX] {
+                assert_eq!(numerator / reduced_divisor, numerator / nonzero_divisor, "Divide failed with numerator: {}, nonzero divisor: {}", numerator, divisor);
+                assert_eq!(numerator, numerator / StrengthReducedU64Narrow::default());
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use ::constant_time::ConstantTimeInt;
+use ::long_multiplication;
+
+const U32_MAX: u64 = u32::MAX as u64;
+
+// estimates the 32-bit quotient digit of (numerator_hi:numerator_digit) / divisor, where numerator_digit is the next 32 bits of the numerator
+// assumes that the divisor is normalized, and that the quotient fits in 32 bits
+const fn divide_96_by_64(numerator_hi: u64, numerator_digit: u64, divisor_hi: u64, divisor_lo: u64) -> u64 {
+    let mut quotient = numerator_hi / divisor_hi;
+    let mut remainder = numerator_hi - quotient * divisor_hi;
+
+    // dividing by the top half of the divisor overestimates the quotient, but since the divisor is normalized, Knuth's theorem B says it's at most 2 too high.
+    // checking the digit range first keeps quotient * divisor_lo from overflowing, and once the remainder doesn't fit in 32 bits, the estimate is known to be correct
+    while quotient > U32_MAX || quotient * divisor_lo > ((remainder << 32) | numerator_digit) {
+        quotient -= 1;
+        remainder += divisor_hi;
+        if remainder > U32_MAX {
+            break;
+        }
+    }
+    quotient
+}
+
+// divides a 128-bit number by a 64-bit divisor, returning the quotient as a 64-bit number, without any 128-bit arithmetic
+// assumes that the divisor has already been bit-shifted so that divisor.leading_zeros() == 0, and that numerator_hi < divisor, so the quotient fits in 64 bits
+// This is Knuth's algorithm D with 32-bit digits, as laid out in Hacker's Delight
+const fn divide_128_by_64_preshifted(numerator_hi: u64, numerator_lo: u64, divisor: u64) -> u64 {
+    let divisor_hi = divisor >> 32;
+    let divisor_lo = divisor & U32_MAX;
+    let numerator_mid = numerator_lo >> 32;
+    let numerator_lo = numerator_lo & U32_MAX;
+
+    let quotient_hi = divide_96_by_64(numerator_hi, numerator_mid, divisor_hi, divisor_lo);
+    // the remainder is smaller than the divisor, so the bits that the wrapping arithmetic drops always cancel out
+    let remainder_hi = ((numerator_hi << 32) | numerator_mid).wrapping_sub(quotient_hi.wrapping_mul(divisor));
+
+    let quotient_lo = divide_96_by_64(remainder_hi, numerator_lo, divisor_hi, divisor_lo);
+    (quotient_hi << 32) | quotient_lo
+}
+
+// Without u128, we can't store the 128-bit multiplier that the default build uses, so we use Granlund and Montgomery's 65-bit "round up" multiplier instead, and drop its implicit top bit.
+// These mirror the default versions in lib.rs, so that strength_reduced_u64 can use either
+pub(crate) type MultiplierU64 = u64;
+
+// computes the multiplier for a divisor that isn't a power of two
+#[inline]
+pub(crate) const fn multiplier_u64(divisor: u64) -> u64 {
+    // With l = ceil(log2(divisor)), the full multiplier is floor(2^(64 + l) / divisor) + 1. Without its implicit top bit, that's floor((2^l - divisor) * 2^64 / divisor) + 1,
+    // and shifting the divisor up to use all 64 bits turns 2^l - divisor into the shifted divisor's negation
+    let shifted_divisor = divisor << divisor.leading_zeros();
+    divide_128_by_64_preshifted(shifted_divisor.wrapping_neg(), 0, shifted_divisor) + 1
+}
+
+// divides by a divisor that isn't a power of two. the shift only depends on the divisor, so it gets hoisted out of loops
+#[inline]
+pub(crate) fn divide_u64(numerator: u64, multiplier: u64, divisor: u64) -> u64 {
+    let upper = long_multiplication::multiply_64_by_64_upperbits(numerator, multiplier);
+
+    // the multiplier has an implicit 65th bit, so the full product is upper + numerator. adding them directly could overflow, so average them instead, and shift one bit less
+    (upper + ((numerator - upper) >> 1)) >> (63 - divisor.leading_zeros())
+}
+
+// divides by any divisor without branching, for the constant-time feature
+#[inline]
+pub(crate) fn divide_u64_constant_time(numerator: u64, multiplier: u64, divisor: u64) -> u64 {
+    // a multiplier of 0 still gives an in-range shift, so we can always take the multiply path, and select the power of two quotient afterwards instead of branching.
+    // for a power of two, the shift is its trailing zeros
+    let shift = 63 - divisor.leading_zeros();
+    let upper = long_multiplication::multiply_64_by_64_upperbits(numerator, multiplier);
+    let quotient = (upper + ((numerator - upper) >> 1)) >> shift;
+    u64::select(multiplier == 0, numerator >> shift, quotient)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use ::StrengthReducedU64;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_divide_128_by_64_preshifted() {
+        let mut rng = StdRng::seed_from_u64(1058);
+        let edge_divisors = [1 << 63, (1 << 63) + 1, (1 << 63) | (1 << 32), (1 << 63) | u32::MAX as u64, u64::MAX - 1, u64::MAX];
+        let random_divisors: [u64; 100] = core::array::from_fn(|_| rng.gen::<u64>() | (1 << 63));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let random_numerators: [u64; 20] = core::array::from_fn(|_| rng.gen_range(0..divisor));
+            for &numerator_hi in [0, 1, divisor >> 1, divisor - 1].iter().chain(&random_numerators) {
+                for &numerator_lo in &[0, 1, u32::MAX as u64, 1 << 32, rng.gen(), u64::MAX] {
+                    let numerator = ((numerator_hi as u128) << 64) | numerator_lo as u128;
+                    let expected = (numerator / divisor as u128) as u64;
+                    assert_eq!(expected, divide_128_by_64_preshifted(numerator_hi, numerator_lo, divisor), "wrong quotient for {}/{}", numerator, divisor);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strength_reduced_u64_no_u128() {
+        let mut rng = StdRng::seed_from_u64(1058);
+        let max = u64::MAX;
+        let edge_divisors = [1, 2, 3, 5, 6, 7, 10, 641, 1 << 32, (1 << 32) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, max - 1, max];
+        let random_divisors: [u64; 200] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..63)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU64::new(divisor);
+            let random_numerators: [u64; 50] = core::array::from_fn(|_| rng.gen());
+            for &numerator in [0, 1, divisor - 1, divisor, divisor.wrapping_add(1), max - max % divisor, (max - max % divisor).wrapping_sub(1), max - 1, max].iter().chain(&random_numerators) {
+                assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!((numerator / divisor, numerator % divisor), StrengthReducedU64::div_rem(numerator, reduced_divisor), "div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+}
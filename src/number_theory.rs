@@ -0,0 +1,117 @@
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+
+// Extended Euclidean algorithm. Returns (g, s, t) such that a * s + b * t == g
+// The bezout coefficients are bounded by max(a, b), so i128 is plenty for 64-bit inputs
+fn extended_euclid(a: u64, b: u64) -> (u64, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    let (mut old_t, mut t) = (0i128, 1i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let new_r = old_r - quotient * r;
+        old_r = r;
+        r = new_r;
+
+        let new_s = old_s - quotient as i128 * s;
+        old_s = s;
+        s = new_s;
+
+        let new_t = old_t - quotient as i128 * t;
+        old_t = t;
+        t = new_t;
+    }
+    (old_r, old_s, old_t)
+}
+
+macro_rules! number_theory_impl {
+    ($struct_name:ident, $primitive_type:ident, $signed_type:ident) => (
+        impl $struct_name {
+            /// Computes the greatest common divisor of `a` and this divisor, along with a pair of Bézout coefficients.
+            /// Returns `(g, x, y)` such that `a * x + divisor * y == g`.
+            ///
+            /// The first step of the Euclidean algorithm divides `a` by this divisor, so it uses strength-reduced division.
+            #[inline]
+            pub fn extended_gcd(&self, a: $primitive_type) -> ($primitive_type, $signed_type, $signed_type) {
+                let (quotient, remainder) = $struct_name::div_rem(a, *self);
+
+                // g = divisor * s + remainder * t = divisor * s + (a - quotient * divisor) * t = a * t + divisor * (s - quotient * t)
+                // the results are the coefficients extended euclid would have computed for (a, divisor), so they're bounded by max(a, divisor) / 2, and fit in the signed type
+                let (gcd, s, t) = extended_euclid(self.divisor.get() as u64, remainder as u64);
+                let x = t;
+                let y = s - quotient as i128 * t;
+                (gcd as $primitive_type, x as $signed_type, y as $signed_type)
+            }
+
+            /// Solves the linear congruence `a * x ≡ b (mod divisor)`.
+            ///
+            /// Returns `Some((x0, step))`, where the solutions are exactly the values `x0 + k * step` for any integer `k`, and `x0 < step`.
+            /// If `a` and the divisor aren't coprime, there are either several solutions per divisor or none at all.
+            /// Returns `None` if there's no solution.
+            #[inline]
+            pub fn solve_congruence(&self, a: $primitive_type, b: $primitive_type) -> Option<($primitive_type, $primitive_type)> {
+                let (gcd, x, _) = self.extended_gcd(a);
+                if b % gcd != 0 {
+                    return None;
+                }
+
+                // a * x ≡ gcd (mod divisor), so a * x * (b / gcd) ≡ b (mod divisor). All of the solutions are congruent mod (divisor / gcd)
+                let step = (self.divisor.get() / gcd) as u128;
+                let scaled_b = (b / gcd) as u128 % step;
+                let x_positive = (x as i128).rem_euclid(step as i128) as u128;
+                let x0 = (x_positive * scaled_b) % step;
+                Some((x0 as $primitive_type, step as $primitive_type))
+            }
+        }
+    )
+}
+
+number_theory_impl!(StrengthReducedU8, u8, i8);
+number_theory_impl!(StrengthReducedU16, u16, i16);
+number_theory_impl!(StrengthReducedU32, u32, i32);
+number_theory_impl!(StrengthReducedU64, u64, i64);
+number_theory_impl!(StrengthReducedUsize, usize, isize);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! number_theory_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let values = [0, 1, 2, 3, 4, 6, 7, 12, 15, 16, 18, 35, 100, 105, max / 2, max - 2, max - 1, max];
+
+                for &divisor in &values[1..] {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &a in &values {
+                        let (gcd, x, y) = reduced_divisor.extended_gcd(a);
+                        assert_eq!(a as i128 * x as i128 + divisor as i128 * y as i128, gcd as i128, "bezout identity failed for a: {}, divisor: {}", a, divisor);
+                        assert_eq!(a % gcd, 0, "gcd doesn't divide a: {}, divisor: {}", a, divisor);
+                        assert_eq!(divisor % gcd, 0, "gcd doesn't divide divisor: {}, divisor: {}", a, divisor);
+
+                        for &b in &values {
+                            let expected_count = (0..divisor.min(200)).filter(|&x| (a as u128 * x as u128) % divisor as u128 == b as u128 % divisor as u128).count();
+                            match reduced_divisor.solve_congruence(a, b) {
+                                Some((x0, step)) => {
+                                    assert!(x0 < step);
+                                    assert_eq!((a as u128 * x0 as u128) % divisor as u128, b as u128 % divisor as u128, "wrong solution for {} * x = {} mod {}", a, b, divisor);
+                                    assert_eq!((a as u128 * ((x0 as u128 + step as u128) % divisor as u128)) % divisor as u128, b as u128 % divisor as u128, "wrong step for {} * x = {} mod {}", a, b, divisor);
+                                    assert_eq!(divisor % step, 0);
+                                }
+                                None => assert_eq!(expected_count, 0, "missed a solution for {} * x = {} mod {}", a, b, divisor),
+                            }
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    number_theory_test!(test_number_theory_u8, StrengthReducedU8, u8);
+    number_theory_test!(test_number_theory_u16, StrengthReducedU16, u16);
+    number_theory_test!(test_number_theory_u32, StrengthReducedU32, u32);
+    number_theory_test!(test_number_theory_u64, StrengthReducedU64, u64);
+    number_theory_test!(test_number_theory_usize, StrengthReducedUsize, usize);
+}
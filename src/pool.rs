@@ -0,0 +1,172 @@
+use alloc::vec::Vec;
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedI128;
+
+/// A bounded cache of recently used divisor instances.
+///
+/// For workloads where the set of divisors is small but isn't known up front, this avoids recomputing the same divisor instance over and over.
+/// When the pool is full, the least recently used divisor is evicted.
+///
+/// Lookups are a linear scan, so this is intended for pools of a few dozen divisors at most.
+///
+/// # Example:
+/// ```
+/// use strength_reduce::{DivisorPool, StrengthReducedU64};
+///
+/// let mut pool: DivisorPool<StrengthReducedU64> = DivisorPool::new(8);
+/// for &(numerator, divisor) in &[(100u64, 7u64), (200, 7), (300, 11)] {
+///     assert_eq!(numerator / divisor, numerator / pool.get(divisor));
+/// }
+/// assert_eq!(pool.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DivisorPool<T> {
+    // ordered from most recently used to least recently used
+    entries: Vec<T>,
+    capacity: usize,
+}
+impl<T> DivisorPool<T> {
+    /// Creates a new, empty pool that holds up to `capacity` divisors.
+    ///
+    /// # Panics:
+    /// 
+    /// Panics if `capacity` is 0
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self { entries: Vec::with_capacity(capacity), capacity }
+    }
+
+    /// Returns the maximum number of divisors this pool can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of divisors currently in this pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this pool doesn't contain any divisors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every divisor from this pool.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+macro_rules! pool_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl DivisorPool<$struct_name> {
+            /// Returns a divisor instance for `divisor`, creating it and adding it to the pool if it isn't already there.
+            ///
+            /// # Panics:
+            /// 
+            /// Panics if `divisor` is 0
+            pub fn get(&mut self, divisor: $primitive_type) -> $struct_name {
+                match self.entries.iter().position(|entry| entry.get() == divisor) {
+                    Some(index) => {
+                        self.entries[..=index].rotate_right(1);
+                        self.entries[0]
+                    }
+                    None => {
+                        let reduced = $struct_name::new(divisor);
+                        if self.entries.len() == self.capacity {
+                            self.entries.pop();
+                        }
+                        self.entries.insert(0, reduced);
+                        reduced
+                    }
+                }
+            }
+        }
+    )
+}
+
+pool_impl!(StrengthReducedU8, u8);
+pool_impl!(StrengthReducedU16, u16);
+pool_impl!(StrengthReducedU32, u32);
+pool_impl!(StrengthReducedU64, u64);
+pool_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+pool_impl!(StrengthReducedU128, u128);
+
+pool_impl!(StrengthReducedI8, i8);
+pool_impl!(StrengthReducedI16, i16);
+pool_impl!(StrengthReducedI32, i32);
+pool_impl!(StrengthReducedI64, i64);
+pool_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+pool_impl!(StrengthReducedI128, i128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! pool_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+                let mut pool: DivisorPool<$struct_name> = DivisorPool::new(4);
+
+                for _ in 0..2 {
+                    for &divisor in divisors.iter().chain(divisors.iter().rev()) {
+                        let reduced_divisor = pool.get(divisor);
+                        assert_eq!(divisor, reduced_divisor.get());
+                        assert!(pool.len() <= pool.capacity());
+                        for &numerator in &[0, 1, 99, 100, max / 3, max - 1, max] {
+                            assert_eq!(numerator / divisor, numerator / reduced_divisor, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                            assert_eq!(numerator % divisor, numerator % reduced_divisor, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    pool_test!(test_pool_u8, StrengthReducedU8, u8);
+    pool_test!(test_pool_u16, StrengthReducedU16, u16);
+    pool_test!(test_pool_u32, StrengthReducedU32, u32);
+    pool_test!(test_pool_u64, StrengthReducedU64, u64);
+    pool_test!(test_pool_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    pool_test!(test_pool_u128, StrengthReducedU128, u128);
+    pool_test!(test_pool_i32, StrengthReducedI32, i32);
+
+    #[test]
+    fn test_pool_eviction() {
+        let mut pool: DivisorPool<StrengthReducedU32> = DivisorPool::new(3);
+        pool.get(3);
+        pool.get(5);
+        pool.get(7);
+        assert_eq!(pool.len(), 3);
+
+        // touching 3 makes 5 the least recently used, so inserting 11 should evict it
+        pool.get(3);
+        pool.get(11);
+        let cached: Vec<u32> = pool.entries.iter().map(|entry| entry.get()).collect();
+        assert_eq!(cached, [11, 3, 7]);
+
+        pool.clear();
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pool_zero_capacity() {
+        let _pool: DivisorPool<StrengthReducedU64> = DivisorPool::new(0);
+    }
+}
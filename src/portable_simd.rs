@@ -0,0 +1,258 @@
+use core::ops::{Div, Rem};
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::num::SimdUint;
+use core::simd::{Select, Simd};
+
+use ::constant_time::ConstantTimeInt;
+use ::{StrengthReducedU32, StrengthReducedU64};
+
+// Every lane shares the same divisor, so the branches on the divisor's properties happen once per vector, and each lane only runs the multiplies
+
+impl<const N: usize> Div<StrengthReducedU32> for Simd<u32, N> {
+    type Output = Simd<u32, N>;
+
+    #[inline]
+    fn div(self, rhs: StrengthReducedU32) -> Self::Output {
+        if cfg!(feature = "constant-time") {
+            // same as the scalar version: the power of two multiplier is masked back in, and a divisor of 1 masks the numerator into the quotient
+            let multiplier = rhs.multiplier | (u64::mask(rhs.multiplier == 0) & ((1 << (63 - rhs.shift)) << 1));
+            let numerator: Simd<u64, N> = self.cast();
+            let multiplied_hi = numerator * Simd::splat(multiplier >> 32);
+            let multiplied_lo = (numerator * Simd::splat(multiplier as u32 as u64)) >> Simd::splat(32);
+
+            let quotient: Simd<u32, N> = ((multiplied_hi + multiplied_lo) >> Simd::splat(32)).cast();
+            quotient | (self & Simd::splat(u32::mask(rhs.divisor.get() == 1)))
+        } else if rhs.multiplier == 0 {
+            self >> Simd::splat(rhs.shift as u32)
+        } else if rhs.large {
+            // the quotient is either 0 or 1, so a comparison is enough
+            self.simd_ge(Simd::splat(rhs.divisor.get())).select(Simd::splat(1), Simd::splat(0))
+        } else {
+            // Same as the scalar version: both halves of the multiplier fit in 32 bits, so each product fits in a 64-bit lane
+            let numerator: Simd<u64, N> = self.cast();
+            let multiplied_hi = numerator * Simd::splat(rhs.multiplier >> 32);
+            let multiplied_lo = (numerator * Simd::splat(rhs.multiplier as u32 as u64)) >> Simd::splat(32);
+
+            ((multiplied_hi + multiplied_lo) >> Simd::splat(32)).cast()
+        }
+    }
+}
+
+impl<const N: usize> Rem<StrengthReducedU32> for Simd<u32, N> {
+    type Output = Simd<u32, N>;
+
+    #[inline]
+    fn rem(self, rhs: StrengthReducedU32) -> Self::Output {
+        if cfg!(feature = "constant-time") {
+            let quotient = self / rhs;
+            self - quotient * Simd::splat(rhs.divisor.get())
+        } else if rhs.multiplier == 0 {
+            self & Simd::splat(rhs.divisor.get() - 1)
+        } else {
+            let quotient = self / rhs;
+            self - quotient * Simd::splat(rhs.divisor.get())
+        }
+    }
+}
+
+// There's no 64x64->128 multiply across lanes, so we assemble the upper half of each product from 32-bit limbs, like long_multiplication::multiply_64_by_64_upperbits_limbs
+#[inline]
+fn multiply_64_by_64_upperbits<const N: usize>(a: Simd<u64, N>, b: u64) -> Simd<u64, N> {
+    let low_mask = Simd::splat(u32::MAX as u64);
+    let a_lo = a & low_mask;
+    let a_hi = a >> Simd::splat(32);
+    let b_lo = Simd::splat(b as u32 as u64);
+    let b_hi = Simd::splat(b >> 32);
+
+    // compute the four 32x32 partial products, and carry the middle ones into the upper half
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> Simd::splat(32)) + (lo_hi & low_mask) + (hi_lo & low_mask);
+    hi_hi + (lo_hi >> Simd::splat(32)) + (hi_lo >> Simd::splat(32)) + (middle >> Simd::splat(32))
+}
+
+impl<const N: usize> Div<StrengthReducedU64> for Simd<u64, N> {
+    type Output = Simd<u64, N>;
+
+    #[inline]
+    fn div(self, rhs: StrengthReducedU64) -> Self::Output {
+        let divisor = rhs.get();
+        let (multiplier, shift) = rhs.round_up_parts();
+        if cfg!(feature = "constant-time") {
+            // always take the multiply path, and select the power of two quotient afterwards
+            let upper = multiply_64_by_64_upperbits(self, multiplier);
+            let quotient = (upper + ((self - upper) >> Simd::splat(1))) >> Simd::splat(shift as u64);
+            let power_of_two_quotient = self >> Simd::splat(divisor.trailing_zeros() as u64);
+
+            let mask = Simd::splat(u64::mask(divisor.is_power_of_two()));
+            (power_of_two_quotient & mask) | (quotient & !mask)
+        } else if divisor.is_power_of_two() {
+            self >> Simd::splat(divisor.trailing_zeros() as u64)
+        } else if divisor > u64::MAX / 2 {
+            // the quotient is either 0 or 1, so a comparison is enough
+            self.simd_ge(Simd::splat(divisor)).select(Simd::splat(1), Simd::splat(0))
+        } else {
+            let upper = multiply_64_by_64_upperbits(self, multiplier);
+
+            // adding the implicit top bit's contribution directly could overflow, so average them instead, and shift one bit less
+            (upper + ((self - upper) >> Simd::splat(1))) >> Simd::splat(shift as u64)
+        }
+    }
+}
+
+impl<const N: usize> Rem<StrengthReducedU64> for Simd<u64, N> {
+    type Output = Simd<u64, N>;
+
+    #[inline]
+    fn rem(self, rhs: StrengthReducedU64) -> Self::Output {
+        if !cfg!(feature = "constant-time") && rhs.get().is_power_of_two() {
+            self & Simd::splat(rhs.get() - 1)
+        } else {
+            let quotient = self / rhs;
+            self - quotient * Simd::splat(rhs.get())
+        }
+    }
+}
+
+/// Implements lane-wise unsigned division and modulo of `Simd<u32, N>` via multiplication and shifts, with a different divisor in each lane.
+///
+/// Every lane can need a different path through the scalar version, so there are no branches at all: Like the `constant-time` feature, powers of two
+/// get a multiplier too, and every lane takes the same multiply path.
+#[derive(Clone, Copy, Debug)]
+pub struct StrengthReducedU32xN<const N: usize> {
+    multipliers: Simd<u64, N>,
+    divisors: Simd<u32, N>,
+    // all ones in the lanes where the divisor is 1, whose multiplier would need 65 bits. We leave it at 0, and mask the numerator into the quotient instead
+    numerator_mask: Simd<u32, N>,
+}
+impl<const N: usize> StrengthReducedU32xN<N> {
+    /// Creates a new divisor instance, with a separate divisor for each lane.
+    ///
+    /// This computes a multiplier for each lane separately, so it's even more expensive than `StrengthReducedU32::new()`. Create it outside your loop.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if any lane of `divisors` is 0
+    pub fn new(divisors: Simd<u32, N>) -> Self {
+        let multipliers = divisors.to_array().map(|divisor| {
+            let reduced = StrengthReducedU32::new(divisor);
+            reduced.multiplier | (u64::mask(reduced.multiplier == 0) & ((1 << (63 - reduced.shift)) << 1))
+        });
+
+        Self {
+            multipliers: Simd::from_array(multipliers),
+            divisors,
+            numerator_mask: divisors.simd_eq(Simd::splat(1)).select(Simd::splat(u32::MAX), Simd::splat(0)),
+        }
+    }
+
+    /// Retrieve the divisors used to create this struct
+    #[inline]
+    pub fn get(&self) -> Simd<u32, N> {
+        self.divisors
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl<const N: usize> Div<StrengthReducedU32xN<N>> for Simd<u32, N> {
+    type Output = Simd<u32, N>;
+
+    #[inline]
+    fn div(self, rhs: StrengthReducedU32xN<N>) -> Self::Output {
+        // Same as the scalar version: both halves of the multiplier fit in 32 bits, so each product fits in a 64-bit lane
+        let numerator: Simd<u64, N> = self.cast();
+        let multiplied_hi = numerator * (rhs.multipliers >> Simd::splat(32));
+        let multiplied_lo = (numerator * (rhs.multipliers & Simd::splat(u32::MAX as u64))) >> Simd::splat(32);
+
+        let quotient: Simd<u32, N> = ((multiplied_hi + multiplied_lo) >> Simd::splat(32)).cast();
+        quotient | (self & rhs.numerator_mask)
+    }
+}
+
+#[deny(clippy::integer_division_remainder_used)]
+impl<const N: usize> Rem<StrengthReducedU32xN<N>> for Simd<u32, N> {
+    type Output = Simd<u32, N>;
+
+    #[inline]
+    fn rem(self, rhs: StrengthReducedU32xN<N>) -> Self::Output {
+        let quotient = self / rhs;
+        self - quotient * rhs.divisors
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_simd_u32() {
+        let mut rng = StdRng::seed_from_u64(1062);
+        let max = u32::MAX;
+        let edge_divisors = [1, 2, 3, 4, 5, 6, 7, 10, 641, 1 << 16, (1 << 16) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, max - 1, max];
+        let random_divisors: [u32; 100] = core::array::from_fn(|_| (rng.gen::<u32>() >> rng.gen_range(0..31)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU32::new(divisor);
+            let edge_numerators = Simd::from_array([0, 1, divisor - 1, divisor, divisor.wrapping_add(1), max - max % divisor, max - 1, max]);
+            let random_numerators = Simd::<u32, 16>::from_array(core::array::from_fn(|_| rng.gen()));
+
+            assert_eq!(edge_numerators / Simd::splat(divisor), edge_numerators / reduced_divisor, "Divide failed with divisor: {}", divisor);
+            assert_eq!(edge_numerators % Simd::splat(divisor), edge_numerators % reduced_divisor, "Modulo failed with divisor: {}", divisor);
+            assert_eq!(random_numerators / Simd::splat(divisor), random_numerators / reduced_divisor, "Divide failed with divisor: {}", divisor);
+            assert_eq!(random_numerators % Simd::splat(divisor), random_numerators % reduced_divisor, "Modulo failed with divisor: {}", divisor);
+        }
+    }
+
+    #[test]
+    fn test_simd_u64() {
+        let mut rng = StdRng::seed_from_u64(1063);
+        let max = u64::MAX;
+        let edge_divisors = [1, 2, 3, 4, 5, 6, 7, 10, 641, 1 << 32, (1 << 32) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, max - 1, max];
+        let random_divisors: [u64; 100] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..63)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU64::new(divisor);
+            let edge_numerators = Simd::from_array([0, 1, divisor - 1, divisor, divisor.wrapping_add(1), max - max % divisor, max - 1, max]);
+            let random_numerators = Simd::<u64, 8>::from_array(core::array::from_fn(|_| rng.gen()));
+
+            assert_eq!(edge_numerators / Simd::splat(divisor), edge_numerators / reduced_divisor, "Divide failed with divisor: {}", divisor);
+            assert_eq!(edge_numerators % Simd::splat(divisor), edge_numerators % reduced_divisor, "Modulo failed with divisor: {}", divisor);
+            assert_eq!(random_numerators / Simd::splat(divisor), random_numerators / reduced_divisor, "Divide failed with divisor: {}", divisor);
+            assert_eq!(random_numerators % Simd::splat(divisor), random_numerators % reduced_divisor, "Modulo failed with divisor: {}", divisor);
+        }
+    }
+
+    #[test]
+    fn test_simd_u32_per_lane() {
+        let mut rng = StdRng::seed_from_u64(1068);
+        let max = u32::MAX;
+        let edge_divisors = Simd::from_array([1, 2, 3, 4, 7, 10, 641, 1 << 16, (1 << 16) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, 1 << 31, max - 1, max]);
+        let reduced_edge_divisors = StrengthReducedU32xN::new(edge_divisors);
+        assert_eq!(edge_divisors, reduced_edge_divisors.get());
+
+        for _ in 0..1000 {
+            let random_divisors = Simd::<u32, 16>::from_array(core::array::from_fn(|_| (rng.gen::<u32>() >> rng.gen_range(0..31)).max(1)));
+            let reduced_random_divisors = StrengthReducedU32xN::new(random_divisors);
+
+            for &(divisors, reduced_divisors) in &[(edge_divisors, reduced_edge_divisors), (random_divisors, reduced_random_divisors)] {
+                let random_numerators = Simd::<u32, 16>::from_array(core::array::from_fn(|_| rng.gen()));
+                let edge_numerators = [Simd::splat(0), Simd::splat(max), divisors - Simd::splat(1), divisors, Simd::splat(max) - Simd::splat(max) % divisors];
+
+                for &numerators in edge_numerators.iter().chain(&[random_numerators]) {
+                    assert_eq!(numerators / divisors, numerators / reduced_divisors, "Divide failed with divisors: {:?}", divisors);
+                    assert_eq!(numerators % divisors, numerators % reduced_divisors, "Modulo failed with divisors: {:?}", divisors);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_simd_u32_per_lane_zero() {
+        StrengthReducedU32xN::new(Simd::from_array([3, 0, 5, 7]));
+    }
+}
@@ -0,0 +1,69 @@
+use num_traits::{PrimInt, Unsigned};
+
+use generic::{Reducible, ReducedDivisor};
+
+/// An unsigned `num-traits` integer that has a strength-reduced divisor type.
+///
+/// This is implemented for every type that's `PrimInt + Unsigned + Reducible`, which includes every unsigned primitive, so code that's already generic over `PrimInt`
+/// only needs to swap its bound for this one to opt into strength reduction.
+pub trait ReducibleInt: PrimInt + Unsigned + Reducible {}
+
+impl<T: PrimInt + Unsigned + Reducible> ReducibleInt for T {}
+
+/// Creates a strength-reduced divisor for `divisor`, without naming the concrete `StrengthReduced*` type.
+///
+/// ```
+/// use strength_reduce::{reduce, ReducedDivisor, ReducibleInt};
+///
+/// fn count_digits<T: ReducibleInt>(mut value: T, radix: T) -> u32 {
+///     let radix = reduce(radix);
+///     let mut digits = 1;
+///     while value >= radix.get() {
+///         value = radix.div(value);
+///         digits += 1;
+///     }
+///     digits
+/// }
+///
+/// assert_eq!(count_digits(12345u32, 10), 5);
+/// assert_eq!(count_digits(0xFFFFu64, 16), 4);
+/// ```
+///
+/// # Panics:
+///
+/// Panics if `divisor` is 0
+#[inline]
+pub fn reduce<T: ReducibleInt>(divisor: T) -> impl ReducedDivisor<T> {
+    <T::Reduced as ReducedDivisor<T>>::new(divisor)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn div_rem_prim_int<T: ReducibleInt>(numerator: T, divisor: T) -> (T, T) {
+        reduce(divisor).div_rem(numerator)
+    }
+
+    macro_rules! reduce_test {
+        ($test_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                for &divisor in &[1, 2, 3, 7, 10, max / 2 + 1, max] {
+                    for &numerator in &[0, 1, 9, 10, 11, max / 3, max - 1, max] {
+                        assert_eq!((numerator / divisor, numerator % divisor), div_rem_prim_int(numerator, divisor), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    reduce_test!(test_reduce_u8, u8);
+    reduce_test!(test_reduce_u16, u16);
+    reduce_test!(test_reduce_u32, u32);
+    reduce_test!(test_reduce_u64, u64);
+    reduce_test!(test_reduce_usize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    reduce_test!(test_reduce_u128, u128);
+}
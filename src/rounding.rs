@@ -0,0 +1,202 @@
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+
+/// Rounding conventions for the `div_with_rounding` methods.
+///
+/// For unsigned division, `Trunc`, `Floor`, and `Euclid` are all the same.
+///
+/// Every mode is computed from a single strength-reduced division followed by a cheap correction, so picking a mode at runtime costs the same as hardcoding it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round toward zero, like the primitive `/` operator.
+    Trunc,
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward positive infinity.
+    Ceil,
+    /// Round so that the remainder is nonnegative, like the primitive `div_euclid` method.
+    Euclid,
+    /// Round to the nearest integer, with ties rounded away from zero.
+    NearestTiesAway,
+    /// Round to the nearest integer, with ties rounded to the even neighbor. This is the default IEEE 754 rounding mode.
+    NearestTiesEven,
+}
+
+macro_rules! unsigned_rounding_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl $struct_name {
+            /// Ceiling division. Computes `numerator / divisor`, rounded up.
+            ///
+            /// Unlike the common `(numerator + divisor - 1) / divisor` idiom, this never overflows.
+            #[inline]
+            pub fn div_ceil(&self, numerator: $primitive_type) -> $primitive_type {
+                let (quotient, remainder) = $struct_name::div_rem(numerator, *self);
+                if remainder > 0 { quotient + 1 } else { quotient }
+            }
+
+            /// Rounds `value` up to the nearest multiple of this divisor.
+            /// Returns `None` if the result would overflow the primitive type.
+            #[inline]
+            pub fn checked_next_multiple_of(&self, value: $primitive_type) -> Option<$primitive_type> {
+                let remainder = value % *self;
+                if remainder == 0 {
+                    Some(value)
+                } else {
+                    value.checked_add(self.get() - remainder)
+                }
+            }
+
+            /// Division with a configurable rounding mode. See [`Rounding`] for the available modes.
+            #[inline]
+            pub fn div_with_rounding(&self, numerator: $primitive_type, rounding: Rounding) -> $primitive_type {
+                let (quotient, remainder) = $struct_name::div_rem(numerator, *self);
+                if remainder == 0 {
+                    return quotient;
+                }
+
+                // the remainder is nonzero, so the divisor is at least 2, and the quotient can't be MAX. so computing quotient + 1 will never overflow
+                match rounding {
+                    Rounding::Trunc | Rounding::Floor | Rounding::Euclid => quotient,
+                    Rounding::Ceil => quotient + 1,
+                    Rounding::NearestTiesAway => if remainder >= self.get() - remainder { quotient + 1 } else { quotient },
+                    Rounding::NearestTiesEven => {
+                        let upper_distance = self.get() - remainder;
+                        if remainder > upper_distance || (remainder == upper_distance && quotient % 2 == 1) { quotient + 1 } else { quotient }
+                    }
+                }
+            }
+        }
+    )
+}
+
+// Alignment helpers for offsets and sizes, so we only need them for the types that represent offsets
+macro_rules! align_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl $struct_name {
+            /// Rounds `offset` down to the start of the block that contains it, treating this divisor as the block size.
+            /// Returns `(block_start, offset_within_block)`.
+            #[inline]
+            pub fn align_down(&self, offset: $primitive_type) -> ($primitive_type, $primitive_type) {
+                let remainder = offset % *self;
+                (offset - remainder, remainder)
+            }
+
+            /// Rounds `offset` up to the end of the block that contains it, treating this divisor as the block size. If `offset` is already aligned, it's returned unchanged.
+            /// Returns `(aligned_offset, offset_within_block)`.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if the aligned offset overflows. Use [`checked_next_multiple_of`](#method.checked_next_multiple_of) to handle that case instead.
+            #[inline]
+            pub fn align_up(&self, offset: $primitive_type) -> ($primitive_type, $primitive_type) {
+                let remainder = offset % *self;
+                if remainder == 0 {
+                    (offset, 0)
+                } else {
+                    let aligned = offset.checked_add(self.get() - remainder).expect("attempt to align with overflow");
+                    (aligned, remainder)
+                }
+            }
+        }
+    )
+}
+
+align_impl!(StrengthReducedU64, u64);
+align_impl!(StrengthReducedUsize, usize);
+
+unsigned_rounding_impl!(StrengthReducedU8, u8);
+unsigned_rounding_impl!(StrengthReducedU16, u16);
+unsigned_rounding_impl!(StrengthReducedU32, u32);
+unsigned_rounding_impl!(StrengthReducedU64, u64);
+unsigned_rounding_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+unsigned_rounding_impl!(StrengthReducedU128, u128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    macro_rules! unsigned_rounding_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let values = [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,max/2,max/2+1,max-2,max-1,max];
+
+                for &divisor in &values {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in values.iter().chain(&[0]) {
+                        let expected_ceil = numerator / divisor + if numerator % divisor > 0 { 1 } else { 0 };
+                        assert_eq!(expected_ceil, reduced_divisor.div_ceil(numerator), "div_ceil failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                        let quotient = numerator / divisor;
+                        let remainder = numerator % divisor;
+
+                        match reduced_divisor.checked_next_multiple_of(numerator) {
+                            Some(multiple) => {
+                                assert_eq!(multiple % divisor, 0, "checked_next_multiple_of returned a non-multiple for value: {}, divisor: {}", numerator, divisor);
+                                assert!(multiple >= numerator && multiple - numerator < divisor, "checked_next_multiple_of returned the wrong multiple for value: {}, divisor: {}", numerator, divisor);
+                            }
+                            None => assert!(remainder > 0 && max - numerator < divisor - remainder, "checked_next_multiple_of failed with value: {}, divisor: {}", numerator, divisor),
+                        }
+                        let (expected_ties_away, expected_ties_even) = match remainder.cmp(&(divisor - remainder)) {
+                            _ if remainder == 0 => (quotient, quotient),
+                            core::cmp::Ordering::Less => (quotient, quotient),
+                            core::cmp::Ordering::Equal => (quotient + 1, quotient + quotient % 2),
+                            core::cmp::Ordering::Greater => (quotient + 1, quotient + 1),
+                        };
+                        assert_eq!(quotient, reduced_divisor.div_with_rounding(numerator, Rounding::Trunc), "Trunc failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(quotient, reduced_divisor.div_with_rounding(numerator, Rounding::Floor), "Floor failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(quotient, reduced_divisor.div_with_rounding(numerator, Rounding::Euclid), "Euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_ceil, reduced_divisor.div_with_rounding(numerator, Rounding::Ceil), "Ceil failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_ties_away, reduced_divisor.div_with_rounding(numerator, Rounding::NearestTiesAway), "NearestTiesAway failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_ties_even, reduced_divisor.div_with_rounding(numerator, Rounding::NearestTiesEven), "NearestTiesEven failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    macro_rules! align_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let block_sizes = [1, 2, 3, 512, 512 * 3, 4096, max / 3, max - 1, max];
+                let offsets = [0, 1, 2, 511, 512, 513, 1535, 1536, 1537, 4095, 4096, max / 3 - 1, max / 3, max / 3 + 1, max / 2, max - 1, max];
+
+                for &block_size in &block_sizes {
+                    let reduced_block_size = $struct_name::new(block_size);
+                    for &offset in &offsets {
+                        let (block_start, offset_within_block) = reduced_block_size.align_down(offset);
+                        assert_eq!(block_start % block_size, 0, "align_down failed with offset: {}, block size: {}", offset, block_size);
+                        assert_eq!(block_start + offset_within_block, offset, "align_down failed with offset: {}, block size: {}", offset, block_size);
+                        assert!(offset_within_block < block_size, "align_down failed with offset: {}, block size: {}", offset, block_size);
+
+                        if let Some(expected_aligned) = reduced_block_size.checked_next_multiple_of(offset) {
+                            assert_eq!((expected_aligned, offset_within_block), reduced_block_size.align_up(offset), "align_up failed with offset: {}, block size: {}", offset, block_size);
+                        }
+                    }
+                }
+            }
+        )
+    }
+
+    align_test!(test_align_u64, StrengthReducedU64, u64);
+    align_test!(test_align_usize, StrengthReducedUsize, usize);
+
+    #[test]
+    #[should_panic]
+    fn test_align_up_overflow() {
+        StrengthReducedU64::new(7).align_up(u64::MAX);
+    }
+
+    unsigned_rounding_test!(test_unsigned_rounding_u8, StrengthReducedU8, u8);
+    unsigned_rounding_test!(test_unsigned_rounding_u16, StrengthReducedU16, u16);
+    unsigned_rounding_test!(test_unsigned_rounding_u32, StrengthReducedU32, u32);
+    unsigned_rounding_test!(test_unsigned_rounding_u64, StrengthReducedU64, u64);
+    unsigned_rounding_test!(test_unsigned_rounding_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    unsigned_rounding_test!(test_unsigned_rounding_u128, StrengthReducedU128, u128);
+}
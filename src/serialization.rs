@@ -0,0 +1,81 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+use ::{DivisorError, LazyStrengthReducedU64, StrengthReducedU64Narrow};
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU128, StrengthReducedI128};
+
+// The multiplier is cheap to recompute and depends on the crate version, so only the divisor goes over the wire. That also means a
+// serialized divisor reads back as a plain integer, so configuration files can spell it as one
+macro_rules! serde_impl {
+    ($struct_name:ident, $primitive_type:ident) => (
+        impl Serialize for $struct_name {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.get().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $struct_name {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let divisor = $primitive_type::deserialize(deserializer)?;
+                $struct_name::try_new(divisor).ok_or_else(|| D::Error::custom(DivisorError::Zero))
+            }
+        }
+    )
+}
+
+serde_impl!(StrengthReducedU8, u8);
+serde_impl!(StrengthReducedU16, u16);
+serde_impl!(StrengthReducedU32, u32);
+serde_impl!(StrengthReducedU64, u64);
+serde_impl!(StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+serde_impl!(StrengthReducedU128, u128);
+serde_impl!(StrengthReducedI8, i8);
+serde_impl!(StrengthReducedI16, i16);
+serde_impl!(StrengthReducedI32, i32);
+serde_impl!(StrengthReducedI64, i64);
+serde_impl!(StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+serde_impl!(StrengthReducedI128, i128);
+serde_impl!(StrengthReducedU64Narrow, u64);
+serde_impl!(LazyStrengthReducedU64, u64);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use serde::de::value::{Error as ValueError, I16Deserializer, U32Deserializer, U64Deserializer};
+    use serde::de::IntoDeserializer;
+
+    #[test]
+    fn test_deserialize() {
+        for &divisor in &[1u32, 2, 3, 7, 1 << 31, u32::MAX] {
+            let deserializer: U32Deserializer<ValueError> = divisor.into_deserializer();
+            let reduced_divisor = StrengthReducedU32::deserialize(deserializer).unwrap();
+            assert_eq!(divisor, reduced_divisor.get());
+            assert_eq!(u32::MAX / divisor, u32::MAX / reduced_divisor);
+        }
+
+        let deserializer: I16Deserializer<ValueError> = (-7i16).into_deserializer();
+        assert_eq!(-7, StrengthReducedI16::deserialize(deserializer).unwrap().get());
+
+        let deserializer: U64Deserializer<ValueError> = 10u64.into_deserializer();
+        assert_eq!(10, LazyStrengthReducedU64::deserialize(deserializer).unwrap().get());
+    }
+
+    #[test]
+    fn test_deserialize_zero() {
+        let deserializer: U32Deserializer<ValueError> = 0u32.into_deserializer();
+        assert!(StrengthReducedU32::deserialize(deserializer).is_err());
+
+        let deserializer: U64Deserializer<ValueError> = 0u64.into_deserializer();
+        assert!(StrengthReducedU64Narrow::deserialize(deserializer).is_err());
+
+        let deserializer: U64Deserializer<ValueError> = 0u64.into_deserializer();
+        assert!(LazyStrengthReducedU64::deserialize(deserializer).is_err());
+    }
+}
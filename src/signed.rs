@@ -0,0 +1,611 @@
+use core::ops::{Div, Rem};
+
+use ::constant_time::ConstantTimeInt;
+use ::Rounding;
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+
+// The signed types are thin wrappers around the unsigned types: we divide the absolute values, then fix up the signs of the results
+macro_rules! strength_reduced_signed {
+    ($struct_name:ident, $primitive_type:ident, $unsigned_struct:ident, $unsigned_type:ident) => (
+        /// Implements signed division and modulo via mutiplication and shifts.
+        ///
+        /// Division truncates toward zero, and the remainder has the same sign as the numerator, exactly like the primitive `/` and `%` operators.
+        ///
+        /// Creating a an instance of this struct is more expensive than a single division, but if the division is repeated,
+        /// this version will be several times faster than naive division.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $struct_name {
+            abs_divisor: $unsigned_struct,
+            divisor: $primitive_type,
+        }
+        impl $struct_name {
+            /// Creates a new divisor instance.
+            ///
+            /// If possible, avoid calling new() from an inner loop: The intended usage is to create an instance of this struct outside the loop, and use it for divison and remainders inside the loop.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `divisor` is 0
+            #[inline]
+            pub fn new(divisor: $primitive_type) -> Self {
+                assert!(divisor != 0, "attempt to create a divisor of zero");
+
+                Self{ abs_divisor: $unsigned_struct::new(divisor.unsigned_abs()), divisor }
+            }
+
+            /// Creates a new divisor instance, or returns `None` if `divisor` is 0.
+            ///
+            /// Use this instead of `new()` when the divisor comes from untrusted input.
+            #[inline]
+            pub fn try_new(divisor: $primitive_type) -> Option<Self> {
+                if divisor == 0 {
+                    None
+                } else {
+                    Some(Self::new(divisor))
+                }
+            }
+
+            /// Creates a new divisor instance without checking that `divisor` is nonzero.
+            ///
+            /// Since the compiler is allowed to assume the divisor is nonzero, the zero branch is removed entirely.
+            ///
+            /// # Safety
+            ///
+            /// `divisor` must not be 0. In debug builds, this is checked with an assertion, but in release builds, a zero divisor is undefined behavior.
+            #[inline]
+            pub unsafe fn new_unchecked(divisor: $primitive_type) -> Self {
+                debug_assert!(divisor != 0);
+                core::hint::assert_unchecked(divisor != 0);
+                Self{ abs_divisor: $unsigned_struct::new_unchecked(divisor.unsigned_abs()), divisor }
+            }
+
+            /// Simultaneous truncated integer division and modulus.
+            /// Returns `(quotient, remainder)`.
+            ///
+            /// To call this as a method on the divisor instead, as `divisor.div_rem(numerator)`, import [`ReducedDivisor`](crate::ReducedDivisor).
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the quotient overflows
+            #[inline]
+            #[deny(clippy::integer_division_remainder_used)]
+            pub fn div_rem(numerator: $primitive_type, denom: Self) -> ($primitive_type, $primitive_type) {
+                if numerator == $primitive_type::MIN && denom.divisor == -1 {
+                    panic!("attempt to divide with overflow");
+                }
+
+                let (abs_quotient, abs_remainder) = $unsigned_struct::div_rem(Self::abs_numerator(numerator), denom.abs_divisor);
+                (Self::apply_quotient_sign(abs_quotient as $primitive_type, numerator, denom.divisor), Self::apply_remainder_sign(abs_remainder as $primitive_type, numerator))
+            }
+
+            /// Divides `numerator` by this divisor. This is the same as the `/` operator, as a named method for generic code and macros where operators are awkward.
+            #[inline]
+            pub fn divide(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator / *self
+            }
+
+            /// Returns the remainder of dividing `numerator` by this divisor. This is the same as the `%` operator.
+            #[inline]
+            pub fn remainder(&self, numerator: $primitive_type) -> $primitive_type {
+                numerator % *self
+            }
+
+            /// Checked truncated division. Returns `None` if the quotient overflows, which only happens if `numerator` is the minimum value of its type and the divisor is -1.
+            #[inline]
+            pub fn checked_div(&self, numerator: $primitive_type) -> Option<$primitive_type> {
+                if numerator == $primitive_type::MIN && self.divisor == -1 {
+                    None
+                } else {
+                    Some(numerator / *self)
+                }
+            }
+
+            /// Checked truncated remainder. Returns `None` if the division overflows, which only happens if `numerator` is the minimum value of its type and the divisor is -1.
+            #[inline]
+            pub fn checked_rem(&self, numerator: $primitive_type) -> Option<$primitive_type> {
+                if numerator == $primitive_type::MIN && self.divisor == -1 {
+                    None
+                } else {
+                    Some(numerator % *self)
+                }
+            }
+
+            /// Checked simultaneous truncated integer division and modulus. Returns `Some((quotient, remainder))`, or `None` if the division overflows,
+            /// which only happens if `numerator` is the minimum value of its type and the divisor is -1.
+            #[inline]
+            pub fn checked_div_rem(&self, numerator: $primitive_type) -> Option<($primitive_type, $primitive_type)> {
+                if numerator == $primitive_type::MIN && self.divisor == -1 {
+                    None
+                } else {
+                    Some(Self::div_rem(numerator, *self))
+                }
+            }
+
+            /// Euclidean division. Computes the quotient `q` such that `numerator == q * divisor + r` with `0 <= r < |divisor|`,
+            /// matching the primitive `div_euclid` method.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the quotient overflows
+            #[inline]
+            pub fn div_euclid(&self, numerator: $primitive_type) -> $primitive_type {
+                let (quotient, remainder) = Self::div_rem(numerator, *self);
+                if remainder < 0 {
+                    if self.divisor > 0 { quotient - 1 } else { quotient + 1 }
+                } else {
+                    quotient
+                }
+            }
+
+            /// Euclidean remainder. The result is always in the range `0..|divisor|`, matching the primitive `rem_euclid` method.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the intermediate truncated division overflows
+            #[inline]
+            pub fn rem_euclid(&self, numerator: $primitive_type) -> $primitive_type {
+                let remainder = numerator % *self;
+                if remainder < 0 {
+                    // the absolute value of the divisor is MIN if the divisor is MIN, but the wrapping add still gets us the right answer
+                    remainder.wrapping_add(self.divisor.wrapping_abs())
+                } else {
+                    remainder
+                }
+            }
+
+            /// Floored division. The quotient is rounded toward negative infinity, like Python's `//` operator.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the quotient overflows
+            #[inline]
+            pub fn div_floor(&self, numerator: $primitive_type) -> $primitive_type {
+                let (quotient, remainder) = Self::div_rem(numerator, *self);
+                if remainder != 0 && (remainder ^ self.divisor) < 0 { quotient - 1 } else { quotient }
+            }
+
+            /// Floored modulus. The result has the same sign as the divisor, like Python's `%` operator.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the intermediate truncated division overflows
+            #[inline]
+            pub fn mod_floor(&self, numerator: $primitive_type) -> $primitive_type {
+                let remainder = numerator % *self;
+                if remainder != 0 && (remainder ^ self.divisor) < 0 { remainder + self.divisor } else { remainder }
+            }
+
+            /// Division with a configurable rounding mode. See [`Rounding`] for the available modes.
+            ///
+            /// # Panics:
+            ///
+            /// Panics if `numerator` is the minimum value of its type and the divisor is -1, because the quotient overflows
+            #[inline]
+            pub fn div_with_rounding(&self, numerator: $primitive_type, rounding: Rounding) -> $primitive_type {
+                let (quotient, remainder) = Self::div_rem(numerator, *self);
+                if remainder == 0 {
+                    return quotient;
+                }
+
+                // The remainder is nonzero, so the exact quotient lies strictly between `quotient` and the next integer away from zero.
+                // If the numerator and divisor have the same sign, that's quotient + 1, otherwise it's quotient - 1
+                let positive = (numerator ^ self.divisor) >= 0;
+                let away_from_zero = if positive { quotient + 1 } else { quotient - 1 };
+                match rounding {
+                    Rounding::Trunc => quotient,
+                    Rounding::Floor => if positive { quotient } else { away_from_zero },
+                    Rounding::Ceil => if positive { away_from_zero } else { quotient },
+                    Rounding::Euclid => if remainder < 0 { if self.divisor > 0 { quotient - 1 } else { quotient + 1 } } else { quotient },
+                    Rounding::NearestTiesAway => {
+                        let abs_remainder = remainder.unsigned_abs();
+                        if abs_remainder >= self.divisor.unsigned_abs() - abs_remainder { away_from_zero } else { quotient }
+                    }
+                    Rounding::NearestTiesEven => {
+                        let abs_remainder = remainder.unsigned_abs();
+                        let upper_distance = self.divisor.unsigned_abs() - abs_remainder;
+                        if abs_remainder > upper_distance || (abs_remainder == upper_distance && quotient % 2 != 0) { away_from_zero } else { quotient }
+                    }
+                }
+            }
+
+            /// Retrieve the value used to create this struct
+            #[inline]
+            pub fn get(&self) -> $primitive_type {
+                self.divisor
+            }
+
+            /// Returns the unsigned divisor for the absolute value of this divisor.
+            ///
+            /// Division and remainders divide the numerator's absolute value by it, and then fix the signs, so its `multiplier()`, `shift()`, and `is_shift_only()` are the constants this divisor uses.
+            #[inline]
+            pub fn abs_divisor(&self) -> $unsigned_struct {
+                self.abs_divisor
+            }
+
+            // Same as unsigned_abs, except that with the constant-time feature, it negates with a mask instead of branching on the sign
+            #[inline]
+            fn abs_numerator(numerator: $primitive_type) -> $unsigned_type {
+                if cfg!(feature = "constant-time") {
+                    numerator.negate_if(numerator < 0) as $unsigned_type
+                } else {
+                    numerator.unsigned_abs()
+                }
+            }
+
+            // The quotient is negative if exactly one of the inputs is negative. abs_quotient might be MIN (if the numerator is MIN), which negates to itself, which is what we want
+            #[inline]
+            fn apply_quotient_sign(abs_quotient: $primitive_type, numerator: $primitive_type, divisor: $primitive_type) -> $primitive_type {
+                if cfg!(feature = "constant-time") {
+                    abs_quotient.negate_if((numerator ^ divisor) < 0)
+                } else if (numerator ^ divisor) < 0 {
+                    abs_quotient.wrapping_neg()
+                } else {
+                    abs_quotient
+                }
+            }
+
+            // The remainder has the same sign as the numerator. abs_remainder is always smaller than abs_divisor, so it always fits
+            #[inline]
+            fn apply_remainder_sign(abs_remainder: $primitive_type, numerator: $primitive_type) -> $primitive_type {
+                if cfg!(feature = "constant-time") {
+                    abs_remainder.negate_if(numerator < 0)
+                } else if numerator < 0 {
+                    -abs_remainder
+                } else {
+                    abs_remainder
+                }
+            }
+        }
+
+        impl Default for $struct_name {
+            /// Creates a divisor of 1.
+            #[inline]
+            fn default() -> Self {
+                Self::new(1)
+            }
+        }
+
+        #[deny(clippy::integer_division_remainder_used)]
+        impl Div<$struct_name> for $primitive_type {
+            type Output = $primitive_type;
+
+            #[inline]
+            fn div(self, rhs: $struct_name) -> Self::Output {
+                if self == $primitive_type::MIN && rhs.divisor == -1 {
+                    panic!("attempt to divide with overflow");
+                }
+
+                let abs_quotient = $struct_name::abs_numerator(self) / rhs.abs_divisor;
+                $struct_name::apply_quotient_sign(abs_quotient as $primitive_type, self, rhs.divisor)
+            }
+        }
+
+        #[deny(clippy::integer_division_remainder_used)]
+        impl Rem<$struct_name> for $primitive_type {
+            type Output = $primitive_type;
+
+            #[inline]
+            fn rem(self, rhs: $struct_name) -> Self::Output {
+                if self == $primitive_type::MIN && rhs.divisor == -1 {
+                    panic!("attempt to calculate the remainder with overflow");
+                }
+
+                let abs_remainder = $struct_name::abs_numerator(self) % rhs.abs_divisor;
+                $struct_name::apply_remainder_sign(abs_remainder as $primitive_type, self)
+            }
+        }
+
+        state_bytes_impl!($struct_name, $primitive_type, [abs_divisor: $unsigned_struct]);
+    )
+}
+
+strength_reduced_signed!(StrengthReducedI8, i8, StrengthReducedU8, u8);
+strength_reduced_signed!(StrengthReducedI16, i16, StrengthReducedU16, u16);
+strength_reduced_signed!(StrengthReducedI32, i32, StrengthReducedU32, u32);
+strength_reduced_signed!(StrengthReducedI64, i64, StrengthReducedU64, u64);
+strength_reduced_signed!(StrengthReducedIsize, isize, StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+strength_reduced_signed!(StrengthReducedI128, i128, StrengthReducedU128, u128);
+
+// Signed numerators can also be divided by the unsigned divisor types, for when the divisor is known to be positive
+// These are methods instead of Div/Rem impls, because a second Div impl for a divisor type breaks type inference for integer literals, like `100 / divisor`
+macro_rules! signed_numerator_impl {
+    ($primitive_type:ident, $unsigned_struct:ident) => (
+        impl $unsigned_struct {
+            /// Truncated division of a signed numerator by this divisor. The quotient is rounded toward zero, just like the primitive `/` operator.
+            #[inline]
+            pub fn div_signed(&self, numerator: $primitive_type) -> $primitive_type {
+                // abs_quotient is at most |MIN|, which negates to MIN, so the wrapping negation is always correct
+                let abs_quotient = (numerator.unsigned_abs() / *self) as $primitive_type;
+                if numerator < 0 { abs_quotient.wrapping_neg() } else { abs_quotient }
+            }
+
+            /// Truncated remainder of a signed numerator and this divisor. The remainder has the same sign as the numerator, just like the primitive `%` operator.
+            #[inline]
+            pub fn rem_signed(&self, numerator: $primitive_type) -> $primitive_type {
+                // if the divisor is larger than |MIN|, the remainder of MIN is |MIN|, which negates to MIN, so the wrapping negation is always correct
+                let abs_remainder = (numerator.unsigned_abs() % *self) as $primitive_type;
+                if numerator < 0 { abs_remainder.wrapping_neg() } else { abs_remainder }
+            }
+        }
+    )
+}
+
+signed_numerator_impl!(i8, StrengthReducedU8);
+signed_numerator_impl!(i16, StrengthReducedU16);
+signed_numerator_impl!(i32, StrengthReducedU32);
+signed_numerator_impl!(i64, StrengthReducedU64);
+signed_numerator_impl!(isize, StrengthReducedUsize);
+#[cfg(not(strength_reduce_no_u128))]
+signed_numerator_impl!(i128, StrengthReducedU128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use ::ReducedDivisor;
+
+    // the signed counterpart of test_divisors: the small divisors of either sign, and the values near both ends of the range
+    macro_rules! signed_test_divisors {
+        ($primitive_type:ident) => ({
+            let (min, max) = ($primitive_type::MIN, $primitive_type::MAX);
+            [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,max-1,max,-1,-2,-3,-4,-5,-6,-7,-8,-9,-10,min+1,min]
+        })
+    }
+
+    macro_rules! signed_reduction_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let min = $primitive_type::MIN;
+                let divisors = signed_test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    assert_eq!(divisor, reduced_divisor.get());
+                    for &numerator in [0].iter().chain(&divisors) {
+                        if numerator == min && divisor == -1 {
+                            continue;
+                        }
+                        let expected_div = numerator / divisor;
+                        let expected_rem = numerator % divisor;
+
+                        let reduced_div = numerator / reduced_divisor;
+                        assert_eq!(expected_div, reduced_div, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        let reduced_rem = numerator % reduced_divisor;
+                        assert_eq!(expected_rem, reduced_rem, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                        let (reduced_combined_div, reduced_combined_rem) = $struct_name::div_rem(numerator, reduced_divisor);
+                        assert_eq!(expected_div, reduced_combined_div, "div_rem divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_rem, reduced_combined_rem, "div_rem modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    signed_reduction_test!(test_strength_reduced_i8, StrengthReducedI8, i8);
+    signed_reduction_test!(test_strength_reduced_i16, StrengthReducedI16, i16);
+    signed_reduction_test!(test_strength_reduced_i32, StrengthReducedI32, i32);
+    signed_reduction_test!(test_strength_reduced_i64, StrengthReducedI64, i64);
+    signed_reduction_test!(test_strength_reduced_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_reduction_test!(test_strength_reduced_i128, StrengthReducedI128, i128);
+
+    macro_rules! signed_constructor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                assert!($struct_name::try_new(0).is_none());
+                assert_eq!(1, $struct_name::default().get());
+
+                for &divisor in &signed_test_divisors!($primitive_type) {
+                    assert_eq!(Some(divisor), $struct_name::try_new(divisor).map(|d| d.get()));
+                    assert_eq!(divisor, unsafe { $struct_name::new_unchecked(divisor) }.get());
+                }
+            }
+        )
+    }
+
+    signed_constructor_test!(test_signed_constructors_i8, StrengthReducedI8, i8);
+    signed_constructor_test!(test_signed_constructors_i16, StrengthReducedI16, i16);
+    signed_constructor_test!(test_signed_constructors_i32, StrengthReducedI32, i32);
+    signed_constructor_test!(test_signed_constructors_i64, StrengthReducedI64, i64);
+    signed_constructor_test!(test_signed_constructors_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_constructor_test!(test_signed_constructors_i128, StrengthReducedI128, i128);
+
+    macro_rules! signed_checked_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let divisors = signed_test_divisors!($primitive_type);
+
+                // unlike the other tests, this includes MIN / -1, which the checked operations have to catch
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in [0].iter().chain(&divisors) {
+                        assert_eq!(numerator.checked_div(divisor), reduced_divisor.checked_div(numerator), "checked_div failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator.checked_rem(divisor), reduced_divisor.checked_rem(numerator), "checked_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                        let expected_div_rem = numerator.checked_div(divisor).map(|quotient| (quotient, numerator % divisor));
+                        assert_eq!(expected_div_rem, reduced_divisor.checked_div_rem(numerator), "checked_div_rem failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    signed_checked_test!(test_signed_checked_i8, StrengthReducedI8, i8);
+    signed_checked_test!(test_signed_checked_i16, StrengthReducedI16, i16);
+    signed_checked_test!(test_signed_checked_i32, StrengthReducedI32, i32);
+    signed_checked_test!(test_signed_checked_i64, StrengthReducedI64, i64);
+    signed_checked_test!(test_signed_checked_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_checked_test!(test_signed_checked_i128, StrengthReducedI128, i128);
+
+    macro_rules! signed_method_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let min = $primitive_type::MIN;
+                let divisors = signed_test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in [0].iter().chain(&divisors) {
+                        if numerator == min && divisor == -1 {
+                            continue;
+                        }
+                        let expected_div = numerator / divisor;
+                        let expected_rem = numerator % divisor;
+                        assert_eq!(expected_div, reduced_divisor.divide(numerator), "divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_rem, reduced_divisor.remainder(numerator), "remainder failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!((expected_div, expected_rem), reduced_divisor.div_rem(numerator), "div_rem method failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    signed_method_test!(test_signed_methods_i8, StrengthReducedI8, i8);
+    signed_method_test!(test_signed_methods_i16, StrengthReducedI16, i16);
+    signed_method_test!(test_signed_methods_i32, StrengthReducedI32, i32);
+    signed_method_test!(test_signed_methods_i64, StrengthReducedI64, i64);
+    signed_method_test!(test_signed_methods_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_method_test!(test_signed_methods_i128, StrengthReducedI128, i128);
+
+    macro_rules! euclid_floor_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let min = $primitive_type::MIN;
+                let divisors = signed_test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in [0].iter().chain(&divisors) {
+                        if numerator == min && divisor == -1 {
+                            continue;
+                        }
+                        assert_eq!(numerator.div_euclid(divisor), reduced_divisor.div_euclid(numerator), "div_euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator.rem_euclid(divisor), reduced_divisor.rem_euclid(numerator), "rem_euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                        let (quotient, remainder) = (numerator / divisor, numerator % divisor);
+                        let expected_floor = if remainder != 0 && (remainder < 0) != (divisor < 0) { quotient - 1 } else { quotient };
+                        assert_eq!(expected_floor, reduced_divisor.div_floor(numerator), "div_floor failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator.wrapping_sub(expected_floor.wrapping_mul(divisor)), reduced_divisor.mod_floor(numerator), "mod_floor failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    euclid_floor_test!(test_euclid_floor_i8, StrengthReducedI8, i8);
+    euclid_floor_test!(test_euclid_floor_i16, StrengthReducedI16, i16);
+    euclid_floor_test!(test_euclid_floor_i32, StrengthReducedI32, i32);
+    euclid_floor_test!(test_euclid_floor_i64, StrengthReducedI64, i64);
+    euclid_floor_test!(test_euclid_floor_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    euclid_floor_test!(test_euclid_floor_i128, StrengthReducedI128, i128);
+
+    macro_rules! signed_numerator_test {
+        ($test_name:ident, $unsigned_struct:ident, $primitive_type:ident, $unsigned_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let max = $primitive_type::MAX;
+                let min = $primitive_type::MIN;
+                let unsigned_max = $unsigned_type::MAX;
+                let divisors = [1,2,3,4,5,6,7,8,9,10,max as $unsigned_type - 1,max as $unsigned_type,min.unsigned_abs(),min.unsigned_abs() + 1,unsigned_max - 1,unsigned_max];
+                let numerators = [0,1,2,3,4,5,6,7,8,9,10,max-1,max,-1,-2,-3,-4,-5,-6,-7,-8,-9,-10,min+1,min];
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $unsigned_struct::new(divisor);
+                    for &numerator in &numerators {
+                        let abs_quotient = (numerator.unsigned_abs() / divisor) as $primitive_type;
+                        let abs_remainder = (numerator.unsigned_abs() % divisor) as $primitive_type;
+                        let expected_div = if numerator < 0 { abs_quotient.wrapping_neg() } else { abs_quotient };
+                        let expected_rem = if numerator < 0 { abs_remainder.wrapping_neg() } else { abs_remainder };
+                        if divisor <= max as $unsigned_type {
+                            assert_eq!(expected_div, numerator / divisor as $primitive_type);
+                            assert_eq!(expected_rem, numerator % divisor as $primitive_type);
+                        }
+
+                        assert_eq!(expected_div, reduced_divisor.div_signed(numerator), "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_rem, reduced_divisor.rem_signed(numerator), "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    signed_numerator_test!(test_signed_numerator_u8, StrengthReducedU8, i8, u8);
+    signed_numerator_test!(test_signed_numerator_u16, StrengthReducedU16, i16, u16);
+    signed_numerator_test!(test_signed_numerator_u32, StrengthReducedU32, i32, u32);
+    signed_numerator_test!(test_signed_numerator_u64, StrengthReducedU64, i64, u64);
+    signed_numerator_test!(test_signed_numerator_usize, StrengthReducedUsize, isize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_numerator_test!(test_signed_numerator_u128, StrengthReducedU128, i128, u128);
+
+    macro_rules! signed_rounding_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let min = $primitive_type::MIN;
+                let divisors = signed_test_divisors!($primitive_type);
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in [0].iter().chain(&divisors) {
+                        if numerator == min && divisor == -1 {
+                            continue;
+                        }
+
+                        // compute the expected results from the truncated quotient and remainder, so that nothing needs a wider type, even for i128.
+                        // the remainder has the numerator's sign, so a nonzero remainder means the exact quotient is negative exactly when the signs differ
+                        let quotient = numerator / divisor;
+                        let remainder = numerator % divisor;
+                        let is_negative = (numerator < 0) != (divisor < 0);
+                        let expected_floor = if remainder != 0 && is_negative { quotient - 1 } else { quotient };
+                        let expected_ceil = if remainder != 0 && !is_negative { quotient + 1 } else { quotient };
+                        let away_from_zero = if is_negative { expected_floor } else { expected_ceil };
+
+                        // the remainder is smaller than the divisor, so twice its magnitude always fits in the unsigned type
+                        let twice_remainder = 2 * remainder.unsigned_abs();
+                        let expected_nearest = if twice_remainder >= divisor.unsigned_abs() { away_from_zero } else { quotient };
+                        let is_tie = twice_remainder == divisor.unsigned_abs();
+                        let expected_even = if !is_tie { expected_nearest } else if expected_floor % 2 == 0 { expected_floor } else { expected_ceil };
+
+                        assert_eq!(quotient, reduced_divisor.div_with_rounding(numerator, Rounding::Trunc), "Trunc failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_floor, reduced_divisor.div_with_rounding(numerator, Rounding::Floor), "Floor failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_ceil, reduced_divisor.div_with_rounding(numerator, Rounding::Ceil), "Ceil failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(numerator.div_euclid(divisor), reduced_divisor.div_with_rounding(numerator, Rounding::Euclid), "Euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_nearest, reduced_divisor.div_with_rounding(numerator, Rounding::NearestTiesAway), "NearestTiesAway failed with numerator: {}, divisor: {}", numerator, divisor);
+                        assert_eq!(expected_even, reduced_divisor.div_with_rounding(numerator, Rounding::NearestTiesEven), "NearestTiesEven failed with numerator: {}, divisor: {}", numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    signed_rounding_test!(test_signed_rounding_i8, StrengthReducedI8, i8);
+    signed_rounding_test!(test_signed_rounding_i16, StrengthReducedI16, i16);
+    signed_rounding_test!(test_signed_rounding_i32, StrengthReducedI32, i32);
+    signed_rounding_test!(test_signed_rounding_i64, StrengthReducedI64, i64);
+    signed_rounding_test!(test_signed_rounding_isize, StrengthReducedIsize, isize);
+    #[cfg(not(strength_reduce_no_u128))]
+    signed_rounding_test!(test_signed_rounding_i128, StrengthReducedI128, i128);
+
+    #[test]
+    #[should_panic(expected = "attempt to create a divisor of zero")]
+    fn test_strength_reduced_i32_zero() {
+        StrengthReducedI32::new(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_strength_reduced_i64_overflow() {
+        let _ = i64::MIN / StrengthReducedI64::new(-1);
+    }
+}
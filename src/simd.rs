@@ -0,0 +1,1619 @@
+//! Slice kernels that divide many numerators by the same divisor, using explicit SIMD instructions when the CPU supports them.
+//!
+//! On x86 and x86_64, the u8 and u16 kernels use SSE2, the u32 kernels use AVX2, and with the `avx512` feature, the u64 kernels use AVX-512. On aarch64, the u8, u16, and u32 kernels use NEON.
+//!
+//! With the `std` feature, the instruction set is detected at runtime, so a single binary gets the fast path on every CPU that has it.
+//! Without `std`, the SIMD kernels are only used if the instruction set is enabled at compile time, eg with `-C target-cpu=native`.
+//! Either way, CPUs without it fall back to a scalar loop over the `Div` and `Rem` impls.
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64};
+#[cfg(not(strength_reduce_no_u128))]
+use ::StrengthReducedU128;
+#[cfg(target_pointer_width = "16")]
+use ::StrengthReducedUsize;
+
+/// Divides every element of `values` by `divisor`, in place.
+///
+/// On x86 and x86_64 CPUs with SSE2, and aarch64 CPUs with NEON, this divides 16 elements at a time, which is a good fit for pixel buffers.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2
+            unsafe { sse2::div_slice_u8(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::div_slice_u8(values, divisor) };
+            return;
+        }
+    }
+
+    div_slice_u8_scalar(values, divisor);
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`.
+///
+/// On x86 and x86_64 CPUs with SSE2, and aarch64 CPUs with NEON, this computes 16 remainders at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn rem_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2
+            unsafe { sse2::rem_slice_u8(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::rem_slice_u8(values, divisor) };
+            return;
+        }
+    }
+
+    rem_slice_u8_scalar(values, divisor);
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`.
+///
+/// This computes each quotient once, and derives the remainder from it, so it's cheaper than dividing and taking the remainder separately. It uses the same SSE2 and NEON kernels as `div_slice_u8`.
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_rem_slice_u8(input: &[u8], quotients: &mut [u8], remainders: &mut [u8], divisor: StrengthReducedU8) {
+    assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+    assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2, and that the slices have the same length
+            unsafe { sse2::div_rem_slice_u8(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON, and that the slices have the same length
+            unsafe { neon::div_rem_slice_u8(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+
+    div_rem_slice_u8_scalar(input, quotients, remainders, divisor);
+}
+
+/// Divides every element of `values` by `divisor`, in place.
+///
+/// On x86 and x86_64 CPUs with SSE2, and aarch64 CPUs with NEON, this divides 8 elements at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2
+            unsafe { sse2::div_slice_u16(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::div_slice_u16(values, divisor) };
+            return;
+        }
+    }
+
+    div_slice_u16_scalar(values, divisor);
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`.
+///
+/// On x86 and x86_64 CPUs with SSE2, and aarch64 CPUs with NEON, this computes 8 remainders at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn rem_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2
+            unsafe { sse2::rem_slice_u16(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::rem_slice_u16(values, divisor) };
+            return;
+        }
+    }
+
+    rem_slice_u16_scalar(values, divisor);
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`.
+///
+/// This computes each quotient once, and derives the remainder from it, so it's cheaper than dividing and taking the remainder separately. It uses the same SSE2 and NEON kernels as `div_slice_u16`.
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_rem_slice_u16(input: &[u16], quotients: &mut [u16], remainders: &mut [u16], divisor: StrengthReducedU16) {
+    assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+    assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_sse2() {
+            // Safety: we just checked that the CPU supports SSE2, and that the slices have the same length
+            unsafe { sse2::div_rem_slice_u16(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON, and that the slices have the same length
+            unsafe { neon::div_rem_slice_u16(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+
+    div_rem_slice_u16_scalar(input, quotients, remainders, divisor);
+}
+
+/// Divides every element of `values` by `divisor`, in place.
+///
+/// On x86 and x86_64 CPUs with AVX2, this divides 8 elements at a time, and on aarch64 CPUs with NEON, it divides 4 elements at a time.
+/// The divisor's properties are checked once per call instead of once per element, so the inner loop is a straight line of multiplies and shifts.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_avx2() {
+            // Safety: we just checked that the CPU supports AVX2
+            unsafe { avx2::div_slice_u32(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::div_slice_u32(values, divisor) };
+            return;
+        }
+    }
+
+    div_slice_u32_scalar(values, divisor);
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`.
+///
+/// On x86 and x86_64 CPUs with AVX2, this computes 8 remainders at a time, and on aarch64 CPUs with NEON, it computes 4 remainders at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn rem_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_avx2() {
+            // Safety: we just checked that the CPU supports AVX2
+            unsafe { avx2::rem_slice_u32(values, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON
+            unsafe { neon::rem_slice_u32(values, divisor) };
+            return;
+        }
+    }
+
+    rem_slice_u32_scalar(values, divisor);
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`.
+///
+/// This computes each quotient once, and derives the remainder from it, so it's cheaper than dividing and taking the remainder separately. It uses the same AVX2 and NEON kernels as `div_slice_u32`.
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_rem_slice_u32(input: &[u32], quotients: &mut [u32], remainders: &mut [u32], divisor: StrengthReducedU32) {
+    assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+    assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if has_avx2() {
+            // Safety: we just checked that the CPU supports AVX2, and that the slices have the same length
+            unsafe { avx2::div_rem_slice_u32(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if has_neon() {
+            // Safety: we just checked that the CPU supports NEON, and that the slices have the same length
+            unsafe { neon::div_rem_slice_u32(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+
+    div_rem_slice_u32_scalar(input, quotients, remainders, divisor);
+}
+
+/// Divides every element of `values` by `divisor`, in place.
+///
+/// With the `avx512` feature, on x86 and x86_64 CPUs with AVX-512F and AVX-512DQ, this divides 8 elements at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_slice_u64(values: &mut [u64], divisor: StrengthReducedU64) {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512"))]
+    {
+        if has_avx512() {
+            // Safety: we just checked that the CPU supports AVX-512F and AVX-512DQ
+            unsafe { avx512::div_slice_u64(values, divisor) };
+            return;
+        }
+    }
+
+    div_slice_u64_scalar(values, divisor);
+}
+
+/// Replaces every element of `values` with its remainder after dividing by `divisor`.
+///
+/// With the `avx512` feature, on x86 and x86_64 CPUs with AVX-512F and AVX-512DQ, this computes 8 remainders at a time.
+#[deny(clippy::integer_division_remainder_used)]
+pub fn rem_slice_u64(values: &mut [u64], divisor: StrengthReducedU64) {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512"))]
+    {
+        if has_avx512() {
+            // Safety: we just checked that the CPU supports AVX-512F and AVX-512DQ
+            unsafe { avx512::rem_slice_u64(values, divisor) };
+            return;
+        }
+    }
+
+    rem_slice_u64_scalar(values, divisor);
+}
+
+/// Divides every element of `input` by `divisor`, writing the quotients to `quotients` and the remainders to `remainders`.
+///
+/// This computes each quotient once, and derives the remainder from it, so it's cheaper than dividing and taking the remainder separately. It uses the same AVX-512 kernels as `div_slice_u64`.
+///
+/// # Panics:
+///
+/// Panics if `quotients` or `remainders` isn't the same length as `input`
+#[deny(clippy::integer_division_remainder_used)]
+pub fn div_rem_slice_u64(input: &[u64], quotients: &mut [u64], remainders: &mut [u64], divisor: StrengthReducedU64) {
+    assert_eq!(input.len(), quotients.len(), "quotients must be the same length as input");
+    assert_eq!(input.len(), remainders.len(), "remainders must be the same length as input");
+
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512"))]
+    {
+        if has_avx512() {
+            // Safety: we just checked that the CPU supports AVX-512F and AVX-512DQ, and that the slices have the same length
+            unsafe { avx512::div_rem_slice_u64(input, quotients, remainders, divisor) };
+            return;
+        }
+    }
+
+    div_rem_slice_u64_scalar(input, quotients, remainders, divisor);
+}
+
+// The scalar fallbacks, which also finish the elements after the last full SIMD chunk. They're unrolled by hand, so that four independent multiplies are in flight at once,
+// even when the compiler doesn't unroll or vectorize the loop on its own
+macro_rules! scalar_slice {
+    ($div_fn:ident, $rem_fn:ident, $div_rem_fn:ident, $struct_name:ident, $primitive_type:ident) => (
+        #[inline]
+        #[deny(clippy::integer_division_remainder_used)]
+        pub(crate) fn $div_fn(values: &mut [$primitive_type], divisor: $struct_name) {
+            let mut chunks = values.chunks_exact_mut(4);
+            for chunk in &mut chunks {
+                let quotients = [chunk[0] / divisor, chunk[1] / divisor, chunk[2] / divisor, chunk[3] / divisor];
+                chunk.copy_from_slice(&quotients);
+            }
+            for value in chunks.into_remainder() {
+                *value = *value / divisor;
+            }
+        }
+
+        #[inline]
+        #[deny(clippy::integer_division_remainder_used)]
+        pub(crate) fn $rem_fn(values: &mut [$primitive_type], divisor: $struct_name) {
+            let mut chunks = values.chunks_exact_mut(4);
+            for chunk in &mut chunks {
+                let remainders = [chunk[0] % divisor, chunk[1] % divisor, chunk[2] % divisor, chunk[3] % divisor];
+                chunk.copy_from_slice(&remainders);
+            }
+            for value in chunks.into_remainder() {
+                *value = *value % divisor;
+            }
+        }
+
+        // div_rem already shares the multiply between the quotient and the remainder, and writing to two outputs keeps the loop busy enough without unrolling
+        #[inline]
+        #[deny(clippy::integer_division_remainder_used)]
+        pub(crate) fn $div_rem_fn(input: &[$primitive_type], quotients: &mut [$primitive_type], remainders: &mut [$primitive_type], divisor: $struct_name) {
+            for ((&value, quotient), remainder) in input.iter().zip(quotients).zip(remainders) {
+                let (value_quotient, value_remainder) = $struct_name::div_rem(value, divisor);
+                *quotient = value_quotient;
+                *remainder = value_remainder;
+            }
+        }
+    )
+}
+
+scalar_slice!(div_slice_u8_scalar, rem_slice_u8_scalar, div_rem_slice_u8_scalar, StrengthReducedU8, u8);
+scalar_slice!(div_slice_u16_scalar, rem_slice_u16_scalar, div_rem_slice_u16_scalar, StrengthReducedU16, u16);
+scalar_slice!(div_slice_u32_scalar, rem_slice_u32_scalar, div_rem_slice_u32_scalar, StrengthReducedU32, u32);
+scalar_slice!(div_slice_u64_scalar, rem_slice_u64_scalar, div_rem_slice_u64_scalar, StrengthReducedU64, u64);
+#[cfg(not(strength_reduce_no_u128))]
+scalar_slice!(div_slice_u128_scalar, rem_slice_u128_scalar, div_rem_slice_u128_scalar, StrengthReducedU128, u128);
+// bulk only needs these where it can't reinterpret usize as one of the fixed-width types
+#[cfg(target_pointer_width = "16")]
+scalar_slice!(div_slice_usize_scalar, rem_slice_usize_scalar, div_rem_slice_usize_scalar, StrengthReducedUsize, usize);
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+#[inline]
+fn has_sse2() -> bool {
+    ::std::is_x86_feature_detected!("sse2")
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "std")))]
+#[inline]
+fn has_sse2() -> bool {
+    cfg!(target_feature = "sse2")
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+#[inline]
+fn has_avx2() -> bool {
+    ::std::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "std")))]
+#[inline]
+fn has_avx2() -> bool {
+    cfg!(target_feature = "avx2")
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512", feature = "std"))]
+#[inline]
+fn has_avx512() -> bool {
+    ::std::is_x86_feature_detected!("avx512f") && ::std::is_x86_feature_detected!("avx512dq")
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512", not(feature = "std")))]
+#[inline]
+fn has_avx512() -> bool {
+    cfg!(all(target_feature = "avx512f", target_feature = "avx512dq"))
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "std"))]
+#[inline]
+fn has_neon() -> bool {
+    ::std::arch::is_aarch64_feature_detected!("neon")
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+#[inline]
+fn has_neon() -> bool {
+    cfg!(target_feature = "neon")
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod avx2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use ::constant_time::ConstantTimeInt;
+    use ::StrengthReducedU32;
+    use super::{div_rem_slice_u32_scalar, div_slice_u32_scalar, rem_slice_u32_scalar};
+
+    const LANES: usize = 8;
+
+    #[derive(Clone, Copy)]
+    enum Path {
+        ConstantTime,
+        PowerOfTwo,
+        Large,
+        Multiply,
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct Divider {
+        path: Path,
+        divisors: __m256i,
+        multiplier_hi: __m256i,
+        multiplier_lo: __m256i,
+        shift: __m128i,
+        numerator_mask: __m256i,
+    }
+
+    // Safety: these need AVX2, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl Divider {
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn new(divisor: StrengthReducedU32) -> Self {
+            let (path, multiplier) = if cfg!(feature = "constant-time") {
+                // same as the scalar version: the power of two multiplier is masked back in, and a divisor of 1 masks the numerator into the quotient
+                (Path::ConstantTime, divisor.multiplier | (u64::mask(divisor.multiplier == 0) & ((1 << (63 - divisor.shift)) << 1)))
+            } else if divisor.multiplier == 0 {
+                (Path::PowerOfTwo, 0)
+            } else if divisor.large {
+                (Path::Large, 0)
+            } else {
+                (Path::Multiply, divisor.multiplier)
+            };
+
+            Self {
+                path,
+                divisors: _mm256_set1_epi32(divisor.divisor.get() as i32),
+                multiplier_hi: _mm256_set1_epi64x((multiplier >> 32) as i64),
+                multiplier_lo: _mm256_set1_epi64x(multiplier as u32 as i64),
+                shift: _mm_cvtsi32_si128(divisor.shift as i32),
+                numerator_mask: _mm256_set1_epi32(u32::mask(divisor.divisor.get() == 1) as i32),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn divide(&self, numerators: __m256i) -> __m256i {
+            match self.path {
+                Path::ConstantTime => _mm256_or_si256(self.multiply(numerators), _mm256_and_si256(numerators, self.numerator_mask)),
+                Path::PowerOfTwo => _mm256_srl_epi32(numerators, self.shift),
+                Path::Large => {
+                    // the quotient is either 0 or 1, so a comparison is enough. AVX2 doesn't have an unsigned comparison, but n >= d exactly when max(n, d) == n
+                    let greater_equal = _mm256_cmpeq_epi32(_mm256_max_epu32(numerators, self.divisors), numerators);
+                    _mm256_srli_epi32(greater_equal, 31)
+                }
+                Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn remainder(&self, numerators: __m256i) -> __m256i {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn div_rem(&self, numerators: __m256i) -> (__m256i, __m256i) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => _mm256_and_si256(numerators, _mm256_sub_epi32(self.divisors, _mm256_set1_epi32(1))),
+                _ => _mm256_sub_epi32(numerators, _mm256_mullo_epi32(quotients, self.divisors)),
+            };
+            (quotients, remainders)
+        }
+
+        // Computes the upper 32 bits of each numerator times the 64-bit multiplier, the same way the scalar version does
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn multiply(&self, numerators: __m256i) -> __m256i {
+            // _mm256_mul_epu32 only reads the even 32-bit lanes, so we shift the odd lanes down to do a second pass
+            let even = self.multiply_even(numerators);
+            let odd = self.multiply_even(_mm256_srli_epi64(numerators, 32));
+
+            // the even quotients are in the low half of each 64-bit lane, and the odd quotients need to be shifted up into the high half
+            _mm256_blend_epi32(even, _mm256_slli_epi64(odd, 32), 0b1010_1010)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        unsafe fn multiply_even(&self, numerators: __m256i) -> __m256i {
+            let multiplied_hi = _mm256_mul_epu32(numerators, self.multiplier_hi);
+            let multiplied_lo = _mm256_srli_epi64(_mm256_mul_epu32(numerators, self.multiplier_lo), 32);
+            _mm256_srli_epi64(_mm256_add_epi64(multiplied_hi, multiplied_lo), 32)
+        }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX2
+    #[target_feature(enable = "avx2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = Divider::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            store(chunk, divider.divide(load(chunk)));
+        }
+
+        div_slice_u32_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX2
+    #[target_feature(enable = "avx2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = Divider::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            store(chunk, divider.remainder(load(chunk)));
+        }
+
+        rem_slice_u32_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX2, and that all three slices have the same length
+    #[target_feature(enable = "avx2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u32(input: &[u32], quotients: &mut [u32], remainders: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = Divider::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            let (quotient, remainder) = divider.div_rem(load(input_chunk));
+            store(quotient_chunk, quotient);
+            store(remainder_chunk, remainder);
+        }
+
+        div_rem_slice_u32_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX2
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn load(chunk: &[u32]) -> __m256i {
+        debug_assert_eq!(chunk.len(), LANES);
+        // Safety: every chunk is exactly 8 elements long, and the unaligned load doesn't care about alignment
+        unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX2
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store(chunk: &mut [u32], vector: __m256i) {
+        debug_assert_eq!(chunk.len(), LANES);
+        // Safety: every chunk is exactly 8 elements long, and the unaligned store doesn't care about alignment
+        unsafe { _mm256_storeu_si256(chunk.as_mut_ptr() as *mut __m256i, vector) }
+    }
+}
+
+// AVX-512 still doesn't have a 64-bit high multiply, so these kernels use the 65-bit round-up multiplier, and assemble its product from 32-bit limbs like portable_simd does.
+// IFMA's 52-bit multiplies don't help here: a 64-bit numerator and multiplier would need just as many 52-bit limbs, with messier carries.
+// The AVX-512 intrinsics need rustc 1.89, so they're behind a feature, to keep the crate building on older compilers
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512"))]
+#[allow(clippy::incompatible_msrv)]
+mod avx512 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use ::constant_time::ConstantTimeInt;
+    use ::StrengthReducedU64;
+    use super::{div_rem_slice_u64_scalar, div_slice_u64_scalar, rem_slice_u64_scalar};
+
+    const LANES: usize = 8;
+
+    #[derive(Clone, Copy)]
+    enum Path {
+        ConstantTime,
+        PowerOfTwo,
+        Large,
+        Multiply,
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct Divider {
+        path: Path,
+        divisors: __m512i,
+        multiplier_hi: __m512i,
+        multiplier_lo: __m512i,
+        multiply_shift: __m128i,
+        power_of_two_shift: __m128i,
+        power_of_two_mask: __m512i,
+    }
+
+    // Safety: these need AVX-512F and AVX-512DQ, so the caller has to make sure that the CPU supports them, the same as for the kernels below
+    impl Divider {
+        #[inline]
+        #[target_feature(enable = "avx512f,avx512dq")]
+        unsafe fn new(divisor: StrengthReducedU64) -> Self {
+            let value = divisor.get();
+            let (multiplier, multiply_shift) = divisor.round_up_parts();
+            let path = if cfg!(feature = "constant-time") {
+                Path::ConstantTime
+            } else if value.is_power_of_two() {
+                Path::PowerOfTwo
+            } else if value > u64::MAX / 2 {
+                Path::Large
+            } else {
+                Path::Multiply
+            };
+
+            Self {
+                path,
+                divisors: _mm512_set1_epi64(value as i64),
+                multiplier_hi: _mm512_set1_epi64((multiplier >> 32) as i64),
+                multiplier_lo: _mm512_set1_epi64(multiplier as u32 as i64),
+                multiply_shift: _mm_cvtsi32_si128(multiply_shift as i32),
+                power_of_two_shift: _mm_cvtsi32_si128(value.trailing_zeros() as i32),
+                power_of_two_mask: _mm512_set1_epi64(u64::mask(value.is_power_of_two()) as i64),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx512f,avx512dq")]
+        unsafe fn divide(&self, numerators: __m512i) -> __m512i {
+            match self.path {
+                Path::ConstantTime => {
+                    // always take the multiply path, and select the power of two quotient afterwards
+                    let quotients = self.multiply(numerators);
+                    let power_of_two_quotients = _mm512_srl_epi64(numerators, self.power_of_two_shift);
+                    _mm512_or_si512(_mm512_and_si512(power_of_two_quotients, self.power_of_two_mask), _mm512_andnot_si512(self.power_of_two_mask, quotients))
+                }
+                Path::PowerOfTwo => _mm512_srl_epi64(numerators, self.power_of_two_shift),
+                Path::Large => {
+                    // the quotient is either 0 or 1, so a comparison is enough
+                    let greater_equal = _mm512_cmpge_epu64_mask(numerators, self.divisors);
+                    _mm512_maskz_mov_epi64(greater_equal, _mm512_set1_epi64(1))
+                }
+                Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx512f,avx512dq")]
+        unsafe fn remainder(&self, numerators: __m512i) -> __m512i {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "avx512f,avx512dq")]
+        unsafe fn div_rem(&self, numerators: __m512i) -> (__m512i, __m512i) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => _mm512_and_si512(numerators, _mm512_sub_epi64(self.divisors, _mm512_set1_epi64(1))),
+                _ => _mm512_sub_epi64(numerators, _mm512_mullo_epi64(quotients, self.divisors)),
+            };
+            (quotients, remainders)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx512f,avx512dq")]
+        unsafe fn multiply(&self, numerators: __m512i) -> __m512i {
+            let low_mask = _mm512_set1_epi64(u32::MAX as i64);
+            let numerators_hi = _mm512_srli_epi64(numerators, 32);
+
+            // compute the four 32x32 partial products, and carry the middle ones into the upper half. _mm512_mul_epu32 ignores the upper half of each lane, so the low limbs don't need masking
+            let lo_lo = _mm512_mul_epu32(numerators, self.multiplier_lo);
+            let lo_hi = _mm512_mul_epu32(numerators, self.multiplier_hi);
+            let hi_lo = _mm512_mul_epu32(numerators_hi, self.multiplier_lo);
+            let hi_hi = _mm512_mul_epu32(numerators_hi, self.multiplier_hi);
+
+            let middle = _mm512_add_epi64(_mm512_srli_epi64(lo_lo, 32), _mm512_add_epi64(_mm512_and_si512(lo_hi, low_mask), _mm512_and_si512(hi_lo, low_mask)));
+            let upper = _mm512_add_epi64(_mm512_add_epi64(hi_hi, _mm512_srli_epi64(lo_hi, 32)), _mm512_add_epi64(_mm512_srli_epi64(hi_lo, 32), _mm512_srli_epi64(middle, 32)));
+
+            // adding the implicit top bit's contribution directly could overflow, so average them instead, and shift one bit less
+            let average = _mm512_add_epi64(upper, _mm512_srli_epi64(_mm512_sub_epi64(numerators, upper), 1));
+            _mm512_srl_epi64(average, self.multiply_shift)
+        }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX-512F and AVX-512DQ
+    #[target_feature(enable = "avx512f,avx512dq")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u64(values: &mut [u64], divisor: StrengthReducedU64) {
+        let divider = Divider::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            store(chunk, divider.divide(load(chunk)));
+        }
+
+        div_slice_u64_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX-512F and AVX-512DQ
+    #[target_feature(enable = "avx512f,avx512dq")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u64(values: &mut [u64], divisor: StrengthReducedU64) {
+        let divider = Divider::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            store(chunk, divider.remainder(load(chunk)));
+        }
+
+        rem_slice_u64_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX-512F and AVX-512DQ, and that all three slices have the same length
+    #[target_feature(enable = "avx512f,avx512dq")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u64(input: &[u64], quotients: &mut [u64], remainders: &mut [u64], divisor: StrengthReducedU64) {
+        let divider = Divider::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            let (quotient, remainder) = divider.div_rem(load(input_chunk));
+            store(quotient_chunk, quotient);
+            store(remainder_chunk, remainder);
+        }
+
+        div_rem_slice_u64_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX-512F
+    #[inline]
+    #[target_feature(enable = "avx512f")]
+    unsafe fn load(chunk: &[u64]) -> __m512i {
+        debug_assert_eq!(chunk.len(), LANES);
+        // Safety: every chunk is exactly 8 elements long, and the unaligned load doesn't care about alignment
+        unsafe { _mm512_loadu_si512(chunk.as_ptr() as *const __m512i) }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports AVX-512F
+    #[inline]
+    #[target_feature(enable = "avx512f")]
+    unsafe fn store(chunk: &mut [u64], vector: __m512i) {
+        debug_assert_eq!(chunk.len(), LANES);
+        // Safety: every chunk is exactly 8 elements long, and the unaligned store doesn't care about alignment
+        unsafe { _mm512_storeu_si512(chunk.as_mut_ptr() as *mut __m512i, vector) }
+    }
+}
+
+// SSE2 is part of the x86_64 baseline, so unlike the other x86 kernels, these run on every x86_64 CPU.
+// SSE2 only has 16-bit multiplies, which is just enough: u8 numerators are widened to 16 bits, and u16 numerators use a split multiplier with an explicit carry
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod sse2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use core::mem;
+
+    use ::constant_time::ConstantTimeInt;
+    use ::{StrengthReducedU8, StrengthReducedU16};
+    use super::{div_rem_slice_u16_scalar, div_rem_slice_u8_scalar, div_slice_u16_scalar, div_slice_u8_scalar, rem_slice_u16_scalar, rem_slice_u8_scalar};
+
+    const LANES_U8: usize = 16;
+    const LANES_U16: usize = 8;
+
+    #[derive(Clone, Copy)]
+    enum Path {
+        ConstantTime,
+        PowerOfTwo,
+        Large,
+        Multiply,
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice.
+    // The multiplies happen on numerators widened to 16 bits, so divisors, multiplier, and numerator_mask have 16-bit lanes
+    struct DividerU8 {
+        path: Path,
+        divisors: __m128i,
+        multiplier: __m128i,
+        numerator_mask: __m128i,
+        shift: __m128i,
+        // SSE2 doesn't have 8-bit shifts, so powers of two shift 16-bit lanes, and mask off the bits that crossed over from the neighboring byte
+        shifted_mask: __m128i,
+        remainder_mask: __m128i,
+    }
+
+    // Safety: these need SSE2, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl DividerU8 {
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn new(divisor: StrengthReducedU8) -> Self {
+            let path = if cfg!(feature = "constant-time") {
+                Path::ConstantTime
+            } else if divisor.multiplier == 0 {
+                Path::PowerOfTwo
+            } else {
+                // every u8 multiplier fits in 16 bits, so large divisors don't need their own path
+                Path::Multiply
+            };
+            // same as the scalar version: the power of two multiplier is masked back in, except for a divisor of 1, where it doesn't fit in 16 bits, so we mask the numerator into the quotient instead
+            let multiplier = divisor.multiplier | (u16::mask(divisor.multiplier == 0) & ((1u32 << (15 - divisor.shift)) << 1) as u16);
+            let value = divisor.divisor.get();
+
+            Self {
+                path,
+                divisors: _mm_set1_epi16(value as i16),
+                multiplier: _mm_set1_epi16(multiplier as i16),
+                numerator_mask: _mm_set1_epi16(u16::mask(value == 1) as i16),
+                shift: _mm_cvtsi32_si128(divisor.shift as i32),
+                shifted_mask: _mm_set1_epi8((u8::MAX >> divisor.shift) as i8),
+                remainder_mask: _mm_set1_epi8((value - 1) as i8),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn divide(&self, numerators: __m128i) -> __m128i {
+            match self.path {
+                Path::PowerOfTwo => _mm_and_si128(_mm_srl_epi16(numerators, self.shift), self.shifted_mask),
+                _ => {
+                    let (numerators_lo, numerators_hi) = widen(numerators);
+                    _mm_packus_epi16(self.divide_widened(numerators_lo), self.divide_widened(numerators_hi))
+                }
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn remainder(&self, numerators: __m128i) -> __m128i {
+            self.div_rem(numerators).1
+        }
+
+        // there's no 8-bit multiply either, so the remainder is computed from the widened quotients before they're packed back down
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn div_rem(&self, numerators: __m128i) -> (__m128i, __m128i) {
+            match self.path {
+                Path::PowerOfTwo => (self.divide(numerators), _mm_and_si128(numerators, self.remainder_mask)),
+                _ => {
+                    let (numerators_lo, numerators_hi) = widen(numerators);
+                    let quotients_lo = self.divide_widened(numerators_lo);
+                    let quotients_hi = self.divide_widened(numerators_hi);
+                    let remainders_lo = _mm_sub_epi16(numerators_lo, _mm_mullo_epi16(quotients_lo, self.divisors));
+                    let remainders_hi = _mm_sub_epi16(numerators_hi, _mm_mullo_epi16(quotients_hi, self.divisors));
+                    (_mm_packus_epi16(quotients_lo, quotients_hi), _mm_packus_epi16(remainders_lo, remainders_hi))
+                }
+            }
+        }
+
+        // Same as the scalar version: a u8 numerator times the 16-bit multiplier fits in 32 bits, and the quotient is the upper 16
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn divide_widened(&self, numerators: __m128i) -> __m128i {
+            let quotients = _mm_mulhi_epu16(numerators, self.multiplier);
+            match self.path {
+                Path::ConstantTime => _mm_or_si128(quotients, _mm_and_si128(numerators, self.numerator_mask)),
+                _ => quotients,
+            }
+        }
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct DividerU16 {
+        path: Path,
+        divisors: __m128i,
+        multiplier_hi: __m128i,
+        multiplier_lo: __m128i,
+        shift: __m128i,
+        numerator_mask: __m128i,
+    }
+
+    // Safety: these need SSE2, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl DividerU16 {
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn new(divisor: StrengthReducedU16) -> Self {
+            let (path, multiplier) = if cfg!(feature = "constant-time") {
+                // same as the NEON version: the power of two multiplier is masked back in, and a divisor of 1 masks the numerator into the quotient
+                (Path::ConstantTime, divisor.multiplier | (u32::mask(divisor.multiplier == 0) & ((1 << (31 - divisor.shift)) << 1)))
+            } else if divisor.multiplier == 0 {
+                (Path::PowerOfTwo, 0)
+            } else if divisor.large {
+                (Path::Large, 0)
+            } else {
+                (Path::Multiply, divisor.multiplier)
+            };
+
+            Self {
+                path,
+                divisors: _mm_set1_epi16(divisor.divisor.get() as i16),
+                multiplier_hi: _mm_set1_epi16((multiplier >> 16) as i16),
+                multiplier_lo: _mm_set1_epi16(multiplier as i16),
+                shift: _mm_cvtsi32_si128(divisor.shift as i32),
+                numerator_mask: _mm_set1_epi16(u16::mask(divisor.divisor.get() == 1) as i16),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn divide(&self, numerators: __m128i) -> __m128i {
+            match self.path {
+                Path::ConstantTime => _mm_or_si128(self.multiply(numerators), _mm_and_si128(numerators, self.numerator_mask)),
+                Path::PowerOfTwo => _mm_srl_epi16(numerators, self.shift),
+                Path::Large => {
+                    // the quotient is either 0 or 1, so a comparison is enough. SSE2 doesn't have an unsigned comparison, but n >= d exactly when d - n saturates to 0
+                    let greater_equal = _mm_cmpeq_epi16(_mm_subs_epu16(self.divisors, numerators), _mm_setzero_si128());
+                    _mm_srli_epi16(greater_equal, 15)
+                }
+                Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn remainder(&self, numerators: __m128i) -> __m128i {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn div_rem(&self, numerators: __m128i) -> (__m128i, __m128i) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => _mm_and_si128(numerators, _mm_sub_epi16(self.divisors, _mm_set1_epi16(1))),
+                _ => _mm_sub_epi16(numerators, _mm_mullo_epi16(quotients, self.divisors)),
+            };
+            (quotients, remainders)
+        }
+
+        // Same as the scalar version, except that the high half's product needs all 32 bits, so it comes from two 16-bit multiplies, and adding the low half's product to it needs an explicit carry
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        unsafe fn multiply(&self, numerators: __m128i) -> __m128i {
+            let multiplied_hi_upper = _mm_mulhi_epu16(numerators, self.multiplier_hi);
+            let multiplied_hi_lower = _mm_mullo_epi16(numerators, self.multiplier_hi);
+            let multiplied_lo = _mm_mulhi_epu16(numerators, self.multiplier_lo);
+
+            // the wrapping sum only matches the saturating sum if the addition didn't carry
+            let sum = _mm_add_epi16(multiplied_hi_lower, multiplied_lo);
+            let carry = _mm_andnot_si128(_mm_cmpeq_epi16(_mm_adds_epu16(multiplied_hi_lower, multiplied_lo), sum), _mm_set1_epi16(-1));
+
+            // the carry mask is -1 where the addition carried, so subtracting it adds the carry
+            _mm_sub_epi16(multiplied_hi_upper, carry)
+        }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U8);
+        for chunk in &mut chunks {
+            store(chunk, divider.divide(load(chunk)));
+        }
+
+        div_slice_u8_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U8);
+        for chunk in &mut chunks {
+            store(chunk, divider.remainder(load(chunk)));
+        }
+
+        rem_slice_u8_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2, and that all three slices have the same length
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u8(input: &[u8], quotients: &mut [u8], remainders: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES_U8);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES_U8);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES_U8);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            let (quotient, remainder) = divider.div_rem(load(input_chunk));
+            store(quotient_chunk, quotient);
+            store(remainder_chunk, remainder);
+        }
+
+        div_rem_slice_u8_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U16);
+        for chunk in &mut chunks {
+            store(chunk, divider.divide(load(chunk)));
+        }
+
+        div_slice_u16_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U16);
+        for chunk in &mut chunks {
+            store(chunk, divider.remainder(load(chunk)));
+        }
+
+        rem_slice_u16_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2, and that all three slices have the same length
+    #[target_feature(enable = "sse2")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u16(input: &[u16], quotients: &mut [u16], remainders: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES_U16);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES_U16);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES_U16);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            let (quotient, remainder) = divider.div_rem(load(input_chunk));
+            store(quotient_chunk, quotient);
+            store(remainder_chunk, remainder);
+        }
+
+        div_rem_slice_u16_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn widen(numerators: __m128i) -> (__m128i, __m128i) {
+        let zero = _mm_setzero_si128();
+        (_mm_unpacklo_epi8(numerators, zero), _mm_unpackhi_epi8(numerators, zero))
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn load<T>(chunk: &[T]) -> __m128i {
+        debug_assert_eq!(mem::size_of_val(chunk), mem::size_of::<__m128i>());
+        // Safety: every chunk is exactly 16 bytes long, and the unaligned load doesn't care about alignment
+        unsafe { _mm_loadu_si128(chunk.as_ptr() as *const __m128i) }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports SSE2
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn store<T>(chunk: &mut [T], vector: __m128i) {
+        debug_assert_eq!(mem::size_of_val(chunk), mem::size_of::<__m128i>());
+        // Safety: every chunk is exactly 16 bytes long, and the unaligned store doesn't care about alignment
+        unsafe { _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, vector) }
+    }
+}
+
+// NEON's widening multiplies handle two lanes per instruction, and multiply-accumulate lets us add the split multiplier's two products without a separate add
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use core::arch::aarch64::*;
+
+    use ::constant_time::ConstantTimeInt;
+    use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32};
+    use super::{div_rem_slice_u8_scalar, div_rem_slice_u16_scalar, div_rem_slice_u32_scalar, div_slice_u8_scalar, div_slice_u16_scalar, div_slice_u32_scalar, rem_slice_u8_scalar, rem_slice_u16_scalar, rem_slice_u32_scalar};
+
+    const LANES_U8: usize = 16;
+    const LANES_U16: usize = 8;
+    const LANES_U32: usize = 4;
+
+    #[derive(Clone, Copy)]
+    enum Path {
+        ConstantTime,
+        PowerOfTwo,
+        Large,
+        Multiply,
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct DividerU8 {
+        path: Path,
+        divisors: uint8x16_t,
+        multiplier_hi: uint8x16_t,
+        multiplier_lo: uint8x16_t,
+        // NEON only shifts left by a vector, so right shifts use a negative shift amount
+        shift: int8x16_t,
+        numerator_mask: uint8x16_t,
+    }
+
+    // Safety: these need NEON, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl DividerU8 {
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn new(divisor: StrengthReducedU8) -> Self {
+            let (path, multiplier) = if cfg!(feature = "constant-time") {
+                // same as the scalar version: the power of two multiplier is masked back in, except for a divisor of 1, where it doesn't fit in 16 bits, so we mask the numerator into the quotient instead
+                (Path::ConstantTime, divisor.multiplier | (u16::mask(divisor.multiplier == 0) & ((1u32 << (15 - divisor.shift)) << 1) as u16))
+            } else if divisor.multiplier == 0 {
+                (Path::PowerOfTwo, 0)
+            } else {
+                // every u8 multiplier fits in 16 bits, so large divisors don't need their own path
+                (Path::Multiply, divisor.multiplier)
+            };
+
+            Self {
+                path,
+                divisors: vdupq_n_u8(divisor.divisor.get()),
+                multiplier_hi: vdupq_n_u8((multiplier >> 8) as u8),
+                multiplier_lo: vdupq_n_u8(multiplier as u8),
+                shift: vdupq_n_s8(-(divisor.shift as i8)),
+                numerator_mask: vdupq_n_u8(u8::mask(divisor.divisor.get() == 1)),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn divide(&self, numerators: uint8x16_t) -> uint8x16_t {
+            match self.path {
+                Path::ConstantTime => vorrq_u8(self.multiply(numerators), vandq_u8(numerators, self.numerator_mask)),
+                Path::PowerOfTwo => vshlq_u8(numerators, self.shift),
+                Path::Large | Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn remainder(&self, numerators: uint8x16_t) -> uint8x16_t {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn div_rem(&self, numerators: uint8x16_t) -> (uint8x16_t, uint8x16_t) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => vandq_u8(numerators, vsubq_u8(self.divisors, vdupq_n_u8(1))),
+                _ => vmlsq_u8(numerators, quotients, self.divisors),
+            };
+            (quotients, remainders)
+        }
+
+        // Same as the scalar version: both halves of the multiplier fit in 8 bits, so each product fits in a 16-bit lane
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn multiply(&self, numerators: uint8x16_t) -> uint8x16_t {
+            let multiplied_lo = vshrq_n_u16::<8>(vmull_u8(vget_low_u8(numerators), vget_low_u8(self.multiplier_lo)));
+            let multiplied_lo = vmlal_u8(multiplied_lo, vget_low_u8(numerators), vget_low_u8(self.multiplier_hi));
+            let multiplied_hi = vshrq_n_u16::<8>(vmull_high_u8(numerators, self.multiplier_lo));
+            let multiplied_hi = vmlal_high_u8(multiplied_hi, numerators, self.multiplier_hi);
+
+            vshrn_high_n_u16::<8>(vshrn_n_u16::<8>(multiplied_lo), multiplied_hi)
+        }
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct DividerU16 {
+        path: Path,
+        divisors: uint16x8_t,
+        multiplier_hi: uint16x8_t,
+        multiplier_lo: uint16x8_t,
+        // NEON only shifts left by a vector, so right shifts use a negative shift amount
+        shift: int16x8_t,
+        numerator_mask: uint16x8_t,
+    }
+
+    // Safety: these need NEON, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl DividerU16 {
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn new(divisor: StrengthReducedU16) -> Self {
+            let (path, multiplier) = if cfg!(feature = "constant-time") {
+                // same idea as the u32 version: the power of two multiplier is masked back in, and a divisor of 1 masks the numerator into the quotient
+                (Path::ConstantTime, divisor.multiplier | (u32::mask(divisor.multiplier == 0) & ((1 << (31 - divisor.shift)) << 1)))
+            } else if divisor.multiplier == 0 {
+                (Path::PowerOfTwo, 0)
+            } else if divisor.large {
+                (Path::Large, 0)
+            } else {
+                (Path::Multiply, divisor.multiplier)
+            };
+
+            Self {
+                path,
+                divisors: vdupq_n_u16(divisor.divisor.get()),
+                multiplier_hi: vdupq_n_u16((multiplier >> 16) as u16),
+                multiplier_lo: vdupq_n_u16(multiplier as u16),
+                shift: vdupq_n_s16(-(divisor.shift as i16)),
+                numerator_mask: vdupq_n_u16(u16::mask(divisor.divisor.get() == 1)),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn divide(&self, numerators: uint16x8_t) -> uint16x8_t {
+            match self.path {
+                Path::ConstantTime => vorrq_u16(self.multiply(numerators), vandq_u16(numerators, self.numerator_mask)),
+                Path::PowerOfTwo => vshlq_u16(numerators, self.shift),
+                // the quotient is either 0 or 1, so a comparison is enough
+                Path::Large => vshrq_n_u16::<15>(vcgeq_u16(numerators, self.divisors)),
+                Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn remainder(&self, numerators: uint16x8_t) -> uint16x8_t {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn div_rem(&self, numerators: uint16x8_t) -> (uint16x8_t, uint16x8_t) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => vandq_u16(numerators, vsubq_u16(self.divisors, vdupq_n_u16(1))),
+                _ => vmlsq_u16(numerators, quotients, self.divisors),
+            };
+            (quotients, remainders)
+        }
+
+        // Same as the scalar version: both halves of the multiplier fit in 16 bits, so each product fits in a 32-bit lane
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn multiply(&self, numerators: uint16x8_t) -> uint16x8_t {
+            let multiplied_lo = vshrq_n_u32::<16>(vmull_u16(vget_low_u16(numerators), vget_low_u16(self.multiplier_lo)));
+            let multiplied_lo = vmlal_u16(multiplied_lo, vget_low_u16(numerators), vget_low_u16(self.multiplier_hi));
+            let multiplied_hi = vshrq_n_u32::<16>(vmull_high_u16(numerators, self.multiplier_lo));
+            let multiplied_hi = vmlal_high_u16(multiplied_hi, numerators, self.multiplier_hi);
+
+            vshrn_high_n_u32::<16>(vshrn_n_u32::<16>(multiplied_lo), multiplied_hi)
+        }
+    }
+
+    // Everything about the divisor that doesn't depend on the numerators, splatted across the lanes once per slice
+    struct DividerU32 {
+        path: Path,
+        divisors: uint32x4_t,
+        multiplier_hi: uint32x4_t,
+        multiplier_lo: uint32x4_t,
+        // NEON only shifts left by a vector, so right shifts use a negative shift amount
+        shift: int32x4_t,
+        numerator_mask: uint32x4_t,
+    }
+
+    // Safety: these need NEON, so the caller has to make sure that the CPU supports it, the same as for the kernels below
+    impl DividerU32 {
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn new(divisor: StrengthReducedU32) -> Self {
+            let (path, multiplier) = if cfg!(feature = "constant-time") {
+                // same as the scalar version: the power of two multiplier is masked back in, and a divisor of 1 masks the numerator into the quotient
+                (Path::ConstantTime, divisor.multiplier | (u64::mask(divisor.multiplier == 0) & ((1 << (63 - divisor.shift)) << 1)))
+            } else if divisor.multiplier == 0 {
+                (Path::PowerOfTwo, 0)
+            } else if divisor.large {
+                (Path::Large, 0)
+            } else {
+                (Path::Multiply, divisor.multiplier)
+            };
+
+            Self {
+                path,
+                divisors: vdupq_n_u32(divisor.divisor.get()),
+                multiplier_hi: vdupq_n_u32((multiplier >> 32) as u32),
+                multiplier_lo: vdupq_n_u32(multiplier as u32),
+                shift: vdupq_n_s32(-(divisor.shift as i32)),
+                numerator_mask: vdupq_n_u32(u32::mask(divisor.divisor.get() == 1)),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn divide(&self, numerators: uint32x4_t) -> uint32x4_t {
+            match self.path {
+                Path::ConstantTime => vorrq_u32(self.multiply(numerators), vandq_u32(numerators, self.numerator_mask)),
+                Path::PowerOfTwo => vshlq_u32(numerators, self.shift),
+                // the quotient is either 0 or 1, so a comparison is enough
+                Path::Large => vshrq_n_u32::<31>(vcgeq_u32(numerators, self.divisors)),
+                Path::Multiply => self.multiply(numerators),
+            }
+        }
+
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn remainder(&self, numerators: uint32x4_t) -> uint32x4_t {
+            self.div_rem(numerators).1
+        }
+
+        // the remainder reuses the quotient's multiply, except for powers of two, where masking is cheaper than multiplying the quotient back
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn div_rem(&self, numerators: uint32x4_t) -> (uint32x4_t, uint32x4_t) {
+            let quotients = self.divide(numerators);
+            let remainders = match self.path {
+                Path::PowerOfTwo => vandq_u32(numerators, vsubq_u32(self.divisors, vdupq_n_u32(1))),
+                _ => vmlsq_u32(numerators, quotients, self.divisors),
+            };
+            (quotients, remainders)
+        }
+
+        // Same as the scalar version: both halves of the multiplier fit in 32 bits, so each product fits in a 64-bit lane
+        #[inline]
+        #[target_feature(enable = "neon")]
+        unsafe fn multiply(&self, numerators: uint32x4_t) -> uint32x4_t {
+            let multiplied_lo = vshrq_n_u64::<32>(vmull_u32(vget_low_u32(numerators), vget_low_u32(self.multiplier_lo)));
+            let multiplied_lo = vmlal_u32(multiplied_lo, vget_low_u32(numerators), vget_low_u32(self.multiplier_hi));
+            let multiplied_hi = vshrq_n_u64::<32>(vmull_high_u32(numerators, self.multiplier_lo));
+            let multiplied_hi = vmlal_high_u32(multiplied_hi, numerators, self.multiplier_hi);
+
+            vshrn_high_n_u64::<32>(vshrn_n_u64::<32>(multiplied_lo), multiplied_hi)
+        }
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U8);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 16 elements long
+            unsafe { vst1q_u8(chunk.as_mut_ptr(), divider.divide(vld1q_u8(chunk.as_ptr()))) };
+        }
+
+        div_slice_u8_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u8(values: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U8);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 16 elements long
+            unsafe { vst1q_u8(chunk.as_mut_ptr(), divider.remainder(vld1q_u8(chunk.as_ptr()))) };
+        }
+
+        rem_slice_u8_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON, and that all three slices have the same length
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u8(input: &[u8], quotients: &mut [u8], remainders: &mut [u8], divisor: StrengthReducedU8) {
+        let divider = DividerU8::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES_U8);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES_U8);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES_U8);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            // Safety: every chunk is exactly 16 elements long
+            unsafe {
+                let (quotient, remainder) = divider.div_rem(vld1q_u8(input_chunk.as_ptr()));
+                vst1q_u8(quotient_chunk.as_mut_ptr(), quotient);
+                vst1q_u8(remainder_chunk.as_mut_ptr(), remainder);
+            }
+        }
+
+        div_rem_slice_u8_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U16);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 8 elements long
+            unsafe { vst1q_u16(chunk.as_mut_ptr(), divider.divide(vld1q_u16(chunk.as_ptr()))) };
+        }
+
+        div_slice_u16_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u16(values: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U16);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 8 elements long
+            unsafe { vst1q_u16(chunk.as_mut_ptr(), divider.remainder(vld1q_u16(chunk.as_ptr()))) };
+        }
+
+        rem_slice_u16_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON, and that all three slices have the same length
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u16(input: &[u16], quotients: &mut [u16], remainders: &mut [u16], divisor: StrengthReducedU16) {
+        let divider = DividerU16::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES_U16);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES_U16);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES_U16);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            // Safety: every chunk is exactly 8 elements long
+            unsafe {
+                let (quotient, remainder) = divider.div_rem(vld1q_u16(input_chunk.as_ptr()));
+                vst1q_u16(quotient_chunk.as_mut_ptr(), quotient);
+                vst1q_u16(remainder_chunk.as_mut_ptr(), remainder);
+            }
+        }
+
+        div_rem_slice_u16_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = DividerU32::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U32);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 4 elements long
+            unsafe { vst1q_u32(chunk.as_mut_ptr(), divider.divide(vld1q_u32(chunk.as_ptr()))) };
+        }
+
+        div_slice_u32_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn rem_slice_u32(values: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = DividerU32::new(divisor);
+        let mut chunks = values.chunks_exact_mut(LANES_U32);
+        for chunk in &mut chunks {
+            // Safety: every chunk is exactly 4 elements long
+            unsafe { vst1q_u32(chunk.as_mut_ptr(), divider.remainder(vld1q_u32(chunk.as_ptr()))) };
+        }
+
+        rem_slice_u32_scalar(chunks.into_remainder(), divisor);
+    }
+
+    // Safety: the caller has to make sure that the CPU supports NEON, and that all three slices have the same length
+    #[target_feature(enable = "neon")]
+    #[deny(clippy::integer_division_remainder_used)]
+    pub(super) unsafe fn div_rem_slice_u32(input: &[u32], quotients: &mut [u32], remainders: &mut [u32], divisor: StrengthReducedU32) {
+        let divider = DividerU32::new(divisor);
+        let mut input_chunks = input.chunks_exact(LANES_U32);
+        let mut quotient_chunks = quotients.chunks_exact_mut(LANES_U32);
+        let mut remainder_chunks = remainders.chunks_exact_mut(LANES_U32);
+        for ((input_chunk, quotient_chunk), remainder_chunk) in (&mut input_chunks).zip(&mut quotient_chunks).zip(&mut remainder_chunks) {
+            // Safety: every chunk is exactly 4 elements long
+            unsafe {
+                let (quotient, remainder) = divider.div_rem(vld1q_u32(input_chunk.as_ptr()));
+                vst1q_u32(quotient_chunk.as_mut_ptr(), quotient);
+                vst1q_u32(remainder_chunk.as_mut_ptr(), remainder);
+            }
+        }
+
+        div_rem_slice_u32_scalar(input_chunks.remainder(), quotient_chunks.into_remainder(), remainder_chunks.into_remainder(), divisor);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_slice_u8() {
+        // every u8 numerator, plus a few more for a scalar tail after the SIMD chunks
+        let mut values = [0u8; 261];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = index as u8;
+        }
+
+        for divisor in 1..=u8::MAX {
+            let reduced_divisor = StrengthReducedU8::new(divisor);
+
+            let mut quotients = values;
+            div_slice_u8(&mut quotients, reduced_divisor);
+            let mut remainders = values;
+            rem_slice_u8(&mut remainders, reduced_divisor);
+            let mut fused_quotients = values;
+            let mut fused_remainders = values;
+            div_rem_slice_u8(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+            assert_eq!(quotients, fused_quotients);
+            assert_eq!(remainders, fused_remainders);
+
+            for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+                assert_eq!(value / divisor, quotient, "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, remainder, "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_u16() {
+        let max = u16::MAX;
+
+        // there are few enough u16 divisors to try all of them, with every interesting numerator, and a scalar tail after the SIMD chunks
+        for divisor in 1..=max {
+            let reduced_divisor = StrengthReducedU16::new(divisor);
+            let values = [0, 1, 2, 3, divisor - 1, divisor, divisor.wrapping_add(1), divisor.wrapping_mul(3), max - max % divisor, max / 2, max / 2 + 1, 12345, max - 2, max - 1, max, 100, 1000];
+
+            let mut quotients = values;
+            div_slice_u16(&mut quotients, reduced_divisor);
+            let mut remainders = values;
+            rem_slice_u16(&mut remainders, reduced_divisor);
+            let mut fused_quotients = values;
+            let mut fused_remainders = values;
+            div_rem_slice_u16(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+            assert_eq!(quotients, fused_quotients);
+            assert_eq!(remainders, fused_remainders);
+
+            for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+                assert_eq!(value / divisor, quotient, "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, remainder, "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_u32() {
+        let mut rng = StdRng::seed_from_u64(1064);
+        let max = u32::MAX;
+        let edge_divisors = [1, 2, 3, 4, 5, 6, 7, 10, 641, 1 << 16, (1 << 16) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, max - 1, max];
+        let random_divisors: [u32; 100] = core::array::from_fn(|_| (rng.gen::<u32>() >> rng.gen_range(0..31)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU32::new(divisor);
+
+            // 8 edge cases and 29 random numerators, so that the SIMD kernels have a scalar tail to finish
+            let mut values = [0u32; 37];
+            values[..8].copy_from_slice(&[0, 1, divisor - 1, divisor, divisor.wrapping_add(1), max - max % divisor, max - 1, max]);
+            for value in &mut values[8..] {
+                *value = rng.gen();
+            }
+
+            let mut quotients = values;
+            div_slice_u32(&mut quotients, reduced_divisor);
+            let mut remainders = values;
+            rem_slice_u32(&mut remainders, reduced_divisor);
+            let mut fused_quotients = values;
+            let mut fused_remainders = values;
+            div_rem_slice_u32(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+            assert_eq!(quotients, fused_quotients);
+            assert_eq!(remainders, fused_remainders);
+
+            for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+                assert_eq!(value / divisor, quotient, "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, remainder, "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_u64() {
+        let mut rng = StdRng::seed_from_u64(1065);
+        let max = u64::MAX;
+        let edge_divisors = [1, 2, 3, 4, 5, 6, 7, 10, 641, 1 << 32, (1 << 32) + 1, max / 3, max / 2, max / 2 + 1, max / 2 + 2, max - 1, max];
+        let random_divisors: [u64; 100] = core::array::from_fn(|_| (rng.gen::<u64>() >> rng.gen_range(0..63)).max(1));
+
+        for &divisor in edge_divisors.iter().chain(&random_divisors) {
+            let reduced_divisor = StrengthReducedU64::new(divisor);
+
+            // 8 edge cases and 29 random numerators, so that the AVX-512 kernels have a scalar tail to finish
+            let mut values = [0u64; 37];
+            values[..8].copy_from_slice(&[0, 1, divisor - 1, divisor, divisor.wrapping_add(1), max - max % divisor, max - 1, max]);
+            for value in &mut values[8..] {
+                *value = rng.gen();
+            }
+
+            let mut quotients = values;
+            div_slice_u64(&mut quotients, reduced_divisor);
+            let mut remainders = values;
+            rem_slice_u64(&mut remainders, reduced_divisor);
+            let mut fused_quotients = values;
+            let mut fused_remainders = values;
+            div_rem_slice_u64(&values, &mut fused_quotients, &mut fused_remainders, reduced_divisor);
+            assert_eq!(quotients, fused_quotients);
+            assert_eq!(remainders, fused_remainders);
+
+            for ((&value, &quotient), &remainder) in values.iter().zip(&quotients).zip(&remainders) {
+                assert_eq!(value / divisor, quotient, "Divide failed with numerator: {}, divisor: {}", value, divisor);
+                assert_eq!(value % divisor, remainder, "Modulo failed with numerator: {}, divisor: {}", value, divisor);
+            }
+        }
+    }
+}
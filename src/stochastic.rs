@@ -0,0 +1,123 @@
+use rand::RngCore;
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32};
+#[cfg(any(not(target_pointer_width = "64"), not(strength_reduce_no_u128)))]
+use ::StrengthReducedUsize;
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU64, StrengthReducedU128};
+#[cfg(not(strength_reduce_no_u128))]
+use ::long_multiplication;
+
+// To round up with probability remainder / divisor, we generate a random fraction in [0, 1) and round up if fraction * divisor < remainder.
+// With the fraction represented as a B-bit fixed point number, fraction * divisor is the upper half of a widening multiply, so we don't need to do any division.
+// The fraction only takes 2^B values, so the probability comes out as ceil(remainder * 2^B / divisor) / 2^B, which is too high by less than 2^-B.
+// The narrow types still draw a full 32-bit fraction, so that B doesn't shrink with the numerator type.
+macro_rules! stochastic_impl {
+    ($struct_name:ident, $primitive_type:ident, $fraction_type:ident, $fraction_bits:literal, $wide_type:ident, $random_fn:ident) => (
+        impl $struct_name {
+            /// Division with stochastic rounding. Rounds up with probability `remainder / divisor`, and down otherwise,
+            /// so that the expected value of the result is `numerator / divisor`.
+            ///
+            #[doc = concat!("The random fraction has ", $fraction_bits, " bits, so the probability of rounding up, and the expected value, are too high by less than 2^-", $fraction_bits, ".")]
+            ///
+            /// Useful for quantization, where always rounding in the same direction accumulates error.
+            #[inline]
+            pub fn div_stochastic<R: RngCore + ?Sized>(&self, numerator: $primitive_type, rng: &mut R) -> $primitive_type {
+                let (quotient, remainder) = $struct_name::div_rem(numerator, *self);
+                if remainder == 0 {
+                    return quotient;
+                }
+
+                let fraction = rng.$random_fn() as $fraction_type;
+                let scaled = ((fraction as $wide_type * self.get() as $wide_type) >> $fraction_bits) as $primitive_type;
+
+                // the remainder is nonzero, so the quotient can't be MAX
+                if scaled < remainder { quotient + 1 } else { quotient }
+            }
+        }
+    )
+}
+
+stochastic_impl!(StrengthReducedU8, u8, u32, 32, u64, next_u32);
+stochastic_impl!(StrengthReducedU16, u16, u32, 32, u64, next_u32);
+stochastic_impl!(StrengthReducedU32, u32, u32, 32, u64, next_u32);
+#[cfg(not(strength_reduce_no_u128))]
+stochastic_impl!(StrengthReducedU64, u64, u64, 64, u128, next_u64);
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+stochastic_impl!(StrengthReducedUsize, usize, u32, 32, u64, next_u32);
+#[cfg(all(target_pointer_width = "64", not(strength_reduce_no_u128)))]
+stochastic_impl!(StrengthReducedUsize, usize, u64, 64, u128, next_u64);
+
+#[cfg(not(strength_reduce_no_u128))]
+impl StrengthReducedU128 {
+    /// Division with stochastic rounding. Rounds up with probability `remainder / divisor`, and down otherwise,
+    /// so that the expected value of the result is `numerator / divisor`.
+    ///
+    /// The random fraction has 128 bits, so the probability of rounding up, and the expected value, are too high by less than 2^-128.
+    ///
+    /// Useful for quantization, where always rounding in the same direction accumulates error.
+    #[inline]
+    pub fn div_stochastic<R: RngCore + ?Sized>(&self, numerator: u128, rng: &mut R) -> u128 {
+        let (quotient, remainder) = StrengthReducedU128::div_rem(numerator, *self);
+        if remainder == 0 {
+            return quotient;
+        }
+
+        let fraction = ((rng.next_u64() as u128) << 64) | (rng.next_u64() as u128);
+        let scaled = long_multiplication::multiply_128_by_128_upperbits(fraction, self.get());
+
+        // the remainder is nonzero, so the quotient can't be MAX
+        if scaled < remainder { quotient + 1 } else { quotient }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    macro_rules! stochastic_test {
+        ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+            #[test]
+            fn $test_name() {
+                let mut rng = StdRng::seed_from_u64(1234567);
+                let max = $primitive_type::MAX;
+                let divisors = test_divisors!($primitive_type);
+                let numerators = [0, 1, 5, 99, 100, 101, max / 2, max - 1, max];
+
+                for &divisor in &divisors {
+                    let reduced_divisor = $struct_name::new(divisor);
+                    for &numerator in &numerators {
+                        let quotient = numerator / divisor;
+                        let remainder = numerator % divisor;
+
+                        // count how often we round up, and make sure it's roughly proportional to the remainder
+                        let trials = 4000;
+                        let mut round_up_count = 0;
+                        for _ in 0..trials {
+                            let result = reduced_divisor.div_stochastic(numerator, &mut rng);
+                            if result != quotient {
+                                assert_eq!(result, quotient + 1, "div_stochastic rounded the wrong way for numerator: {}, divisor: {}", numerator, divisor);
+                                round_up_count += 1;
+                            }
+                        }
+
+                        let expected_fraction = remainder as f64 / divisor as f64;
+                        let actual_fraction = round_up_count as f64 / trials as f64;
+                        assert!((expected_fraction - actual_fraction).abs() < 0.05, "div_stochastic rounded up {} of the time, expected {} for numerator: {}, divisor: {}", actual_fraction, expected_fraction, numerator, divisor);
+                    }
+                }
+            }
+        )
+    }
+
+    stochastic_test!(test_stochastic_u8, StrengthReducedU8, u8);
+    stochastic_test!(test_stochastic_u16, StrengthReducedU16, u16);
+    stochastic_test!(test_stochastic_u32, StrengthReducedU32, u32);
+    #[cfg(not(strength_reduce_no_u128))]
+    stochastic_test!(test_stochastic_u64, StrengthReducedU64, u64);
+    #[cfg(any(not(target_pointer_width = "64"), not(strength_reduce_no_u128)))]
+    stochastic_test!(test_stochastic_usize, StrengthReducedUsize, usize);
+    #[cfg(not(strength_reduce_no_u128))]
+    stochastic_test!(test_stochastic_u128, StrengthReducedU128, u128);
+}
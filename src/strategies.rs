@@ -0,0 +1,128 @@
+//! Proptest strategies for divisors, so downstream property tests can generate them directly.
+//!
+//! Uniformly random divisors almost never hit the cases that strength reduction handles specially, so every strategy here mixes random divisors
+//! with the edge cases: 1, 2, every power of two, `MAX - 1`, and `MAX`, and for the signed types, their negations and `MIN`. These are the same cases that this crate's own tests check.
+//!
+//! Every divisor type also implements proptest's `Arbitrary` with the matching strategy, so `any::<StrengthReducedU32>()` works.
+//!
+//! ```
+//! # #[macro_use] extern crate proptest;
+//! # extern crate strength_reduce;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//! use strength_reduce::StrengthReducedU32;
+//!
+//! # fn main() {
+//! TestRunner::default().run(&(any::<StrengthReducedU32>(), any::<u32>()), |(divisor, numerator)| {
+//!     prop_assert_eq!(numerator / divisor, numerator / divisor.get());
+//!     Ok(())
+//! }).unwrap();
+//! # }
+//! ```
+use proptest::arbitrary::Arbitrary;
+use proptest::sample::select;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use ::{StrengthReducedU8, StrengthReducedU16, StrengthReducedU32, StrengthReducedU64, StrengthReducedUsize};
+use ::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+use ::{StrengthReducedU128, StrengthReducedI128};
+
+macro_rules! unsigned_strategy {
+    ($strategy_fn:ident, $struct_name:ident, $primitive_type:ident) => (
+        #[doc = concat!("Generates nonzero `", stringify!($primitive_type), "` divisors, biased toward 1, 2, powers of two, `MAX - 1`, and `MAX`.")]
+        pub fn $strategy_fn() -> impl Strategy<Value = $primitive_type> {
+            prop_oneof![
+                2 => 1..=$primitive_type::MAX,
+                1 => select(&[1, 2, $primitive_type::MAX - 1, $primitive_type::MAX][..]),
+                1 => (0..$primitive_type::BITS).prop_map(|shift| 1 << shift),
+            ]
+        }
+
+        impl Arbitrary for $struct_name {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                $strategy_fn().prop_map($struct_name::new).boxed()
+            }
+        }
+    )
+}
+
+macro_rules! signed_strategy {
+    ($strategy_fn:ident, $struct_name:ident, $primitive_type:ident) => (
+        #[doc = concat!("Generates nonzero `", stringify!($primitive_type), "` divisors, biased toward ±1, ±2, powers of two and their negations, `MAX - 1`, `MAX`, `MIN + 1`, and `MIN`.")]
+        pub fn $strategy_fn() -> impl Strategy<Value = $primitive_type> {
+            prop_oneof![
+                2 => ($primitive_type::MIN..=$primitive_type::MAX).prop_filter("divisor must be nonzero", |&divisor| divisor != 0),
+                1 => select(&[1, -1, 2, -2, $primitive_type::MAX - 1, $primitive_type::MAX, $primitive_type::MIN + 1, $primitive_type::MIN][..]),
+                // MIN is the negation of the largest power of two, which doesn't fit as a positive value
+                1 => (0..$primitive_type::BITS, proptest::bool::ANY).prop_map(|(shift, negate)| {
+                    let power = (1 as $primitive_type).wrapping_shl(shift);
+                    if negate { power.wrapping_neg() } else { power }
+                }),
+            ]
+        }
+
+        impl Arbitrary for $struct_name {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                $strategy_fn().prop_map($struct_name::new).boxed()
+            }
+        }
+    )
+}
+
+unsigned_strategy!(divisor_u8, StrengthReducedU8, u8);
+unsigned_strategy!(divisor_u16, StrengthReducedU16, u16);
+unsigned_strategy!(divisor_u32, StrengthReducedU32, u32);
+unsigned_strategy!(divisor_u64, StrengthReducedU64, u64);
+unsigned_strategy!(divisor_usize, StrengthReducedUsize, usize);
+#[cfg(not(strength_reduce_no_u128))]
+unsigned_strategy!(divisor_u128, StrengthReducedU128, u128);
+signed_strategy!(divisor_i8, StrengthReducedI8, i8);
+signed_strategy!(divisor_i16, StrengthReducedI16, i16);
+signed_strategy!(divisor_i32, StrengthReducedI32, i32);
+signed_strategy!(divisor_i64, StrengthReducedI64, i64);
+signed_strategy!(divisor_isize, StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+signed_strategy!(divisor_i128, StrengthReducedI128, i128);
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    #[test]
+    fn test_strategies_hit_edge_cases() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = divisor_u64();
+        let (mut ones, mut maxes, mut powers_of_two) = (0, 0, 0);
+        for _ in 0..1000 {
+            let divisor = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(divisor != 0);
+            ones += (divisor == 1) as u32;
+            maxes += (divisor == u64::MAX) as u32;
+            powers_of_two += divisor.is_power_of_two() as u32;
+        }
+        assert!(ones > 0 && maxes > 0 && powers_of_two > 100);
+
+        let strategy = divisor_i32();
+        for _ in 0..1000 {
+            assert!(strategy.new_tree(&mut runner).unwrap().current() != 0);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_divisors(divisor: StrengthReducedI16, unsigned_divisor: StrengthReducedU16, numerator: i16, unsigned_numerator: u16) {
+            prop_assume!(!(numerator == i16::MIN && divisor.get() == -1));
+            prop_assert_eq!(numerator / divisor.get(), numerator / divisor);
+            prop_assert_eq!(unsigned_numerator % unsigned_divisor.get(), unsigned_numerator % unsigned_divisor);
+        }
+    }
+}
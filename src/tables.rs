@@ -0,0 +1,117 @@
+#[cfg(all(feature = "small-divisor-tables", not(strength_reduce_no_u128)))]
+use ::long_division;
+
+// Precomputed multipliers for every divisor below 256, so that tiny divisors can skip the wide division in new()
+// Powers of two are stored as 0, to match the flag new() uses for them. Entry 0 is unused.
+
+// Every u8 divisor fits in this table, and it's only 512 bytes, so StrengthReducedU8 always uses it
+pub(crate) const MULTIPLIERS_U16: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut divisor = 1;
+    while divisor < 256 {
+        if !(divisor as u16).is_power_of_two() {
+            table[divisor] = u16::MAX / divisor as u16 + 1;
+        }
+        divisor += 1;
+    }
+    table
+};
+
+#[cfg(feature = "small-divisor-tables")]
+pub(crate) const SMALL_MULTIPLIERS_U32: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut divisor = 1;
+    while divisor < 256 {
+        if !(divisor as u32).is_power_of_two() {
+            table[divisor] = u32::MAX / divisor as u32 + 1;
+        }
+        divisor += 1;
+    }
+    table
+};
+
+#[cfg(feature = "small-divisor-tables")]
+pub(crate) const SMALL_MULTIPLIERS_U64: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut divisor = 1;
+    while divisor < 256 {
+        if !(divisor as u64).is_power_of_two() {
+            table[divisor] = u64::MAX / divisor as u64 + 1;
+        }
+        divisor += 1;
+    }
+    table
+};
+
+#[cfg(all(feature = "small-divisor-tables", not(strength_reduce_no_u128)))]
+pub(crate) const SMALL_MULTIPLIERS_U128: [u128; 256] = {
+    let mut table = [0; 256];
+    let mut divisor = 1;
+    while divisor < 256 {
+        if !(divisor as u64).is_power_of_two() {
+            table[divisor] = long_division::divide_128_max_by_64(divisor as u64) + 1;
+        }
+        divisor += 1;
+    }
+    table
+};
+
+// Möller and Granlund's 11-bit reciprocal approximations, used to seed the reciprocal of a 64-bit divisor with its top bit set
+// Entry i is floor((2^19 - 3 * 2^8) / (i + 256)), so that it can be indexed by the divisor's top 9 bits, minus 256
+#[cfg(not(strength_reduce_no_u128))]
+pub(crate) const RECIPROCAL_SEEDS: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        table[index] = (((1u32 << 19) - 3 * (1 << 8)) / (index as u32 + 256)) as u16;
+        index += 1;
+    }
+    table
+};
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    #[cfg(feature = "small-divisor-tables")]
+    use ::{StrengthReducedU16, StrengthReducedU32, StrengthReducedU64};
+
+    #[test]
+    fn test_u8_multiplier_table() {
+        assert_eq!(0, MULTIPLIERS_U16[1]);
+        for (divisor, &multiplier) in MULTIPLIERS_U16.iter().enumerate().skip(2) {
+            let expected = if divisor.is_power_of_two() { 0 } else { (u16::MAX / divisor as u16) + 1 };
+            assert_eq!(expected, multiplier, "table entry is wrong for divisor: {}", divisor);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "small-divisor-tables")]
+    fn test_small_multiplier_tables() {
+        for divisor in 1..256usize {
+            if divisor.is_power_of_two() {
+                assert_eq!(0, SMALL_MULTIPLIERS_U32[divisor]);
+                assert_eq!(0, SMALL_MULTIPLIERS_U64[divisor]);
+                #[cfg(not(strength_reduce_no_u128))]
+                assert_eq!(0, SMALL_MULTIPLIERS_U128[divisor]);
+            } else {
+                assert_eq!(u32::MAX / divisor as u32 + 1, SMALL_MULTIPLIERS_U32[divisor]);
+                assert_eq!(u64::MAX / divisor as u64 + 1, SMALL_MULTIPLIERS_U64[divisor]);
+                #[cfg(not(strength_reduce_no_u128))]
+                assert_eq!(u128::MAX / divisor as u128 + 1, SMALL_MULTIPLIERS_U128[divisor]);
+            }
+
+            let reduced_u16 = StrengthReducedU16::new(divisor as u16);
+            let reduced_u32 = StrengthReducedU32::new(divisor as u32);
+            let reduced_u64 = StrengthReducedU64::new(divisor as u64);
+            for &numerator in &[0, 1, 254, 255, 256, 257, 12345, u16::MAX - 1, u16::MAX] {
+                assert_eq!(numerator / divisor as u16, numerator / reduced_u16, "u16 divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator as u32 / divisor as u32, numerator as u32 / reduced_u32, "u32 divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator as u64 / divisor as u64, numerator as u64 / reduced_u64, "u64 divide failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+            for &numerator in &[u64::MAX / 3, u64::MAX - 1, u64::MAX] {
+                assert_eq!(numerator / divisor as u64, numerator / reduced_u64, "u64 divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator % divisor as u64, numerator % reduced_u64, "u64 modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// Linked for thumbv6m-none-eabi by tests/no_division_libcalls.rs. Every operation reads its operands through a pointer, so nothing can be constant-folded,
+// and writes its result through a volatile pointer, so nothing can be optimized out. If any of them divides, the division routine ends up in the binary.
+#![no_std]
+#![no_main]
+extern crate strength_reduce;
+
+use core::ptr::{read_unaligned, write_volatile};
+use strength_reduce::*;
+
+type ConstDivisorProbe = ConstDivisor<1_000_000_007>;
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+macro_rules! probe {
+    ($numerators:ident, $divisors:ident, $out:ident, $struct_name:ident, $primitive_type:ident) => ({
+        let numerator = read_unaligned($numerators as *const $primitive_type);
+        let divisor = read_unaligned($divisors as *const $struct_name);
+        let (quotient, remainder) = $struct_name::div_rem(numerator, divisor);
+        write_volatile($out as *mut $primitive_type, numerator / divisor);
+        write_volatile($out as *mut $primitive_type, numerator % divisor);
+        write_volatile($out as *mut $primitive_type, quotient);
+        write_volatile($out as *mut $primitive_type, remainder);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn probe_entry(numerators: *const u8, divisors: *const u8, out: *mut u8) {
+    probe!(numerators, divisors, out, StrengthReducedU8, u8);
+    probe!(numerators, divisors, out, StrengthReducedU16, u16);
+    probe!(numerators, divisors, out, StrengthReducedU32, u32);
+    probe!(numerators, divisors, out, StrengthReducedU64, u64);
+    #[cfg(not(strength_reduce_no_u128))]
+    probe!(numerators, divisors, out, StrengthReducedU128, u128);
+    probe!(numerators, divisors, out, StrengthReducedUsize, usize);
+    probe!(numerators, divisors, out, StrengthReducedI8, i8);
+    probe!(numerators, divisors, out, StrengthReducedI16, i16);
+    probe!(numerators, divisors, out, StrengthReducedI32, i32);
+    probe!(numerators, divisors, out, StrengthReducedI64, i64);
+    #[cfg(not(strength_reduce_no_u128))]
+    probe!(numerators, divisors, out, StrengthReducedI128, i128);
+    probe!(numerators, divisors, out, StrengthReducedIsize, isize);
+    probe!(numerators, divisors, out, StrengthReducedU64Narrow, u32);
+    probe!(numerators, divisors, out, ConstDivisorProbe, u64);
+}
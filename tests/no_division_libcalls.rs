@@ -0,0 +1,81 @@
+// Checks that `Div`, `Rem`, and `div_rem` never divide, on a target where every division is a libcall.
+// Builds the crate for thumbv6m-none-eabi (Cortex-M0, which has no hardware divide), links tests/division_probe/probe.rs against it, and fails if any of
+// compiler-builtins' division routines made it into the binary. The linker only keeps what's reachable, so this catches divisions anywhere in the call graph, including inside helpers.
+// Skipped, with a message, if the thumbv6m-none-eabi target isn't installed.
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv6m-none-eabi";
+
+// the ARM EABI routines, plus the generic ones they and the 128-bit operators call into
+const DIVISION_ROUTINES: &[&str] = &[
+    "__aeabi_uidiv", "__aeabi_uidivmod", "__aeabi_idiv", "__aeabi_idivmod", "__aeabi_uldivmod", "__aeabi_ldivmod",
+    "__udivsi3", "__umodsi3", "__divsi3", "__modsi3", "__udivdi3", "__umoddi3", "__divdi3", "__moddi3", "__udivmoddi4",
+    "__udivti3", "__umodti3", "__divti3", "__modti3", "__udivmodti4",
+];
+
+fn rustc() -> Command {
+    Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+fn target_installed() -> bool {
+    let output = rustc().args(["--print", "target-libdir", "--target", TARGET]).output().expect("failed to run rustc");
+    output.status.success() && Path::new(String::from_utf8_lossy(&output.stdout).trim()).exists()
+}
+
+fn run(command: &mut Command) {
+    let status = command.status().expect("failed to run the probe build");
+    assert!(status.success(), "probe build failed: {:?}", command);
+}
+
+// builds the crate with the given cfg, links the probe against it, and returns the linked binary
+fn link_probe(name: &str, cfg: Option<&str>) -> Vec<u8> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("division_probe").join(name);
+    // the crate and the probe have to agree on the cfg, so this replaces whatever RUSTFLAGS the test itself was built with
+    let rustflags = cfg.map(|cfg| format!("--cfg {}", cfg)).unwrap_or_default();
+    run(Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .env("RUSTFLAGS", rustflags)
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .args(["build", "--release", "--lib", "--target", TARGET, "--target-dir"])
+        .arg(&target_dir));
+
+    let release_dir = target_dir.join(TARGET).join("release");
+    let binary = target_dir.join("probe.elf");
+    let mut link = rustc();
+    link.args(["--edition", "2015", "--crate-type", "bin", "--target", TARGET])
+        .args(["-C", "opt-level=3", "-C", "panic=abort", "-C", "strip=debuginfo", "-C", "link-arg=--entry=probe_entry"])
+        .arg("-L").arg(format!("dependency={}", release_dir.join("deps").display()))
+        .arg("--extern").arg(format!("strength_reduce={}", release_dir.join("libstrength_reduce.rlib").display()))
+        .arg("-o").arg(&binary)
+        .arg(manifest_dir.join("tests").join("division_probe").join("probe.rs"));
+    if let Some(cfg) = cfg {
+        link.args(["--cfg", cfg]);
+    }
+    run(&mut link);
+
+    fs::read(&binary).expect("failed to read the probe binary")
+}
+
+fn assert_no_division_routines(binary: &[u8], name: &str) {
+    // symbol names are nul-terminated in the string table, so this doesn't mistake __aeabi_uidivmod for __aeabi_uidiv
+    let found: Vec<&str> = DIVISION_ROUTINES.iter().cloned().filter(|routine| {
+        let needle: Vec<u8> = routine.bytes().chain(Some(0)).collect();
+        binary.windows(needle.len()).any(|window| window == &needle[..])
+    }).collect();
+    assert!(found.is_empty(), "{} division probe links against {:?}, so a division reached Div, Rem, or div_rem", name, found);
+}
+
+#[test]
+fn test_no_division_libcalls() {
+    if !target_installed() {
+        eprintln!("skipping: the {} target isn't installed. Install it with `rustup target add {}` to run this test", TARGET, TARGET);
+        return;
+    }
+
+    assert_no_division_routines(&link_probe("default", None), "default");
+    assert_no_division_routines(&link_probe("no_u128", Some("strength_reduce_no_u128")), "strength_reduce_no_u128");
+}
@@ -0,0 +1,135 @@
+#[macro_use]
+extern crate proptest;
+extern crate strength_reduce;
+
+use proptest::test_runner::Config;
+use strength_reduce::{StrengthReducedI8, StrengthReducedI16, StrengthReducedI32, StrengthReducedI64, StrengthReducedIsize};
+#[cfg(not(strength_reduce_no_u128))]
+use strength_reduce::StrengthReducedI128;
+
+
+macro_rules! signed_reduction_proptest {
+    ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+        mod $test_name {
+            use super::*;
+            use proptest::sample::select;
+
+            fn assert_div_rem_equivalence(divisor: $primitive_type, numerator: $primitive_type) {
+                let reduced_divisor = $struct_name::new(divisor);
+                let expected_div = numerator / divisor;
+                let expected_rem = numerator % divisor;
+                let reduced_div = numerator / reduced_divisor;
+                let reduced_rem = numerator % reduced_divisor;
+                assert_eq!(expected_div, reduced_div, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected_rem, reduced_rem, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                let (reduced_combined_div, reduced_combined_rem) = $struct_name::div_rem(numerator, reduced_divisor);
+                assert_eq!(expected_div, reduced_combined_div, "div_rem divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected_rem, reduced_combined_rem, "div_rem modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+
+            fn assert_euclid_equivalence(divisor: $primitive_type, numerator: $primitive_type) {
+                let reduced_divisor = $struct_name::new(divisor);
+                assert_eq!(numerator.div_euclid(divisor), reduced_divisor.div_euclid(numerator), "div_euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(numerator.rem_euclid(divisor), reduced_divisor.rem_euclid(numerator), "rem_euclid failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+
+            fn assert_floor_equivalence(divisor: $primitive_type, numerator: $primitive_type) {
+                let reduced_divisor = $struct_name::new(divisor);
+                let expected_div = numerator / divisor;
+                let expected_rem = numerator % divisor;
+                let (expected_floor_div, expected_floor_mod) = if expected_rem != 0 && (expected_rem < 0) != (divisor < 0) {
+                    (expected_div - 1, expected_rem + divisor)
+                } else {
+                    (expected_div, expected_rem)
+                };
+                assert_eq!(expected_floor_div, reduced_divisor.div_floor(numerator), "div_floor failed with numerator: {}, divisor: {}", numerator, divisor);
+                assert_eq!(expected_floor_mod, reduced_divisor.mod_floor(numerator), "mod_floor failed with numerator: {}, divisor: {}", numerator, divisor);
+            }
+
+            fn edge_cases() -> Vec<$primitive_type> {
+                vec![$primitive_type::MIN, $primitive_type::MIN + 1, -2, -1, 1, 2, $primitive_type::MAX - 1, $primitive_type::MAX]
+            }
+
+            proptest! {
+                #![proptest_config(Config::with_cases(100_000))]
+
+                #[test]
+                fn fully_generated_inputs_are_div_rem_equivalent(divisor in $primitive_type::MIN..$primitive_type::MAX, numerator in $primitive_type::MIN..$primitive_type::MAX) {
+                    prop_assume!(divisor != 0 && !(divisor == -1 && numerator == $primitive_type::MIN));
+                    assert_div_rem_equivalence(divisor, numerator);
+                }
+
+                #[test]
+                fn generated_divisors_with_edge_case_numerators_are_div_rem_equivalent(
+                        divisor in $primitive_type::MIN..$primitive_type::MAX,
+                        numerator in select(edge_cases())) {
+                    prop_assume!(divisor != 0 && !(divisor == -1 && numerator == $primitive_type::MIN));
+                    assert_div_rem_equivalence(divisor, numerator);
+                }
+
+                #[test]
+                fn generated_numerators_with_edge_case_divisors_are_div_rem_equivalent(
+                        divisor in select(edge_cases()),
+                        numerator in $primitive_type::MIN..$primitive_type::MAX) {
+                    prop_assume!(!(divisor == -1 && numerator == $primitive_type::MIN));
+                    assert_div_rem_equivalence(divisor, numerator);
+                }
+
+                #[test]
+                fn generated_inputs_are_euclid_equivalent(divisor in $primitive_type::MIN..$primitive_type::MAX, numerator in $primitive_type::MIN..$primitive_type::MAX) {
+                    prop_assume!(divisor != 0 && !(divisor == -1 && numerator == $primitive_type::MIN));
+                    assert_euclid_equivalence(divisor, numerator);
+                }
+
+                #[test]
+                fn generated_inputs_are_floor_equivalent(divisor in $primitive_type::MIN..$primitive_type::MAX, numerator in $primitive_type::MIN..$primitive_type::MAX) {
+                    prop_assume!(divisor != 0 && !(divisor == -1 && numerator == $primitive_type::MIN));
+                    assert_floor_equivalence(divisor, numerator);
+                }
+            }
+        }
+    )
+}
+signed_reduction_proptest!(strength_reduced_i08, StrengthReducedI8, i8);
+signed_reduction_proptest!(strength_reduced_i16, StrengthReducedI16, i16);
+signed_reduction_proptest!(strength_reduced_i32, StrengthReducedI32, i32);
+signed_reduction_proptest!(strength_reduced_i64, StrengthReducedI64, i64);
+signed_reduction_proptest!(strength_reduced_isize, StrengthReducedIsize, isize);
+#[cfg(not(strength_reduce_no_u128))]
+signed_reduction_proptest!(strength_reduced_i128, StrengthReducedI128, i128);
+
+macro_rules! exhaustive_test {
+    ($test_name:ident, $struct_name:ident, $primitive_type:ident) => (
+        #[test]
+        #[ignore]
+        fn $test_name() {
+            for divisor in $primitive_type::MIN..=$primitive_type::MAX {
+                if divisor == 0 {
+                    continue;
+                }
+                let reduced_divisor = $struct_name::new(divisor);
+
+                for numerator in $primitive_type::MIN..=$primitive_type::MAX {
+                    if numerator == $primitive_type::MIN && divisor == -1 {
+                        continue;
+                    }
+                    let expected_div = numerator / divisor;
+                    let expected_rem = numerator % divisor;
+
+                    let reduced_div = numerator / reduced_divisor;
+                    assert_eq!(expected_div, reduced_div, "Divide failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                    let reduced_rem = numerator % reduced_divisor;
+                    assert_eq!(expected_rem, reduced_rem, "Modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+
+                    let (reduced_combined_div, reduced_combined_rem) = $struct_name::div_rem(numerator, reduced_divisor);
+                    assert_eq!(expected_div, reduced_combined_div, "div_rem divide failed with numerator: {}, divisor: {}", numerator, divisor);
+                    assert_eq!(expected_rem, reduced_combined_rem, "div_rem modulo failed with numerator: {}, divisor: {}", numerator, divisor);
+                }
+            }
+        }
+    )
+}
+
+exhaustive_test!(test_strength_reduced_i08_exhaustive, StrengthReducedI8, i8);
+exhaustive_test!(test_strength_reduced_i16_exhaustive, StrengthReducedI16, i16);